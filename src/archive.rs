@@ -0,0 +1,328 @@
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::cli::{ArchiveArgs, RestoreArgs};
+use crate::pins::{self, Pins};
+use crate::sizes;
+use crate::utils;
+
+/// Default archive folder inside the tries directory (`archive_path` overrides it).
+pub const ARCHIVE_DIR_NAME: &str = ".archive";
+
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    /// tar + zstd, small and fast
+    #[default]
+    Zst,
+    /// tar + gzip, readable everywhere
+    Gz,
+}
+
+impl ArchiveFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ArchiveFormat::Zst => "tar.zst",
+            ArchiveFormat::Gz => "tar.gz",
+        }
+    }
+
+    /// Splits an archive file name into the experiment name and its format.
+    /// The `~N` counter added when the name was already archived is dropped.
+    pub fn from_file_name(file_name: &str) -> Option<(String, ArchiveFormat)> {
+        [ArchiveFormat::Zst, ArchiveFormat::Gz]
+            .into_iter()
+            .find_map(|format| {
+                file_name
+                    .strip_suffix(format.extension())
+                    .and_then(|stem| stem.strip_suffix('.'))
+                    .filter(|stem| !stem.is_empty())
+                    .map(|stem| (strip_counter(stem).to_string(), format))
+            })
+    }
+}
+
+/// `name~2` -> `name`, other names are kept as they are.
+fn strip_counter(stem: &str) -> &str {
+    match stem.rsplit_once('~') {
+        Some((name, n))
+            if !name.is_empty() && !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            name
+        }
+        _ => stem,
+    }
+}
+
+/// A packed experiment in the archive folder.
+#[derive(Clone, Debug)]
+pub struct ArchiveEntry {
+    /// Folder name the archive restores to, date prefix included
+    pub name: String,
+    pub path: PathBuf,
+    pub format: ArchiveFormat,
+    /// Compressed size in bytes
+    pub size: u64,
+    pub archived: SystemTime,
+}
+
+/// Packs `tries_dir/name` into the archive folder and removes the original.
+/// Git worktrees are refused because their `.git` file would dangle after a restore.
+pub fn archive_folder(
+    tries_dir: &Path,
+    archive_dir: &Path,
+    name: &str,
+    format: ArchiveFormat,
+) -> Result<ArchiveEntry> {
+    let source = tries_dir.join(name);
+    if !source.is_dir() {
+        bail!("'{}' does not exist", name);
+    }
+    if utils::is_git_worktree(&source) {
+        bail!(
+            "'{}' is a git worktree, remove it instead of archiving",
+            name
+        );
+    }
+    fs::create_dir_all(archive_dir)?;
+
+    let mut file_name = format!("{}.{}", name, format.extension());
+    let mut n = 1;
+    while archive_dir.join(&file_name).exists() {
+        n += 1;
+        file_name = format!("{}~{}.{}", name, n, format.extension());
+    }
+    let path = archive_dir.join(&file_name);
+    let partial = archive_dir.join(format!("{file_name}.partial"));
+
+    if let Err(e) = write_archive(&source, &partial, format) {
+        let _ = fs::remove_file(&partial);
+        return Err(e).with_context(|| format!("could not archive '{}'", name));
+    }
+    fs::rename(&partial, &path)?;
+    fs::remove_dir_all(&source)
+        .with_context(|| format!("archived '{}' but could not remove it", name))?;
+
+    let metadata = fs::metadata(&path)?;
+    Ok(ArchiveEntry {
+        name: ArchiveFormat::from_file_name(&file_name)
+            .map(|(name, _)| name)
+            .unwrap_or_else(|| name.to_string()),
+        path,
+        format,
+        size: metadata.len(),
+        archived: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+    })
+}
+
+fn write_archive(source: &Path, dest: &Path, format: ArchiveFormat) -> Result<()> {
+    let file = BufWriter::new(File::create(dest)?);
+    match format {
+        ArchiveFormat::Zst => {
+            let encoder = zstd::Encoder::new(file, 0)?;
+            finish_tar(source, encoder)?.finish()?.flush()?;
+        }
+        ArchiveFormat::Gz => {
+            let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            finish_tar(source, encoder)?.finish()?.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the folder content (not the folder itself) into a tar stream.
+fn finish_tar<W: Write>(source: &Path, writer: W) -> io::Result<W> {
+    let mut builder = tar::Builder::new(writer);
+    builder.follow_symlinks(false);
+    builder.append_dir_all(".", source)?;
+    builder.into_inner()
+}
+
+/// Returns all archives, most recently archived first.
+pub fn list_archives(archive_dir: &Path) -> Vec<ArchiveEntry> {
+    let mut archives = Vec::new();
+    if let Ok(read_dir) = fs::read_dir(archive_dir) {
+        for entry in read_dir.flatten() {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if let Some((name, format)) = ArchiveFormat::from_file_name(&file_name)
+                && let Ok(metadata) = entry.metadata()
+                && metadata.is_file()
+            {
+                archives.push(ArchiveEntry {
+                    name,
+                    path: entry.path(),
+                    format,
+                    size: metadata.len(),
+                    archived: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                });
+            }
+        }
+    }
+    archives.sort_by_key(|a| Reverse(a.archived));
+    archives
+}
+
+/// Fuzzy-matches archives by name, best match first. An empty query keeps all.
+pub fn search_archives(archives: Vec<ArchiveEntry>, query: &str) -> Vec<ArchiveEntry> {
+    if query.is_empty() {
+        return archives;
+    }
+    let matcher = SkimMatcherV2::default();
+    let mut scored: Vec<(i64, ArchiveEntry)> = archives
+        .into_iter()
+        .filter_map(|a| matcher.fuzzy_match(&a.name, query).map(|score| (score, a)))
+        .collect();
+    scored.sort_by_key(|(score, _)| Reverse(*score));
+    scored.into_iter().map(|(_, a)| a).collect()
+}
+
+/// Unpacks an archive into `tries_dir` under its original name and deletes the
+/// archive unless `keep` is set. Returns the path of the restored folder.
+pub fn restore_archive(archive: &ArchiveEntry, tries_dir: &Path, keep: bool) -> Result<PathBuf> {
+    let target = tries_dir.join(&archive.name);
+    if target.exists() {
+        bail!("'{}' already exists", archive.name);
+    }
+    // Unpack next to the target first so a failure leaves no half-restored folder
+    let staging = tries_dir.join(format!(".{}.restoring", archive.name));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging)?;
+
+    let file = BufReader::new(File::open(&archive.path)?);
+    let result = match archive.format {
+        ArchiveFormat::Zst => unpack(zstd::Decoder::new(file)?, &staging),
+        ArchiveFormat::Gz => unpack(flate2::read::GzDecoder::new(file), &staging),
+    };
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e).with_context(|| format!("could not unpack '{}'", archive.name));
+    }
+    fs::rename(&staging, &target)?;
+
+    if !keep {
+        fs::remove_file(&archive.path)?;
+    }
+    Ok(target)
+}
+
+fn unpack<R: Read>(reader: R, dest: &Path) -> io::Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    archive.set_preserve_mtime(true);
+    archive.set_preserve_permissions(true);
+    archive.unpack(dest)
+}
+
+/// Handles `try-rs archive`.
+pub fn run_archive(
+    tries_dir: &Path,
+    archive_dir: &Path,
+    args: &ArchiveArgs,
+    default_format: ArchiveFormat,
+) -> Result<()> {
+    let name = if tries_dir.join(&args.name).is_dir() {
+        args.name.clone()
+    } else {
+        let matches = utils::matching_folders(&args.name, &tries_dir.to_path_buf());
+        match matches.as_slice() {
+            [name] => name.clone(),
+            [] => bail!("No experiment named '{}'", args.name),
+            _ => bail!(
+                "'{}' is ambiguous, use the full name: {}",
+                args.name,
+                matches.join(", ")
+            ),
+        }
+    };
+    let archive = archive_folder(
+        tries_dir,
+        archive_dir,
+        &name,
+        args.format.unwrap_or(default_format),
+    )?;
+    // The folder is gone, a restore starts unpinned
+    let pins_path = pins::pins_path();
+    let mut pins = Pins::load(&pins_path);
+    if pins.unpin(&tries_dir.join(&name)) {
+        let _ = pins.save(&pins_path);
+    }
+    eprintln!(
+        "Archived: {} -> {} ({})",
+        name,
+        archive.path.to_string_lossy(),
+        sizes::format_long(archive.size)
+    );
+    Ok(())
+}
+
+/// Handles `try-rs restore`.
+pub fn run_restore(tries_dir: &Path, archive_dir: &Path, args: &RestoreArgs) -> Result<()> {
+    let query = args.query.as_deref().unwrap_or("");
+    let archives = list_archives(archive_dir);
+    // The same name may have been archived several times
+    let exact: Vec<ArchiveEntry> = archives
+        .iter()
+        .filter(|a| a.name == query)
+        .cloned()
+        .collect();
+    let matches = if exact.is_empty() {
+        search_archives(archives, query)
+    } else {
+        exact
+    };
+
+    if matches.is_empty() {
+        if query.is_empty() {
+            bail!("No archived experiments");
+        }
+        bail!("No archive matching '{}'", query);
+    }
+
+    if args.list || matches.len() > 1 {
+        for (i, archive) in matches.iter().enumerate() {
+            let archived: chrono::DateTime<chrono::Local> = archive.archived.into();
+            println!(
+                "{:>3}. {} ({}, archived {})",
+                i + 1,
+                archive.name,
+                sizes::format_long(archive.size),
+                archived.format("%Y-%m-%d")
+            );
+        }
+        if args.list {
+            return Ok(());
+        }
+    }
+
+    let archive = if matches.len() == 1 {
+        &matches[0]
+    } else {
+        eprint!("Restore which archive? [1-{}]: ", matches.len());
+        io::stderr().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        match input
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| matches.get(n.wrapping_sub(1)))
+        {
+            Some(archive) => archive,
+            None => {
+                eprintln!("Aborted.");
+                return Ok(());
+            }
+        }
+    };
+
+    let target = restore_archive(archive, tries_dir, args.keep)?;
+    eprintln!("Restored: {}", target.to_string_lossy());
+    Ok(())
+}
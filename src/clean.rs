@@ -0,0 +1,187 @@
+use anyhow::Result;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+use crate::cli::CleanArgs;
+use crate::entries::load_entries;
+use crate::fork::artifact_patterns;
+use crate::projects::ProjectDetector;
+use crate::prune::stale_entries;
+use crate::sizes::{self, SizeCache, folder_size};
+use crate::utils::glob_match;
+
+/// A build artifact directory of an experiment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Artifact {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Finds the directories directly inside the project root `folder` matching
+/// `patterns`. Deeper folders are never matched, a `build` package in the
+/// sources is not the build output. In git repositories, directories holding
+/// tracked files are left alone.
+pub fn find_artifacts(folder: &Path, patterns: &[String]) -> Vec<Artifact> {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return Vec::new();
+    };
+    let is_git = folder.join(".git").exists();
+    let mut found = Vec::new();
+    for entry in entries.flatten() {
+        if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if patterns.iter().any(|p| glob_match(p, &name))
+            && (!is_git || !has_tracked_files(folder, &path))
+        {
+            let bytes = folder_size(&path, &[]).total;
+            found.push(Artifact { path, bytes });
+        }
+    }
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    found
+}
+
+/// Whether git tracks anything below `path`. Errors count as tracked so a
+/// failing git never leads to removing sources.
+fn has_tracked_files(repo: &Path, path: &Path) -> bool {
+    let output = Command::new("git")
+        .args(["ls-files", "--"])
+        .arg(path)
+        .current_dir(repo)
+        .output();
+    match output {
+        Ok(output) if output.status.success() => !output.stdout.is_empty(),
+        _ => true,
+    }
+}
+
+/// The build artifacts of the experiment at `folder`, by the project types
+/// detected from its manifests.
+pub fn experiment_artifacts(folder: &Path, detectors: &[ProjectDetector]) -> Vec<Artifact> {
+    let patterns = artifact_patterns(folder, detectors);
+    if patterns.is_empty() {
+        return Vec::new();
+    }
+    find_artifacts(folder, &patterns)
+}
+
+/// Removes the artifacts and returns the number of bytes freed.
+pub fn remove_artifacts(artifacts: &[Artifact]) -> io::Result<u64> {
+    let mut freed = 0;
+    for artifact in artifacts {
+        fs::remove_dir_all(&artifact.path)?;
+        freed += artifact.bytes;
+    }
+    Ok(freed)
+}
+
+/// Drops the cached sizes of cleaned folders, so the list does not show their
+/// old size until they are measured again.
+pub fn forget_sizes(folders: &[PathBuf]) {
+    let cache_path = sizes::size_cache_path();
+    let mut cache = SizeCache::load(&cache_path);
+    for folder in folders {
+        cache.folders.remove(folder.to_string_lossy().as_ref());
+    }
+    let _ = cache.save(&cache_path);
+}
+
+/// Artifact paths relative to the experiment, for display.
+pub fn artifact_names(folder: &Path, artifacts: &[Artifact]) -> String {
+    artifacts
+        .iter()
+        .map(|a| {
+            a.path
+                .strip_prefix(folder)
+                .unwrap_or(&a.path)
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Handles `try-rs clean`.
+pub fn run_clean(
+    tries_dir: &Path,
+    project_detectors: &[ProjectDetector],
+    args: &CleanArgs,
+) -> Result<()> {
+    let mut entries = load_entries(tries_dir, project_detectors);
+    if !args.names.is_empty() {
+        entries.retain(|e| {
+            args.names
+                .iter()
+                .any(|n| *n == e.name || *n == e.display_name)
+        });
+    }
+    if let Some(days) = args.older_than {
+        entries = stale_entries(
+            entries,
+            Duration::from_secs(days * 86400),
+            SystemTime::now(),
+        );
+    }
+
+    let mut found: Vec<(String, Vec<Artifact>)> = Vec::new();
+    for entry in entries {
+        let artifacts = experiment_artifacts(&tries_dir.join(&entry.name), project_detectors);
+        if !artifacts.is_empty() {
+            found.push((entry.name, artifacts));
+        }
+    }
+    if found.is_empty() {
+        eprintln!("No build artifacts to clean.");
+        return Ok(());
+    }
+
+    let total: u64 = found.iter().flat_map(|(_, a)| a).map(|a| a.bytes).sum();
+    for (name, artifacts) in &found {
+        let bytes: u64 = artifacts.iter().map(|a| a.bytes).sum();
+        println!(
+            "{:>8}  {} ({})",
+            sizes::format_long(bytes),
+            name,
+            artifact_names(&tries_dir.join(name), artifacts)
+        );
+    }
+    eprintln!(
+        "{} reclaimable in {} experiments.",
+        sizes::format_long(total),
+        found.len()
+    );
+
+    if args.dry_run {
+        eprintln!("Dry run: nothing was removed.");
+        return Ok(());
+    }
+    if !args.yes {
+        eprint!("Remove these build artifacts? [y/N]: ");
+        io::stderr().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+            eprintln!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let mut freed = 0;
+    let mut cleaned = Vec::new();
+    for (name, artifacts) in &found {
+        match remove_artifacts(artifacts) {
+            Ok(bytes) => freed += bytes,
+            Err(e) => eprintln!("Error cleaning {}: {}", name, e),
+        }
+        cleaned.push(tries_dir.join(name));
+    }
+    forget_sizes(&cleaned);
+    eprintln!("Freed {}.", sizes::format_long(freed));
+    Ok(())
+}
@@ -1,4 +1,8 @@
-use clap::{Parser, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+use crate::archive::ArchiveFormat;
+use crate::fork::ForkMethod;
 
 #[derive(Parser)]
 #[command(name = "try-rs")]
@@ -32,6 +36,247 @@ pub struct Cli {
     /// Create a git worktree from current repository (must be inside a git repo)
     #[arg(short = 'w', long = "worktree", value_name = "WORKTREE_NAME")]
     pub worktree: Option<String>,
+
+    /// Use only this root (`[roots.<name>]` in config.toml) instead of all of them
+    #[arg(long, global = true, value_name = "ROOT")]
+    pub root: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Print all experiments without opening the TUI
+    List(ListArgs),
+    /// Remove experiments that have not been touched for a while
+    Prune(PruneArgs),
+    /// Remove build artifacts (target/, node_modules/, ...) from experiments
+    Clean(CleanArgs),
+    /// Inspect, restore or empty the trash of deleted experiments
+    Trash {
+        #[command(subcommand)]
+        action: TrashAction,
+    },
+    /// Pack an experiment into a compressed tarball and remove the folder
+    Archive(ArchiveArgs),
+    /// Unpack an archived experiment back into the tries directory
+    Restore(RestoreArgs),
+    /// Create a new experiment, optionally scaffolded from a template
+    New(NewArgs),
+    /// List the templates available to `try-rs new --template`
+    Templates,
+    /// Rename an experiment, keeping or updating its date prefix
+    Mv(MvArgs),
+    /// Copy an experiment into a new dated experiment
+    Fork(ForkArgs),
+    /// Manage the color themes of the TUI
+    Theme {
+        #[command(subcommand)]
+        action: ThemeAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TrashAction {
+    /// List trashed experiments, most recently deleted first
+    List,
+    /// Move a trashed experiment back into the tries directory
+    Restore {
+        /// Original folder name or trash id
+        name: String,
+    },
+    /// Permanently delete trashed experiments
+    Empty {
+        /// Only delete items trashed more than N days ago
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ThemeAction {
+    /// Convert a base16/base24, Alacritty, kitty or WezTerm color scheme into a theme file
+    Import(ThemeImportArgs),
+    /// List the built-in and user themes with their colors
+    List,
+    /// Draw a sample of the TUI with a theme
+    Show {
+        /// Theme name (case-insensitive)
+        name: String,
+    },
+    /// Print every color of a theme as a theme file, to customize it
+    Export {
+        /// Theme name (case-insensitive)
+        name: String,
+    },
+}
+
+#[derive(Args, Default)]
+pub struct ThemeImportArgs {
+    /// Color scheme file (.yaml, .toml or .conf)
+    pub file: PathBuf,
+
+    /// Theme name (defaults to the name in the file, or the file name)
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Overwrite an existing theme file
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+#[derive(Args, Default)]
+pub struct ListArgs {
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = ListFormat::Table)]
+    pub format: ListFormat,
+
+    /// Sort entries by this field
+    #[arg(long, value_enum, default_value_t = SortKey::Modified)]
+    pub sort: SortKey,
+
+    /// Reverse the sort order
+    #[arg(short, long)]
+    pub reverse: bool,
+
+    /// Only show entries fuzzy-matching this query
+    #[arg(value_name = "QUERY")]
+    pub query: Option<String>,
+
+    /// Only show entries of this project type (can be repeated)
+    #[arg(short = 't', long = "type", value_name = "TYPE")]
+    pub project_types: Vec<String>,
+
+    /// Only show git repositories
+    #[arg(long)]
+    pub git: bool,
+
+    /// Only show entries modified within the last N days
+    #[arg(long, value_name = "DAYS")]
+    pub newer_than: Option<u64>,
+
+    /// Only show entries not modified within the last N days
+    #[arg(long, value_name = "DAYS")]
+    pub older_than: Option<u64>,
+}
+
+#[derive(Args, Default)]
+pub struct NewArgs {
+    /// Name of the new experiment
+    pub name: String,
+
+    /// Template from the `templates` folder of the config directory
+    #[arg(short, long)]
+    pub template: Option<String>,
+
+    /// Set a template variable (can be repeated)
+    #[arg(long = "var", value_name = "KEY=VALUE")]
+    pub vars: Vec<String>,
+}
+
+#[derive(Args, Default)]
+pub struct ArchiveArgs {
+    /// Folder name, with or without its date prefix
+    pub name: String,
+
+    /// Compression format (defaults to `archive_format` from config, then zst)
+    #[arg(short, long, value_enum)]
+    pub format: Option<ArchiveFormat>,
+}
+
+#[derive(Args, Default)]
+pub struct RestoreArgs {
+    /// Fuzzy search over the archived names; asks which one when several match
+    #[arg(value_name = "QUERY")]
+    pub query: Option<String>,
+
+    /// Only list the matching archives
+    #[arg(short, long)]
+    pub list: bool,
+
+    /// Keep the archive file after restoring
+    #[arg(short, long)]
+    pub keep: bool,
+}
+
+#[derive(Args, Default)]
+pub struct MvArgs {
+    /// Current folder name (with or without the date prefix)
+    pub old: String,
+
+    /// New name; the date prefix is kept unless it is given or --redate is set
+    pub new: Option<String>,
+
+    /// Replace the date prefix with today's date
+    #[arg(short = 'd', long)]
+    pub redate: bool,
+}
+
+#[derive(Args, Default)]
+pub struct ForkArgs {
+    /// Folder to fork, with or without its date prefix
+    pub name: String,
+
+    /// Name of the fork (defaults to `<name>-fork`), prefixed with today's date
+    pub new: Option<String>,
+
+    /// How to create the fork; git repositories ask when not given
+    #[arg(short, long, value_enum)]
+    pub method: Option<ForkMethod>,
+}
+
+#[derive(Args, Default)]
+pub struct PruneArgs {
+    /// Remove experiments older than N days (defaults to `max_age_days` from config)
+    #[arg(long, value_name = "DAYS")]
+    pub older_than: Option<u64>,
+
+    /// Only print what would be removed
+    #[arg(short = 'n', long)]
+    pub dry_run: bool,
+
+    /// Remove without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+
+    /// Also remove pinned experiments
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+#[derive(Args, Default)]
+pub struct CleanArgs {
+    /// Experiments to clean, with or without their date prefix (all by default)
+    pub names: Vec<String>,
+
+    /// Only clean experiments without activity for more than N days
+    #[arg(long, value_name = "DAYS")]
+    pub older_than: Option<u64>,
+
+    /// Only print what would be removed
+    #[arg(short = 'n', long)]
+    pub dry_run: bool,
+
+    /// Remove without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ListFormat {
+    #[default]
+    Table,
+    Json,
+    Tsv,
+}
+
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SortKey {
+    #[default]
+    Modified,
+    Created,
+    Name,
 }
 
 #[derive(ValueEnum, Clone, Copy, PartialEq, Eq, Debug, Hash)]
@@ -0,0 +1,258 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Colors the terminal can show (`color_depth` in config.toml).
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ColorDepth {
+    /// 24-bit RGB colors, the themes as designed
+    #[default]
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    /// The xterm 256-color palette
+    #[serde(rename = "256")]
+    Ansi256,
+    /// The 16 ANSI colors, whose shades depend on the terminal scheme
+    #[serde(rename = "16")]
+    Ansi16,
+    /// No colors at all, only bold and reverse video
+    #[serde(rename = "none")]
+    NoColor,
+}
+
+/// The 16 ANSI colors with the RGB values of xterm.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel values of the 6x6x6 color cube (indices 16-231).
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Squared distance weighted by how sensitive the eye is to each channel
+/// ("redmean").
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let red_mean = (a.0 as i32 + b.0 as i32) / 2;
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+    (((512 + red_mean) * dr * dr) / 256 + 4 * dg * dg + ((767 - red_mean) * db * db) / 256) as u32
+}
+
+/// RGB value of a 256-color palette index.
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16[index as usize].1,
+        16..=231 => {
+            let i = index - 16;
+            (
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[(i / 6 % 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+/// Whether a color is light, by its perceived brightness.
+pub fn is_light_rgb((r, g, b): (u8, u8, u8)) -> bool {
+    299 * r as u32 + 587 * g as u32 + 114 * b as u32 > 128_000
+}
+
+/// RGB value of a color, as xterm shows the ANSI ones. `None` for `Reset`.
+pub fn color_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(index) => Some(indexed_rgb(index)),
+        Color::Reset => None,
+        named => ANSI16
+            .iter()
+            .find(|(c, _)| *c == named)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+/// Nearest entry of the color cube or the gray ramp (232-255).
+fn nearest_256(rgb: (u8, u8, u8)) -> u8 {
+    let level = |v: u8| {
+        (0..6)
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - v as i32).abs())
+            .unwrap_or(0) as u8
+    };
+    let cube = 16 + 36 * level(rgb.0) + 6 * level(rgb.1) + level(rgb.2);
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let gray = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+    if distance(rgb, indexed_rgb(gray)) < distance(rgb, indexed_rgb(cube)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+/// The ANSI color of the same hue. The nearest color by distance would turn
+/// most pastel theme colors into gray, terminals show these brighter anyway.
+fn nearest_16((r, g, b): (u8, u8, u8)) -> Color {
+    let max = r.max(g).max(b) as i32;
+    let min = r.min(g).min(b) as i32;
+    let chroma = max - min;
+    // Grays and very dark colors (backgrounds) have no usable hue
+    if chroma * 4 < max || max < 64 {
+        let average = (r as u32 + g as u32 + b as u32) / 3;
+        return match average {
+            0..40 => Color::Black,
+            40..140 => Color::DarkGray,
+            140..220 => Color::Gray,
+            _ => Color::White,
+        };
+    }
+    let (r, g, b) = (r as i32, g as i32, b as i32);
+    let hue = if max == r {
+        (60 * (g - b) / chroma).rem_euclid(360)
+    } else if max == g {
+        60 * (b - r) / chroma + 120
+    } else {
+        60 * (r - g) / chroma + 240
+    };
+    let bright = max > 200;
+    let (normal, light) = match hue {
+        0..40 | 330.. => (Color::Red, Color::LightRed),
+        40..90 => (Color::Yellow, Color::LightYellow),
+        90..150 => (Color::Green, Color::LightGreen),
+        150..210 => (Color::Cyan, Color::LightCyan),
+        210..270 => (Color::Blue, Color::LightBlue),
+        _ => (Color::Magenta, Color::LightMagenta),
+    };
+    if bright { light } else { normal }
+}
+
+impl ColorDepth {
+    /// The closest color this depth can show. Named ANSI colors are kept,
+    /// as the terminal renders them from its own scheme.
+    pub fn quantize(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor, _) => color,
+            (ColorDepth::NoColor, _) => Color::Reset,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256((r, g, b))),
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_16((r, g, b)),
+            (ColorDepth::Ansi16, Color::Indexed(index)) if index < 16 => ANSI16[index as usize].0,
+            (ColorDepth::Ansi16, Color::Indexed(index)) => nearest_16(indexed_rgb(index)),
+            _ => color,
+        }
+    }
+}
+
+/// Guesses the color depth of the terminal from `NO_COLOR`, `COLORTERM`,
+/// and the `colors` capability of the terminfo entry of `TERM`.
+pub fn detect_color_depth(env: &dyn Fn(&str) -> Option<String>) -> ColorDepth {
+    let set = |name: &str| env(name).filter(|v| !v.is_empty());
+    if set("NO_COLOR").is_some() {
+        return ColorDepth::NoColor;
+    }
+    if set("COLORTERM").is_some_and(|v| v == "truecolor" || v == "24bit") {
+        return ColorDepth::TrueColor;
+    }
+    // Windows terminals have no TERM and all handle RGB nowadays
+    let Some(term) = set("TERM") else {
+        return ColorDepth::TrueColor;
+    };
+    if term == "dumb" {
+        return ColorDepth::NoColor;
+    }
+    match terminfo_colors(&term, env) {
+        Some(colors) if colors >= 1 << 24 => ColorDepth::TrueColor,
+        Some(colors) if colors >= 256 => ColorDepth::Ansi256,
+        Some(colors) if colors >= 8 => ColorDepth::Ansi16,
+        Some(_) => ColorDepth::NoColor,
+        // No terminfo database, go by the name
+        None if term.ends_with("-direct") => ColorDepth::TrueColor,
+        None if term.contains("256color") => ColorDepth::Ansi256,
+        None => ColorDepth::Ansi16,
+    }
+}
+
+/// Directories searched for terminfo entries, in the order of ncurses.
+fn terminfo_dirs(env: &dyn Fn(&str) -> Option<String>) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = env("TERMINFO") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(home) = env("HOME") {
+        dirs.push(PathBuf::from(home).join(".terminfo"));
+    }
+    if let Some(list) = env("TERMINFO_DIRS") {
+        dirs.extend(list.split(':').filter(|d| !d.is_empty()).map(PathBuf::from));
+    }
+    for dir in [
+        "/etc/terminfo",
+        "/lib/terminfo",
+        "/usr/share/terminfo",
+        "/usr/lib/terminfo",
+    ] {
+        dirs.push(PathBuf::from(dir));
+    }
+    dirs
+}
+
+/// The `colors` capability of the compiled terminfo entry of `term`, `Some(0)`
+/// for an entry without colors and `None` when there is no entry.
+fn terminfo_colors(term: &str, env: &dyn Fn(&str) -> Option<String>) -> Option<u32> {
+    let first = term.chars().next()?;
+    terminfo_dirs(env).into_iter().find_map(|dir| {
+        // Linux uses the first letter, macOS its hex code
+        [first.to_string(), format!("{:x}", first as u32)]
+            .iter()
+            .find_map(|sub| fs::read(dir.join(sub).join(term)).ok())
+            .and_then(|data| parse_terminfo_colors(&data))
+    })
+}
+
+/// Reads `colors` (number capability 13) from a compiled terminfo entry,
+/// legacy (16-bit numbers) or extended (32-bit numbers) format.
+pub fn parse_terminfo_colors(data: &[u8]) -> Option<u32> {
+    const COLORS: usize = 13;
+    let short = |i: usize| -> Option<i16> {
+        let bytes = data.get(2 * i..2 * i + 2)?;
+        Some(i16::from_le_bytes([bytes[0], bytes[1]]))
+    };
+    let number_size = match short(0)? {
+        0o432 => 2,
+        0o1036 => 4,
+        _ => return None,
+    };
+    let names_size = usize::try_from(short(1)?).ok()?;
+    let bools_count = usize::try_from(short(2)?).ok()?;
+    let numbers_count = usize::try_from(short(3)?).ok()?;
+    if numbers_count <= COLORS {
+        return Some(0);
+    }
+    let mut offset = 12 + names_size + bools_count;
+    // The numbers start on an even byte
+    offset += offset % 2;
+    let start = offset + COLORS * number_size;
+    let bytes = data.get(start..start + number_size)?;
+    let colors = if number_size == 2 {
+        i16::from_le_bytes([bytes[0], bytes[1]]) as i32
+    } else {
+        i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    };
+    Some(colors.max(0) as u32)
+}
@@ -1,18 +1,141 @@
+use crate::archive::{ARCHIVE_DIR_NAME, ArchiveFormat};
+use crate::color_depth::{ColorDepth, detect_color_depth};
+use crate::keybindings::{KeybindingsConfig, Keymap};
+use crate::projects::{ColorRole, ProjectDetector};
+use crate::roots::{self, RootConfig, TriesRoot};
+use crate::terminal_background::Background;
 use crate::tui::Theme;
+use crate::user_themes;
 use crate::utils::expand_path;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
-#[derive(Deserialize, Serialize)]
+/// How the TUI orders the experiments when the search box is empty.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Most recently modified first
+    Mtime,
+    /// Most often and recently visited first, then by modification time
+    #[default]
+    Frecency,
+    /// Most recently created first (date prefix or creation time)
+    Created,
+    /// Largest first, as sizes are computed in the background
+    Size,
+}
+
+impl SortOrder {
+    pub fn label(self) -> &'static str {
+        match self {
+            SortOrder::Mtime => "modified",
+            SortOrder::Frecency => "frecency",
+            SortOrder::Created => "created",
+            SortOrder::Size => "size",
+        }
+    }
+
+    /// The next order when cycling through them in the TUI.
+    pub fn next(self) -> SortOrder {
+        match self {
+            SortOrder::Frecency => SortOrder::Mtime,
+            SortOrder::Mtime => SortOrder::Created,
+            SortOrder::Created => SortOrder::Size,
+            SortOrder::Size => SortOrder::Frecency,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Default)]
 pub struct Config {
     pub tries_path: Option<String>,
     pub theme: Option<String>,
+    /// Theme for light terminal backgrounds, replaces `theme` when one is detected
+    pub theme_light: Option<String>,
+    /// Theme for dark terminal backgrounds, replaces `theme` when one is detected
+    pub theme_dark: Option<String>,
     pub editor: Option<String>,
     pub apply_date_prefix: Option<bool>,
     pub transparent_background: Option<bool>,
+    /// Forces the colors (`"truecolor"`, `"256"`, `"16"`, `"none"`) instead of detecting them
+    pub color_depth: Option<ColorDepth>,
+    /// Experiments older than this are removed by `try-rs prune`
+    pub max_age_days: Option<u64>,
+    /// Pinned experiments are never pruned (default true, `prune --force` overrides)
+    pub keep_pinned: Option<bool>,
+    /// Trashed experiments are deleted for good after this many days (default
+    /// 30, 0 keeps them), checked when the TUI or `try-rs trash` starts
+    pub trash_auto_empty_days: Option<u64>,
+    /// Where archived experiments are stored (defaults to `<tries_path>/.archive`)
+    pub archive_path: Option<String>,
+    pub archive_format: Option<ArchiveFormat>,
+    pub sort_order: Option<SortOrder>,
+    /// Further tries directories, e.g. `[roots.work]` with `path = "~/work/tries"`
+    pub roots: Option<BTreeMap<String, RootConfig>>,
+    /// Root used for new experiments and the subcommands (defaults to `tries_path`)
+    pub default_root: Option<String>,
+    /// Start the TUI in a vim-style navigation mode (j/k, gg, G, dd, `/`)
+    pub vim_mode: Option<bool>,
+    /// Keys per TUI mode, e.g. `[keybindings.normal]` with `ctrl-b = "theme"`
+    pub keybindings: Option<KeybindingsConfig>,
+    pub project_types: Option<Vec<ProjectTypeConfig>>,
+}
+
+/// A user-defined project type (`[[project_types]]` in config.toml).
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct ProjectTypeConfig {
+    pub name: String,
+    /// File name patterns checked in the folder root, e.g. `"WORKSPACE"` or `"*.tf"`
+    pub markers: Vec<String>,
+    pub icon: String,
+    /// A theme role (`"green"`, `"accent"`, ...) or a color (`"#7b42bc"`)
+    pub color: Option<String>,
+    /// Directories skipped when forking and removed by `try-rs clean`, e.g. `"build"`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<String>,
+}
+
+/// Builds the detector registry: built-ins followed by the user-defined types.
+/// A user-defined type replaces a built-in one with the same name.
+pub fn project_detectors(custom: &[ProjectTypeConfig]) -> Vec<ProjectDetector> {
+    let mut detectors = ProjectDetector::all();
+    for project_type in custom {
+        let color = match project_type.color.as_deref() {
+            Some(color) => ColorRole::parse(color).unwrap_or_else(|| {
+                eprintln!(
+                    "Warning: invalid color '{}' for project type '{}'",
+                    color, project_type.name
+                );
+                ColorRole::Accent
+            }),
+            None => ColorRole::Accent,
+        };
+        // Icons are rendered with a trailing space like the built-in ones
+        let mut icon = project_type.icon.trim_end().to_string();
+        icon.push(' ');
+        let mut detector = ProjectDetector {
+            name: project_type.name.clone(),
+            markers: project_type.markers.clone(),
+            icon,
+            color,
+            artifacts: project_type.artifacts.clone(),
+        };
+        match detectors.iter_mut().find(|d| d.name == detector.name) {
+            Some(existing) => {
+                // Overriding the markers keeps the built-in artifacts
+                if detector.artifacts.is_empty() {
+                    detector.artifacts = std::mem::take(&mut existing.artifacts);
+                }
+                *existing = detector;
+            }
+            None => detectors.push(detector),
+        }
+    }
+    detectors
 }
 
 pub fn get_file_config_toml_name() -> String {
@@ -25,6 +148,14 @@ pub fn get_config_dir() -> PathBuf {
         .unwrap_or_else(|| get_base_config_dir().join("try-rs"))
 }
 
+/// Directory for state written by try-rs itself, like the visit history.
+pub fn get_data_dir() -> PathBuf {
+    std::env::var_os("TRY_DATA_DIR")
+        .map(PathBuf::from)
+        .or_else(|| dirs::data_local_dir().map(|dir| dir.join("try-rs")))
+        .unwrap_or_else(get_config_dir)
+}
+
 pub fn get_base_config_dir() -> PathBuf {
     dirs::config_dir().unwrap_or_else(|| {
         dirs::home_dir()
@@ -63,12 +194,67 @@ pub fn load_file_config_toml_if_exists() -> Option<Config> {
 }
 
 pub struct AppConfig {
+    /// Path of the active root, where new experiments are created
     pub tries_dir: PathBuf,
+    pub roots: Vec<TriesRoot>,
+    pub active_root: usize,
+    /// Whether the TUI lists the experiments of every root (unset by `--root`)
+    pub show_all_roots: bool,
     pub theme: Theme,
+    /// `theme_light` and `theme_dark`, picked by the terminal background
+    pub theme_light: Option<Theme>,
+    pub theme_dark: Option<Theme>,
     pub editor_cmd: Option<String>,
     pub config_path: Option<PathBuf>,
     pub apply_date_prefix: Option<bool>,
     pub transparent_background: Option<bool>,
+    pub project_detectors: Vec<ProjectDetector>,
+    pub max_age_days: Option<u64>,
+    pub keep_pinned: bool,
+    pub trash_auto_empty_days: Option<u64>,
+    pub archive_dir: PathBuf,
+    pub archive_format: ArchiveFormat,
+    pub sort_order: SortOrder,
+    pub keymap: Keymap,
+    pub vim_mode: bool,
+    /// Built-in and user themes, as listed in the theme selector
+    pub themes: Vec<Theme>,
+    /// Problems with the configuration, shown on stderr and in the TUI
+    pub warnings: Vec<String>,
+}
+
+impl AppConfig {
+    /// Makes the root `name` the active one (`--root`), moving the default
+    /// archive directory along with it.
+    pub fn select_root(&mut self, name: &str) -> Result<(), String> {
+        let index = roots::find_root(&self.roots, name)?;
+        let path = self.roots[index].path.clone();
+        if self.archive_dir == self.tries_dir.join(ARCHIVE_DIR_NAME) {
+            self.archive_dir = path.join(ARCHIVE_DIR_NAME);
+        }
+        self.tries_dir = path;
+        self.active_root = index;
+        self.show_all_roots = false;
+        Ok(())
+    }
+
+    /// Whether the theme depends on the terminal background.
+    pub fn follows_background(&self) -> bool {
+        self.theme_light.is_some() || self.theme_dark.is_some()
+    }
+
+    /// Uses `theme_light` or `theme_dark` for the detected background, keeping
+    /// `theme` when the background is unknown or has no theme configured.
+    pub fn select_background_theme(&mut self, background: Option<Background>) {
+        let theme = match background {
+            Some(Background::Light) => &self.theme_light,
+            Some(Background::Dark) => &self.theme_dark,
+            None => &None,
+        };
+        if let Some(theme) = theme {
+            self.theme = theme.clone();
+        }
+    }
 }
 
 pub fn load_configuration() -> AppConfig {
@@ -78,14 +264,29 @@ pub fn load_configuration() -> AppConfig {
         .join("tries");
 
     let mut theme = Theme::default();
+    let mut theme_light = None;
+    let mut theme_dark = None;
     let try_path = std::env::var_os("TRY_PATH");
     let try_path_specified = try_path.is_some();
-    let mut final_path = try_path.map(PathBuf::from).unwrap_or(default_path);
     let mut editor_cmd = std::env::var("VISUAL")
         .ok()
         .or_else(|| std::env::var("EDITOR").ok());
     let mut apply_date_prefix = None;
     let mut transparent_background = None;
+    let mut custom_project_types = Vec::new();
+    let mut max_age_days = None;
+    let mut keep_pinned = true;
+    let mut trash_auto_empty_days = None;
+    let mut archive_path = None;
+    let mut archive_format = ArchiveFormat::default();
+    let mut sort_order = SortOrder::default();
+    let mut tries_path = try_path.map(PathBuf::from);
+    let mut configured_roots = BTreeMap::new();
+    let mut default_root = None;
+    let mut keymap = Keymap::default();
+    let mut vim_mode = false;
+    let mut color_depth = None;
+    let (themes, mut warnings) = user_themes::load_themes(&user_themes::themes_dir());
 
     let loaded_config_path = find_config_path();
 
@@ -93,27 +294,104 @@ pub fn load_configuration() -> AppConfig {
         if let Some(path_str) = config.tries_path
             && !try_path_specified
         {
-            final_path = expand_path(&path_str);
+            tries_path = Some(expand_path(&path_str));
         }
         if let Some(editor) = config.editor {
             editor_cmd = Some(editor);
         }
-        if let Some(theme_name) = config.theme {
-            if let Some(found_theme) = Theme::all().into_iter().find(|t| t.name == theme_name) {
-                theme = found_theme;
+        let mut find_theme = |key: &str, name: Option<String>| {
+            let name = name?;
+            let found = themes.iter().find(|t| t.name == name).cloned();
+            if found.is_none() {
+                warnings.push(format!(
+                    "unknown {} '{}', using the default (themes: {})",
+                    key,
+                    name,
+                    themes
+                        .iter()
+                        .map(|t| t.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
             }
+            found
+        };
+        if let Some(found_theme) = find_theme("theme", config.theme) {
+            theme = found_theme;
         }
+        theme_light = find_theme("theme_light", config.theme_light);
+        theme_dark = find_theme("theme_dark", config.theme_dark);
         apply_date_prefix = config.apply_date_prefix;
         transparent_background = config.transparent_background;
+        custom_project_types = config.project_types.unwrap_or_default();
+        max_age_days = config.max_age_days;
+        keep_pinned = config.keep_pinned.unwrap_or(true);
+        trash_auto_empty_days = config.trash_auto_empty_days;
+        archive_path = config.archive_path.map(|p| expand_path(&p));
+        archive_format = config.archive_format.unwrap_or_default();
+        sort_order = config.sort_order.unwrap_or_default();
+        configured_roots = config.roots.unwrap_or_default();
+        // TRY_PATH wins over the configured default root
+        default_root = config.default_root.filter(|_| !try_path_specified);
+        vim_mode = config.vim_mode.unwrap_or(false);
+        color_depth = config.color_depth;
+        if let Some(keybindings) = config.keybindings {
+            keymap = Keymap::from_config(&keybindings).unwrap_or_else(|e| {
+                warnings.push(format!("ignoring [keybindings]:\n{}", e));
+                Keymap::default()
+            });
+        }
+    }
+
+    let roots = roots::tries_roots(tries_path, default_path, &configured_roots);
+    let active_root = match default_root {
+        Some(name) => roots::find_root(&roots, &name).unwrap_or_else(|e| {
+            warnings.push(format!("default_root: {}", e));
+            0
+        }),
+        None => 0,
+    };
+    for warning in &warnings {
+        eprintln!("Warning: {}", warning);
     }
+    // Reduce the RGB themes for terminals that cannot show them
+    let color_depth =
+        color_depth.unwrap_or_else(|| detect_color_depth(&|name| std::env::var(name).ok()));
+    let theme = theme.with_color_depth(color_depth);
+    let theme_light = theme_light.map(|t| t.with_color_depth(color_depth));
+    let theme_dark = theme_dark.map(|t| t.with_color_depth(color_depth));
+    // The theme selector lists the dark themes first, then the light ones
+    let mut themes: Vec<Theme> = themes
+        .into_iter()
+        .map(|t| t.with_color_depth(color_depth))
+        .collect();
+    themes.sort_by_key(Theme::is_light);
+    let final_path = roots[active_root].path.clone();
+    let archive_dir = archive_path.unwrap_or_else(|| final_path.join(ARCHIVE_DIR_NAME));
 
     AppConfig {
         tries_dir: final_path,
+        roots,
+        active_root,
+        show_all_roots: true,
         theme,
+        theme_light,
+        theme_dark,
         editor_cmd,
         config_path: loaded_config_path,
         apply_date_prefix,
         transparent_background,
+        project_detectors: project_detectors(&custom_project_types),
+        max_age_days,
+        keep_pinned,
+        trash_auto_empty_days,
+        archive_dir,
+        archive_format,
+        sort_order,
+        keymap,
+        vim_mode,
+        themes,
+        warnings,
     }
 }
 
@@ -125,12 +403,23 @@ pub fn save_config(
     apply_date_prefix: Option<bool>,
     transparent_background: Option<bool>,
 ) -> std::io::Result<()> {
+    // Keep settings that are not managed from the TUI (e.g. project types)
+    let existing = fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| toml::from_str::<Config>(&contents).ok())
+        .unwrap_or_default();
+    // With several roots the TUI may be on any of them, keep the configured default
+    let tries_path = match &existing.roots {
+        Some(roots) if !roots.is_empty() => existing.tries_path.clone(),
+        _ => Some(tries_path.to_string_lossy().to_string()),
+    };
     let config = Config {
-        tries_path: Some(tries_path.to_string_lossy().to_string()),
+        tries_path,
         theme: Some(theme.name.clone()),
         editor: editor.clone(),
         apply_date_prefix,
         transparent_background,
+        ..existing
     };
 
     let toml_string =
@@ -0,0 +1,149 @@
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::archive::ARCHIVE_DIR_NAME;
+use crate::metadata::TryMetadata;
+use crate::projects::{self, ProjectDetector};
+use crate::roots::TriesRoot;
+use crate::trash::TRASH_DIR_NAME;
+use crate::utils;
+
+#[derive(Clone)]
+pub struct TryEntry {
+    pub name: String,
+    pub display_name: String,
+    /// Full path of the folder, which also identifies it across roots
+    pub path: PathBuf,
+    /// Name of the root the folder is in, set when several roots are listed
+    pub root: Option<String>,
+    pub modified: SystemTime,
+    pub created: SystemTime,
+    pub score: i64,
+    pub is_git: bool,
+    pub is_worktree: bool,
+    pub is_worktree_locked: bool,
+    pub is_gitmodules: bool,
+    /// Names of the project detectors matching this folder
+    pub project_types: Vec<String>,
+    pub metadata: TryMetadata,
+}
+
+/// Scans the tries directory and returns its entries, most recently modified first.
+pub fn load_entries(path: &Path, project_detectors: &[ProjectDetector]) -> Vec<TryEntry> {
+    let mut entries = Vec::new();
+    if let Ok(read_dir) = fs::read_dir(path) {
+        for entry in read_dir.flatten() {
+            if let Ok(metadata) = entry.metadata()
+                && metadata.is_dir()
+                && entry.file_name() != TRASH_DIR_NAME
+                && entry.file_name() != ARCHIVE_DIR_NAME
+            {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let git_path = entry.path().join(".git");
+                let is_git = git_path.exists();
+                let is_worktree = git_path.is_file();
+                let is_worktree_locked = utils::is_git_worktree_locked(&entry.path());
+                let is_gitmodules = entry.path().join(".gitmodules").exists();
+                let project_types =
+                    projects::detect_project_types(&entry.path(), project_detectors);
+
+                let created;
+                let display_name;
+                if let Some((date_prefix, remainder)) = utils::extract_prefix_date(&name) {
+                    created = date_prefix;
+                    display_name = remainder;
+                } else {
+                    created = metadata.created().unwrap_or(SystemTime::UNIX_EPOCH);
+                    display_name = name.clone();
+                }
+                entries.push(TryEntry {
+                    name,
+                    display_name,
+                    path: entry.path(),
+                    root: None,
+                    modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                    created,
+                    score: 0,
+                    is_git,
+                    is_worktree,
+                    is_worktree_locked,
+                    is_gitmodules,
+                    project_types,
+                    metadata: TryMetadata::load(&entry.path()),
+                });
+            }
+        }
+    }
+    entries.sort_by_key(|e| std::cmp::Reverse(e.modified));
+    entries
+}
+
+/// Loads the entries of all `roots`, most recently modified first. With more
+/// than one root, each entry is labelled with the name of its root.
+pub fn load_roots_entries(
+    roots: &[TriesRoot],
+    project_detectors: &[ProjectDetector],
+) -> Vec<TryEntry> {
+    let mut entries = Vec::new();
+    for root in roots {
+        let mut root_entries = load_entries(&root.path, project_detectors);
+        if roots.len() > 1 {
+            for entry in &mut root_entries {
+                entry.root = Some(root.name.clone());
+            }
+        }
+        entries.extend(root_entries);
+    }
+    entries.sort_by_key(|e| std::cmp::Reverse(e.modified));
+    entries
+}
+
+/// A search box query: `tag:<prefix>` terms filter by tag, the remaining
+/// text is fuzzy-matched.
+#[derive(Debug, Default, PartialEq)]
+pub struct SearchQuery {
+    pub tags: Vec<String>,
+    pub text: String,
+}
+
+impl SearchQuery {
+    pub fn parse(query: &str) -> SearchQuery {
+        let mut tags = Vec::new();
+        let mut words = Vec::new();
+        for word in query.split(' ') {
+            match word.strip_prefix("tag:") {
+                Some(tag) if !tag.is_empty() => tags.push(tag.to_string()),
+                Some(_) => {}
+                None => words.push(word),
+            }
+        }
+        SearchQuery {
+            tags,
+            text: words.join(" ").trim().to_string(),
+        }
+    }
+
+    /// Scores an entry, `None` if it does not match. Matches on the folder name
+    /// rank above matches on the description or tags.
+    pub fn score(&self, entry: &TryEntry, matcher: &SkimMatcherV2) -> Option<i64> {
+        if !self.tags.iter().all(|tag| entry.metadata.has_tag(tag)) {
+            return None;
+        }
+        if self.text.is_empty() {
+            return Some(0);
+        }
+        let name_score = matcher.fuzzy_match(&entry.name, &self.text);
+        let notes = format!(
+            "{} {}",
+            entry.metadata.description.as_deref().unwrap_or_default(),
+            entry.metadata.tags.join(" ")
+        );
+        let notes_score = matcher
+            .fuzzy_match(&notes, &self.text)
+            .map(|score| score / 2);
+        name_score.max(notes_score)
+    }
+}
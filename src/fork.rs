@@ -0,0 +1,223 @@
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::clean;
+use crate::cli::ForkArgs;
+use crate::metadata::TryMetadata;
+use crate::projects::{ProjectDetector, detect_project_types};
+use crate::utils;
+
+/// How the new experiment is created from the original one.
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ForkMethod {
+    /// Copy the files, skipping build artifacts
+    #[default]
+    Copy,
+    /// `git worktree add` on a new branch (git repositories only)
+    Worktree,
+    /// Local `git clone` of the repository (git repositories only)
+    Clone,
+}
+
+impl ForkMethod {
+    pub fn label(self) -> &'static str {
+        match self {
+            ForkMethod::Copy => "copy",
+            ForkMethod::Worktree => "worktree",
+            ForkMethod::Clone => "clone",
+        }
+    }
+}
+
+/// Name of the fork: `new` (or `<name>-fork`) with today's date prefix.
+pub fn fork_name(source: &str, new: Option<&str>, today: &str) -> String {
+    let name = match new.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(new) => utils::extract_prefix_date(new)
+            .map(|(_, name)| name)
+            .unwrap_or_else(|| new.to_string()),
+        None => {
+            let name = utils::extract_prefix_date(source)
+                .map(|(_, name)| name)
+                .unwrap_or_else(|| source.to_string());
+            format!("{}-fork", name)
+        }
+    };
+    format!("{} {}", today, name)
+}
+
+/// Directory names skipped when copying `folder`: the artifacts of every
+/// project type detected in it.
+pub fn artifact_patterns(folder: &Path, detectors: &[ProjectDetector]) -> Vec<String> {
+    let types = detect_project_types(folder, detectors);
+    let mut patterns: Vec<String> = Vec::new();
+    for detector in detectors.iter().filter(|d| types.contains(&d.name)) {
+        for artifact in &detector.artifacts {
+            if !patterns.contains(artifact) {
+                patterns.push(artifact.clone());
+            }
+        }
+    }
+    patterns
+}
+
+/// Creates `new` next to `source` inside `tries_dir` and returns its path.
+/// The notes of the original are carried over, with the original as source.
+pub fn fork_experiment(
+    tries_dir: &Path,
+    source: &str,
+    new: &str,
+    method: ForkMethod,
+    detectors: &[ProjectDetector],
+) -> Result<PathBuf> {
+    if new.is_empty() || new.contains(['/', '\\']) || new.starts_with('.') {
+        bail!("'{}' is not a valid name", new);
+    }
+    let from = tries_dir.join(source);
+    let to = tries_dir.join(new);
+    if to.exists() {
+        bail!("'{}' already exists", new);
+    }
+    let is_git = from.join(".git").exists();
+    if method != ForkMethod::Copy && !is_git {
+        bail!(
+            "'{}' is not a git repository, it can only be copied",
+            source
+        );
+    }
+    if method == ForkMethod::Copy && utils::is_git_worktree(&from) {
+        bail!(
+            "'{}' is a git worktree, a copy would share its git data: fork it as a worktree or clone",
+            source
+        );
+    }
+
+    match method {
+        ForkMethod::Copy => {
+            // Copy next to the target first so a failure leaves no half-copied folder
+            let staging = tries_dir.join(format!(".{}.forking", new));
+            let _ = fs::remove_dir_all(&staging);
+            // Same rule as `try-rs clean`: top-level, untracked artifacts only
+            let skip: Vec<PathBuf> = clean::experiment_artifacts(&from, detectors)
+                .into_iter()
+                .map(|artifact| artifact.path)
+                .collect();
+            if let Err(e) = utils::copy_dir(&from, &staging, &skip) {
+                let _ = fs::remove_dir_all(&staging);
+                return Err(e).with_context(|| format!("could not copy '{}'", source));
+            }
+            fs::rename(&staging, &to)?;
+        }
+        ForkMethod::Worktree => {
+            let branch = branch_name(new);
+            git(
+                &from,
+                &["worktree", "add", "-b", &branch, &to.to_string_lossy()],
+            )?;
+        }
+        ForkMethod::Clone => {
+            git(
+                tries_dir,
+                &["clone", &from.to_string_lossy(), &to.to_string_lossy()],
+            )?;
+        }
+    }
+
+    let mut metadata = TryMetadata::load(&from);
+    metadata.source = Some(from.to_string_lossy().to_string());
+    if let Err(e) = metadata.save(&to) {
+        eprintln!("Warning: could not save the notes: {}", e);
+    }
+    Ok(to)
+}
+
+/// Branch name for a worktree fork: the name without its date prefix.
+fn branch_name(folder: &str) -> String {
+    let name = utils::extract_prefix_date(folder)
+        .map(|(_, name)| name)
+        .unwrap_or_else(|| folder.to_string());
+    name.split_whitespace().collect::<Vec<_>>().join("-")
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("could not run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .next()
+                .unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+/// Handles `try-rs fork`. Returns the path of the new experiment.
+pub fn run_fork(
+    tries_dir: &Path,
+    detectors: &[ProjectDetector],
+    args: &ForkArgs,
+) -> Result<PathBuf> {
+    let source = if tries_dir.join(&args.name).is_dir() {
+        args.name.clone()
+    } else {
+        let matches = utils::matching_folders(&args.name, &tries_dir.to_path_buf());
+        match matches.as_slice() {
+            [name] => name.clone(),
+            [] => bail!("No experiment named '{}'", args.name),
+            _ => bail!(
+                "'{}' is ambiguous, use the full name: {}",
+                args.name,
+                matches.join(", ")
+            ),
+        }
+    };
+    let new = fork_name(&source, args.new.as_deref(), &utils::generate_prefix_date());
+
+    let method = match args.method {
+        Some(method) => method,
+        None if utils::is_git_worktree(&tries_dir.join(&source)) => {
+            eprint!(
+                "'{}' is a git worktree: [w]orktree or [c]lone? [w]: ",
+                source
+            );
+            io::stderr().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            match input.trim().to_lowercase().as_str() {
+                "" | "w" | "worktree" => ForkMethod::Worktree,
+                "c" | "clone" => ForkMethod::Clone,
+                _ => bail!("Aborted"),
+            }
+        }
+        None if tries_dir.join(&source).join(".git").exists() => {
+            eprint!(
+                "'{}' is a git repository: [w]orktree, [c]lone or [r]aw copy? [w]: ",
+                source
+            );
+            io::stderr().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            match input.trim().to_lowercase().as_str() {
+                "" | "w" | "worktree" => ForkMethod::Worktree,
+                "c" | "clone" => ForkMethod::Clone,
+                "r" | "copy" => ForkMethod::Copy,
+                _ => bail!("Aborted"),
+            }
+        }
+        None => ForkMethod::Copy,
+    };
+
+    let to = fork_experiment(tries_dir, &source, &new, method, detectors)?;
+    eprintln!("Forked ({}): {} -> {}", method.label(), source, new);
+    Ok(to)
+}
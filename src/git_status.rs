@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Repository state of a git try folder.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GitStatus {
+    /// Current branch, `None` for a detached HEAD
+    pub branch: Option<String>,
+    pub upstream: Option<String>,
+    pub ahead: usize,
+    pub behind: usize,
+    /// Staged, modified and conflicted files
+    pub changed: usize,
+    pub untracked: usize,
+    /// Commits of HEAD that are on no remote branch, every commit of every
+    /// branch without remotes
+    pub unpushed: Option<usize>,
+    pub last_commit: Option<LastCommit>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LastCommit {
+    pub subject: String,
+    /// Unix timestamp (seconds) of the commit
+    pub time: i64,
+}
+
+impl GitStatus {
+    pub fn is_dirty(&self) -> bool {
+        self.changed > 0 || self.untracked > 0
+    }
+
+    /// Commits that would be lost with the folder: ahead of the upstream, or
+    /// not on any remote for branches without one, or all of them when the
+    /// repository has no remote.
+    pub fn unpushed_commits(&self) -> usize {
+        self.unpushed.unwrap_or(0).max(self.ahead)
+    }
+}
+
+/// Parses the output of `git status --porcelain=v2 --branch`.
+pub fn parse_porcelain_v2(output: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+    for line in output.lines() {
+        if let Some(head) = line.strip_prefix("# branch.head ") {
+            status.branch = (head != "(detached)").then(|| head.to_string());
+        } else if let Some(upstream) = line.strip_prefix("# branch.upstream ") {
+            status.upstream = Some(upstream.to_string());
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            for part in ab.split_whitespace() {
+                if let Some(n) = part.strip_prefix('+') {
+                    status.ahead = n.parse().unwrap_or(0);
+                } else if let Some(n) = part.strip_prefix('-') {
+                    status.behind = n.parse().unwrap_or(0);
+                }
+            }
+        } else if line.starts_with("1 ") || line.starts_with("2 ") || line.starts_with("u ") {
+            status.changed += 1;
+        } else if line.starts_with("? ") {
+            status.untracked += 1;
+        }
+    }
+    status
+}
+
+fn git_output(path: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(path)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Reads the state of the repository at `path`, `None` if it is not one.
+pub fn git_status(path: &Path) -> Option<GitStatus> {
+    let output = git_output(path, &["status", "--porcelain=v2", "--branch"])?;
+    let mut status = parse_porcelain_v2(&output);
+
+    let has_remotes = git_output(path, &["remote"]).is_some_and(|r| !r.trim().is_empty());
+    let unpushed_args: &[&str] = if has_remotes {
+        &["rev-list", "--count", "HEAD", "--not", "--remotes"]
+    } else {
+        &["rev-list", "--count", "--all"]
+    };
+    status.unpushed = git_output(path, unpushed_args).and_then(|count| count.trim().parse().ok());
+
+    status.last_commit = git_output(path, &["log", "-1", "--format=%ct%x00%s"]).and_then(|log| {
+        let (time, subject) = log.trim_end().split_once('\0')?;
+        Some(LastCommit {
+            subject: subject.to_string(),
+            time: time.parse().ok()?,
+        })
+    });
+    Some(status)
+}
+
+/// Git status of each entry by path, filled in by a background thread.
+pub type GitStatuses = Arc<Mutex<HashMap<PathBuf, GitStatus>>>;
+
+/// Computes the status of every repository in a background thread, one at a
+/// time, so the list shows up before git has answered.
+pub fn spawn_git_status(repos: Vec<PathBuf>, statuses: &GitStatuses) {
+    let statuses = Arc::clone(statuses);
+    thread::spawn(move || {
+        for path in repos {
+            if let Some(status) = git_status(&path)
+                && let Ok(mut statuses) = statuses.lock()
+            {
+                statuses.insert(path, status);
+            }
+        }
+    });
+}
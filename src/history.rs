@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::get_data_dir;
+
+const HOUR: i64 = 3600;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+
+/// Visits of a single experiment.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Visit {
+    pub count: u32,
+    /// Unix timestamp (seconds) of the last visit
+    pub last: i64,
+}
+
+/// Visit history used for frecency ranking, keyed by the full path of the folder.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct History {
+    #[serde(default)]
+    pub visits: BTreeMap<String, Visit>,
+}
+
+pub fn history_path() -> PathBuf {
+    get_data_dir().join("history.toml")
+}
+
+pub fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+impl History {
+    pub fn load(path: &Path) -> History {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the history, forgetting folders that no longer exist.
+    pub fn save(&mut self, path: &Path) -> std::io::Result<()> {
+        self.visits.retain(|folder, _| Path::new(folder).exists());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string(self).map_err(std::io::Error::other)?;
+        fs::write(path, contents)
+    }
+
+    pub fn record(&mut self, folder: &Path, now: i64) {
+        let visit = self
+            .visits
+            .entry(folder.to_string_lossy().to_string())
+            .or_insert(Visit {
+                count: 0,
+                last: now,
+            });
+        visit.count = visit.count.saturating_add(1);
+        visit.last = now;
+    }
+
+    /// Keeps the visits when a folder is renamed or moved.
+    pub fn rename(&mut self, from: &Path, to: &Path) {
+        if let Some(visit) = self.visits.remove(from.to_string_lossy().as_ref()) {
+            self.visits.insert(to.to_string_lossy().to_string(), visit);
+        }
+    }
+
+    /// Zoxide-style frecency: the visit count weighted by how recent the last visit is.
+    pub fn frecency(&self, folder: &Path, now: i64) -> f64 {
+        let Some(visit) = self.visits.get(folder.to_string_lossy().as_ref()) else {
+            return 0.0;
+        };
+        let age = now - visit.last;
+        let weight = if age < HOUR {
+            4.0
+        } else if age < DAY {
+            2.0
+        } else if age < WEEK {
+            0.5
+        } else {
+            0.25
+        };
+        visit.count as f64 * weight
+    }
+}
+
+/// Adds a visit of `folder` to the history file. Failures are ignored, the
+/// history only influences the ordering.
+pub fn record_visit(folder: &Path) {
+    let path = history_path();
+    let mut history = History::load(&path);
+    history.record(folder, unix_now());
+    let _ = history.save(&path);
+}
+
+/// Bonus added to a fuzzy match score, growing slowly with the frecency so a
+/// much better textual match still wins.
+pub fn frecency_bonus(frecency: f64) -> i64 {
+    (10.0 * (1.0 + frecency).ln()).round() as i64
+}
@@ -0,0 +1,687 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::tui::AppMode;
+
+/// `[keybindings.<mode>]` tables of config.toml: key chord to action name.
+pub type KeybindingsConfig = BTreeMap<String, BTreeMap<String, String>>;
+
+/// What a key does in the TUI. Which actions a mode understands is listed in
+/// `MODES`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    Up,
+    Down,
+    /// Enter: select, create, save or restore depending on the mode
+    Confirm,
+    /// Esc: close the popup, or clear the marks and quit in the list
+    Cancel,
+    Quit,
+    ClearInput,
+    Mark,
+    Unmark,
+    PreviewUp,
+    PreviewDown,
+    PreviewPageUp,
+    PreviewPageDown,
+    Delete,
+    Archive,
+    Clean,
+    Move,
+    OpenEditor,
+    Theme,
+    About,
+    Undo,
+    Trash,
+    Pin,
+    Notes,
+    Rename,
+    Fork,
+    Sort,
+    Roots,
+    /// Confirm a delete or archive including the pinned entries, which are
+    /// skipped otherwise
+    Force,
+    /// Flip the option of the popup (transparency, date prefix, fork method)
+    Toggle,
+    /// Leave the vim navigation for the search box
+    Search,
+    First,
+    Last,
+}
+
+const ACTION_NAMES: &[(&str, Action)] = &[
+    ("up", Action::Up),
+    ("down", Action::Down),
+    ("confirm", Action::Confirm),
+    ("cancel", Action::Cancel),
+    ("quit", Action::Quit),
+    ("clear_input", Action::ClearInput),
+    ("mark", Action::Mark),
+    ("unmark", Action::Unmark),
+    ("preview_up", Action::PreviewUp),
+    ("preview_down", Action::PreviewDown),
+    ("preview_page_up", Action::PreviewPageUp),
+    ("preview_page_down", Action::PreviewPageDown),
+    ("delete", Action::Delete),
+    ("archive", Action::Archive),
+    ("clean", Action::Clean),
+    ("move", Action::Move),
+    ("open_editor", Action::OpenEditor),
+    ("theme", Action::Theme),
+    ("about", Action::About),
+    ("undo", Action::Undo),
+    ("trash", Action::Trash),
+    ("pin", Action::Pin),
+    ("notes", Action::Notes),
+    ("rename", Action::Rename),
+    ("fork", Action::Fork),
+    ("sort", Action::Sort),
+    ("roots", Action::Roots),
+    ("force", Action::Force),
+    ("toggle", Action::Toggle),
+    ("search", Action::Search),
+    ("first", Action::First),
+    ("last", Action::Last),
+];
+
+/// Action name that removes the default binding of a key.
+pub const UNBIND: &str = "none";
+
+impl Action {
+    pub fn name(self) -> &'static str {
+        ACTION_NAMES
+            .iter()
+            .find(|(_, action)| *action == self)
+            .map_or("", |(name, _)| name)
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        ACTION_NAMES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, action)| *action)
+    }
+}
+
+/// A key with its modifiers, e.g. `ctrl-t` or `shift-up`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> KeyChord {
+        // Shift is part of the character (`Y`) or of the key (`BackTab`)
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        KeyChord { code, modifiers }
+    }
+
+    pub fn from_event(key: &KeyEvent) -> KeyChord {
+        KeyChord::new(key.code, key.modifiers)
+    }
+
+    /// Parses `ctrl-t`, `C-t`, `alt+x`, `shift-up`, `F2`, `esc`, `Y`, ...
+    pub fn parse(text: &str) -> Result<KeyChord, String> {
+        let mut rest = text.trim();
+        let mut modifiers = KeyModifiers::NONE;
+        loop {
+            let lower = rest.to_lowercase();
+            let prefix = [
+                ("ctrl", KeyModifiers::CONTROL),
+                ("c", KeyModifiers::CONTROL),
+                ("alt", KeyModifiers::ALT),
+                ("a", KeyModifiers::ALT),
+                ("m", KeyModifiers::ALT),
+                ("shift", KeyModifiers::SHIFT),
+                ("s", KeyModifiers::SHIFT),
+            ]
+            .into_iter()
+            .find(|(name, _)| {
+                lower.len() > name.len() + 1
+                    && lower.starts_with(name)
+                    && matches!(lower.as_bytes()[name.len()], b'-' | b'+')
+            });
+            match prefix {
+                Some((name, modifier)) => {
+                    modifiers |= modifier;
+                    rest = &rest[name.len() + 1..];
+                }
+                None => break,
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => {
+                if modifiers.contains(KeyModifiers::SHIFT) {
+                    KeyCode::Char(c.to_ascii_uppercase())
+                } else if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+                    // `Ctrl-T` means Ctrl and t, not Ctrl-Shift-t
+                    KeyCode::Char(c.to_ascii_lowercase())
+                } else {
+                    KeyCode::Char(c)
+                }
+            }
+            _ => match rest.to_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{}'", text)),
+                },
+            },
+        };
+        Ok(KeyChord::new(code, modifiers))
+    }
+
+    /// Whether the key types a character into a text field.
+    pub fn is_text(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_))
+            && !self
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift-Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// A mode as named in `[keybindings.<name>]`.
+pub struct ModeKeys {
+    pub name: &'static str,
+    pub mode: AppMode,
+    /// Whether plain characters are typed into a text field in this mode
+    pub text_input: bool,
+    /// The actions of the mode with their default keys
+    pub defaults: &'static [(Action, &'static [&'static str])],
+}
+
+impl ModeKeys {
+    /// The actions that can be bound in the mode. The navigate mode also runs
+    /// those of the normal mode.
+    pub fn actions(&self) -> Vec<Action> {
+        let mut actions: Vec<Action> = self.defaults.iter().map(|(a, _)| *a).collect();
+        if self.mode == AppMode::Navigate {
+            for (action, _) in MODES[0].defaults {
+                if !actions.contains(action) {
+                    actions.push(*action);
+                }
+            }
+        }
+        actions
+    }
+}
+
+const LIST_KEYS: &[(Action, &[&str])] = &[
+    (Action::Up, &["up", "k", "p"]),
+    (Action::Down, &["down", "j", "n"]),
+    (Action::Confirm, &["enter"]),
+    (Action::Cancel, &["esc", "ctrl-c"]),
+];
+
+pub const MODES: &[ModeKeys] = &[
+    ModeKeys {
+        name: "normal",
+        mode: AppMode::Normal,
+        text_input: true,
+        defaults: &[
+            (Action::Up, &["up", "ctrl-k", "ctrl-p"]),
+            (Action::Down, &["down", "ctrl-j", "ctrl-n"]),
+            (Action::Confirm, &["enter"]),
+            (Action::Cancel, &["esc"]),
+            (Action::Quit, &["ctrl-c"]),
+            (Action::ClearInput, &["ctrl-u"]),
+            (Action::Mark, &["tab"]),
+            (Action::Unmark, &["shift-tab"]),
+            (Action::PreviewUp, &["shift-up"]),
+            (Action::PreviewDown, &["shift-down"]),
+            (Action::PreviewPageUp, &["pageup"]),
+            (Action::PreviewPageDown, &["pagedown"]),
+            (Action::Delete, &["ctrl-d"]),
+            (Action::Archive, &["ctrl-x"]),
+            (Action::Clean, &["ctrl-g"]),
+            (Action::Move, &["ctrl-o"]),
+            (Action::OpenEditor, &["ctrl-e"]),
+            (Action::Theme, &["ctrl-t"]),
+            (Action::About, &["ctrl-a"]),
+            (Action::Undo, &["ctrl-z"]),
+            (Action::Trash, &["ctrl-r"]),
+            (Action::Pin, &["ctrl-f"]),
+            (Action::Notes, &["ctrl-w"]),
+            (Action::Rename, &["f2"]),
+            (Action::Fork, &["ctrl-y"]),
+            (Action::Sort, &["ctrl-s"]),
+            (Action::Roots, &["ctrl-l"]),
+        ],
+    },
+    ModeKeys {
+        name: "navigate",
+        mode: AppMode::Navigate,
+        text_input: false,
+        defaults: &[
+            (Action::Up, &["k", "up"]),
+            (Action::Down, &["j", "down"]),
+            (Action::First, &["home"]),
+            (Action::Last, &["G", "end"]),
+            (Action::Search, &["/", "i"]),
+            (Action::Confirm, &["enter"]),
+            (Action::Cancel, &["esc"]),
+            (Action::Quit, &["q"]),
+        ],
+    },
+    ModeKeys {
+        name: "batch_confirm",
+        mode: AppMode::BatchConfirm,
+        text_input: false,
+        defaults: &[
+            (Action::Confirm, &["y", "Y"]),
+            (Action::Force, &["F"]),
+            (Action::Cancel, &["n", "N", "esc"]),
+            (Action::Quit, &["ctrl-c"]),
+        ],
+    },
+    ModeKeys {
+        name: "move_target",
+        mode: AppMode::MoveTarget,
+        text_input: true,
+        defaults: &[
+            (Action::Confirm, &["enter"]),
+            (Action::Cancel, &["esc", "ctrl-c"]),
+            (Action::ClearInput, &["ctrl-u"]),
+        ],
+    },
+    ModeKeys {
+        name: "theme_select",
+        mode: AppMode::ThemeSelect,
+        text_input: false,
+        defaults: &[
+            (Action::Up, &["up", "k", "p"]),
+            (Action::Down, &["down", "j", "n"]),
+            (Action::Confirm, &["enter"]),
+            (Action::Cancel, &["esc", "ctrl-c"]),
+            (Action::Toggle, &["space"]),
+        ],
+    },
+    ModeKeys {
+        name: "config_save_prompt",
+        mode: AppMode::ConfigSavePrompt,
+        text_input: false,
+        defaults: &[
+            (Action::Confirm, &["y", "Y", "enter"]),
+            (Action::Cancel, &["n", "N", "esc", "ctrl-c"]),
+        ],
+    },
+    ModeKeys {
+        name: "config_save_location_select",
+        mode: AppMode::ConfigSaveLocationSelect,
+        text_input: false,
+        defaults: LIST_KEYS,
+    },
+    ModeKeys {
+        name: "about",
+        mode: AppMode::About,
+        text_input: false,
+        defaults: &[(Action::Cancel, &["esc", "enter", "q", "space", "ctrl-c"])],
+    },
+    ModeKeys {
+        name: "trash",
+        mode: AppMode::Trash,
+        text_input: false,
+        defaults: &[
+            (Action::Up, &["up", "k", "p"]),
+            (Action::Down, &["down", "j", "n"]),
+            (Action::Confirm, &["enter", "r"]),
+            (Action::Delete, &["x", "delete"]),
+            (Action::Cancel, &["esc", "q", "ctrl-c"]),
+        ],
+    },
+    ModeKeys {
+        name: "template_select",
+        mode: AppMode::TemplateSelect,
+        text_input: false,
+        defaults: LIST_KEYS,
+    },
+    ModeKeys {
+        name: "edit_notes",
+        mode: AppMode::EditNotes,
+        text_input: true,
+        defaults: &[
+            (Action::Up, &["up", "shift-tab"]),
+            (Action::Down, &["down", "tab"]),
+            (Action::Confirm, &["enter"]),
+            (Action::Cancel, &["esc", "ctrl-c"]),
+        ],
+    },
+    ModeKeys {
+        name: "rename",
+        mode: AppMode::Rename,
+        text_input: true,
+        defaults: &[
+            (Action::Confirm, &["enter"]),
+            (Action::Cancel, &["esc", "ctrl-c"]),
+            (Action::ClearInput, &["ctrl-u"]),
+            (Action::Toggle, &["tab"]),
+        ],
+    },
+    ModeKeys {
+        name: "fork",
+        mode: AppMode::Fork,
+        text_input: true,
+        defaults: &[
+            (Action::Confirm, &["enter"]),
+            (Action::Cancel, &["esc", "ctrl-c"]),
+            (Action::ClearInput, &["ctrl-u"]),
+            (Action::Toggle, &["tab"]),
+        ],
+    },
+    ModeKeys {
+        name: "root_select",
+        mode: AppMode::RootSelect,
+        text_input: false,
+        defaults: LIST_KEYS,
+    },
+];
+
+/// Entries of the help bar below the list, in display order.
+const HELP: &[(&[Action], &str)] = &[
+    (&[Action::Up, Action::Down], "Nav"),
+    (&[Action::Confirm], "Select"),
+    (&[Action::Search], "Search"),
+    (&[Action::Mark], "Mark"),
+    (&[Action::PreviewPageUp, Action::PreviewPageDown], "Scroll"),
+    (&[Action::Delete], "Del"),
+    (&[Action::Clean], "Clean"),
+    (&[Action::Move], "Move"),
+    (&[Action::Rename], "Rename"),
+    (&[Action::Fork], "Fork"),
+    (&[Action::Sort], "Sort"),
+    (&[Action::Roots], "Roots"),
+    (&[Action::Trash], "Trash"),
+    (&[Action::OpenEditor], "Edit"),
+    (&[Action::Notes], "Notes"),
+    (&[Action::Theme], "Theme"),
+    (&[Action::About], "About"),
+    (&[Action::Cancel, Action::Quit], "Quit"),
+];
+
+/// The keys of every mode: the defaults with the `[keybindings]` of the
+/// config applied.
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: HashMap<AppMode, Vec<(KeyChord, Action)>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut bindings = HashMap::new();
+        for mode in MODES {
+            let keys = mode
+                .defaults
+                .iter()
+                .flat_map(|(action, keys)| {
+                    keys.iter().map(|key| {
+                        let chord = KeyChord::parse(key).expect("invalid default key");
+                        (chord, *action)
+                    })
+                })
+                .collect();
+            bindings.insert(mode.mode, keys);
+        }
+        Keymap { bindings }
+    }
+}
+
+impl Keymap {
+    /// Applies the configured bindings on top of the defaults. A configured key
+    /// replaces its default action, `"none"` unbinds it. All problems are
+    /// reported at once, one per line.
+    pub fn from_config(config: &KeybindingsConfig) -> Result<Keymap, String> {
+        let mut keymap = Keymap::default();
+        let mut errors = Vec::new();
+        for (mode_name, table) in config {
+            let Some(mode) = MODES.iter().find(|m| m.name == mode_name) else {
+                let names: Vec<&str> = MODES.iter().map(|m| m.name).collect();
+                errors.push(format!(
+                    "unknown mode '{}' (available: {})",
+                    mode_name,
+                    names.join(", ")
+                ));
+                continue;
+            };
+            let mut configured: HashMap<KeyChord, &str> = HashMap::new();
+            for (key, action_name) in table {
+                let context = format!("keybindings.{}", mode.name);
+                let chord = match KeyChord::parse(key) {
+                    Ok(chord) => chord,
+                    Err(e) => {
+                        errors.push(format!("{}: {}", context, e));
+                        continue;
+                    }
+                };
+                if let Some(other) = configured.insert(chord, key) {
+                    errors.push(format!(
+                        "{}: '{}' and '{}' are the same key",
+                        context, other, key
+                    ));
+                    continue;
+                }
+                if mode.text_input && chord.is_text() {
+                    errors.push(format!(
+                        "{}: '{}' is needed to type text in this mode",
+                        context, key
+                    ));
+                    continue;
+                }
+
+                let bindings = keymap.bindings.entry(mode.mode).or_default();
+                bindings.retain(|(c, _)| *c != chord);
+                if action_name == UNBIND {
+                    continue;
+                }
+                let actions = mode.actions();
+                match Action::from_name(action_name) {
+                    Some(action) if actions.contains(&action) => {
+                        // Configured keys come first, so the help shows them
+                        bindings.insert(0, (chord, action));
+                    }
+                    Some(_) | None => {
+                        let names: Vec<&str> = actions.iter().map(|a| a.name()).collect();
+                        errors.push(format!(
+                            "{}: unknown action '{}' for '{}' (available: {}, {})",
+                            context,
+                            action_name,
+                            key,
+                            names.join(", "),
+                            UNBIND
+                        ));
+                    }
+                }
+            }
+
+            let can_leave = keymap.bindings[&mode.mode]
+                .iter()
+                .any(|(_, a)| matches!(a, Action::Cancel | Action::Quit));
+            if !can_leave {
+                errors.push(format!(
+                    "keybindings.{}: no key is left to cancel or quit",
+                    mode.name
+                ));
+            }
+        }
+        if errors.is_empty() {
+            Ok(keymap)
+        } else {
+            Err(errors.join("\n"))
+        }
+    }
+
+    fn lookup(&self, mode: AppMode, chord: KeyChord) -> Option<Action> {
+        self.bindings
+            .get(&mode)?
+            .iter()
+            .find(|(c, _)| *c == chord)
+            .map(|(_, action)| *action)
+    }
+
+    /// The action of `key` in `mode`, if it is bound. The navigate mode falls
+    /// back to the keys of the normal mode that do not type text (Ctrl-D, ...).
+    pub fn action(&self, mode: AppMode, key: &KeyEvent) -> Option<Action> {
+        let chord = KeyChord::from_event(key);
+        self.lookup(mode, chord).or_else(|| {
+            (mode == AppMode::Navigate && !chord.is_text())
+                .then(|| self.lookup(AppMode::Normal, chord))
+                .flatten()
+        })
+    }
+
+    /// The keys bound to `action` in `mode`, configured ones first.
+    pub fn keys(&self, mode: AppMode, action: Action) -> Vec<KeyChord> {
+        let keys: Vec<KeyChord> = self
+            .bindings
+            .get(&mode)
+            .map(|bindings| {
+                bindings
+                    .iter()
+                    .filter(|(_, a)| *a == action)
+                    .map(|(chord, _)| *chord)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if keys.is_empty() && mode == AppMode::Navigate {
+            return self
+                .keys(AppMode::Normal, action)
+                .into_iter()
+                .filter(|chord| !chord.is_text() && self.lookup(mode, *chord).is_none())
+                .collect();
+        }
+        keys
+    }
+
+    /// Key and label of each entry of the help bar in `mode` (normal or
+    /// navigate), e.g. `("Ctrl-D", "Del")`. Entries whose actions have no key
+    /// are left out.
+    pub fn help(&self, mode: AppMode) -> Vec<(String, &'static str)> {
+        HELP.iter()
+            .filter_map(|(actions, label)| {
+                let keys: Vec<String> = actions
+                    .iter()
+                    .filter_map(|action| self.keys(mode, *action).first().copied())
+                    .map(|chord| chord.to_string())
+                    .collect();
+                if keys.is_empty() {
+                    return None;
+                }
+                // Arrows read best side by side: ↑↓
+                let separator = if keys.iter().all(|k| ["↑", "↓"].contains(&k.as_str())) {
+                    ""
+                } else {
+                    "/"
+                };
+                Some((keys.join(separator), *label))
+            })
+            .collect()
+    }
+}
+
+/// Count and operator typed so far in the navigate mode, e.g. `12` or `3d`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VimPending {
+    pub count: Option<usize>,
+    /// First key of a two-key command (`gg`, `dd`)
+    pub operator: Option<char>,
+}
+
+impl VimPending {
+    pub fn is_empty(&self) -> bool {
+        self.count.is_none() && self.operator.is_none()
+    }
+
+    /// Adds `c` to the count if it is a digit that can be part of one: `0` only
+    /// continues a count, and nothing follows the operator.
+    pub fn push_digit(&mut self, c: char) -> bool {
+        let Some(digit) = c.to_digit(10) else {
+            return false;
+        };
+        if self.operator.is_some() || (digit == 0 && self.count.is_none()) {
+            return false;
+        }
+        let count = self.count.unwrap_or(0).saturating_mul(10);
+        self.count = Some(count.saturating_add(digit as usize));
+        true
+    }
+
+    /// Takes the count (1 when none was typed) and clears the pending keys.
+    pub fn take_count(&mut self) -> usize {
+        let count = self.count.unwrap_or(1);
+        *self = VimPending::default();
+        count
+    }
+}
+
+impl fmt::Display for VimPending {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(count) = self.count {
+            write!(f, "{}", count)?;
+        }
+        if let Some(operator) = self.operator {
+            write!(f, "{}", operator)?;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,29 @@
+pub mod archive;
+pub mod clean;
 pub mod cli;
+pub mod color_depth;
 pub mod config;
+pub mod entries;
+pub mod fork;
+pub mod git_status;
+pub mod history;
+pub mod keybindings;
+pub mod list;
+pub mod metadata;
+pub mod pins;
+pub mod preview;
+pub mod projects;
+pub mod prune;
+pub mod rename;
+pub mod roots;
 pub mod shell;
+pub mod sizes;
+pub mod templates;
+pub mod terminal_background;
+pub mod theme_import;
+pub mod theme_preview;
 pub mod themes;
+pub mod trash;
 pub mod tui;
+pub mod user_themes;
 pub mod utils;
@@ -0,0 +1,188 @@
+use anyhow::Result;
+use chrono::{DateTime, Local, SecondsFormat};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use serde::Serialize;
+use std::time::{Duration, SystemTime};
+
+use crate::cli::{ListArgs, ListFormat, SortKey};
+use crate::entries::{SearchQuery, TryEntry, load_roots_entries};
+use crate::projects::ProjectDetector;
+use crate::roots::TriesRoot;
+
+#[derive(Serialize)]
+struct ListEntry<'a> {
+    name: &'a str,
+    display_name: &'a str,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    root: Option<&'a str>,
+    created: String,
+    modified: String,
+    project_types: &'a [String],
+    is_git: bool,
+    is_worktree: bool,
+    description: Option<&'a str>,
+    tags: &'a [String],
+    source: Option<&'a str>,
+}
+
+/// Applies the filter and sort flags of `try-rs list` to the entries.
+pub fn filter_and_sort(entries: Vec<TryEntry>, args: &ListArgs) -> Vec<TryEntry> {
+    let matcher = SkimMatcherV2::default();
+    let query = args.query.as_deref().map(SearchQuery::parse);
+    let now = SystemTime::now();
+    let days = |d: u64| Duration::from_secs(d * 86400);
+    let age = |e: &TryEntry| now.duration_since(e.modified).unwrap_or(Duration::ZERO);
+
+    let mut entries: Vec<TryEntry> = entries
+        .into_iter()
+        .filter(|e| match &query {
+            Some(query) => query.score(e, &matcher).is_some(),
+            None => true,
+        })
+        .filter(|e| {
+            args.project_types.iter().all(|wanted| {
+                e.project_types
+                    .iter()
+                    .any(|t| t.eq_ignore_ascii_case(wanted))
+            })
+        })
+        .filter(|e| !args.git || e.is_git)
+        .filter(|e| args.newer_than.is_none_or(|d| age(e) <= days(d)))
+        .filter(|e| args.older_than.is_none_or(|d| age(e) > days(d)))
+        .collect();
+
+    match args.sort {
+        SortKey::Modified => entries.sort_by_key(|e| std::cmp::Reverse(e.modified)),
+        SortKey::Created => entries.sort_by_key(|e| std::cmp::Reverse(e.created)),
+        SortKey::Name => entries.sort_by_key(|e| e.display_name.to_lowercase()),
+    }
+    if args.reverse {
+        entries.reverse();
+    }
+    entries
+}
+
+fn format_time(time: SystemTime, format: &str) -> String {
+    let dt: DateTime<Local> = time.into();
+    dt.format(format).to_string()
+}
+
+fn rfc3339(time: SystemTime) -> String {
+    let dt: DateTime<Local> = time.into();
+    dt.to_rfc3339_opts(SecondsFormat::Secs, false)
+}
+
+/// Keeps a TSV field on one line and in one column.
+fn tsv_escape(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Renders the entries in the requested output format.
+pub fn format_entries(entries: &[TryEntry], format: ListFormat) -> String {
+    match format {
+        ListFormat::Json => {
+            let items: Vec<ListEntry> = entries
+                .iter()
+                .map(|e| ListEntry {
+                    name: &e.name,
+                    display_name: &e.display_name,
+                    path: e.path.to_string_lossy().to_string(),
+                    root: e.root.as_deref(),
+                    created: rfc3339(e.created),
+                    modified: rfc3339(e.modified),
+                    project_types: &e.project_types,
+                    is_git: e.is_git,
+                    is_worktree: e.is_worktree,
+                    description: e.metadata.description.as_deref(),
+                    tags: &e.metadata.tags,
+                    source: e.metadata.source.as_deref(),
+                })
+                .collect();
+            let mut json = serde_json::to_string_pretty(&items).unwrap_or_default();
+            json.push('\n');
+            json
+        }
+        ListFormat::Tsv => entries
+            .iter()
+            .map(|e| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\n",
+                    tsv_escape(&e.name),
+                    tsv_escape(&e.display_name),
+                    rfc3339(e.created),
+                    rfc3339(e.modified),
+                    e.project_types.join(",")
+                )
+            })
+            .collect(),
+        ListFormat::Table => {
+            // The root column only shows up when several roots are listed
+            let with_root = entries.iter().any(|e| e.root.is_some());
+            let rows: Vec<[String; 5]> = entries
+                .iter()
+                .map(|e| {
+                    let mut types = e.project_types.clone();
+                    if e.is_worktree {
+                        types.push("Git-Worktree".to_string());
+                    } else if e.is_git {
+                        types.push("Git".to_string());
+                    }
+                    [
+                        e.root.clone().unwrap_or_default(),
+                        format_time(e.created, "%Y-%m-%d"),
+                        format_time(e.modified, "%Y-%m-%d %H:%M"),
+                        e.display_name.clone(),
+                        types.join(", "),
+                    ]
+                })
+                .collect();
+            let header = ["ROOT", "CREATED", "MODIFIED", "NAME", "TYPES"].map(String::from);
+
+            let mut widths = [0usize; 5];
+            for row in std::iter::once(&header).chain(&rows) {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+
+            let mut out = String::new();
+            for row in std::iter::once(&header).chain(&rows) {
+                let root = if with_root {
+                    format!("{:<w$}  ", row[0], w = widths[0])
+                } else {
+                    String::new()
+                };
+                let line = format!(
+                    "{}{:<w1$}  {:<w2$}  {:<w3$}  {}",
+                    root,
+                    row[1],
+                    row[2],
+                    row[3],
+                    row[4],
+                    w1 = widths[1],
+                    w2 = widths[2],
+                    w3 = widths[3],
+                );
+                out.push_str(line.trim_end());
+                out.push('\n');
+            }
+            out
+        }
+    }
+}
+
+/// Handles `try-rs list`.
+pub fn run_list(
+    roots: &[TriesRoot],
+    project_detectors: &[ProjectDetector],
+    args: &ListArgs,
+) -> Result<()> {
+    let entries = filter_and_sort(load_roots_entries(roots, project_detectors), args);
+    print!("{}", format_entries(&entries, args.format));
+    Ok(())
+}
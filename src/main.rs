@@ -12,16 +12,40 @@ use std::{
     io::{self, Write},
 };
 
+mod archive;
+mod clean;
 mod cli;
+mod color_depth;
 mod config;
+mod entries;
+mod fork;
+mod git_status;
+mod history;
+mod keybindings;
+mod list;
+mod metadata;
+mod pins;
+mod preview;
+mod projects;
+mod prune;
+mod rename;
+mod roots;
 mod shell;
+mod sizes;
+mod templates;
+mod terminal_background;
+mod theme_import;
+mod theme_preview;
 mod themes;
+mod trash;
 mod tui;
+mod user_themes;
 mod utils;
 
-use cli::{Cli, Shell};
-use config::{AppConfig, load_configuration};
+use cli::{Cli, Command, Shell};
+use config::load_configuration;
 use shell::{generate_completions, get_shell_content, setup_shell};
+use templates::Template;
 use tui::{App, run_app};
 
 use crate::utils::{SelectionResult, generate_prefix_date};
@@ -35,6 +59,20 @@ fn print_cd_or_editor(path: &std::path::Path, open_editor: bool, editor_cmd: &Op
     }
 }
 
+/// Deletes the trashed experiments older than `trash_auto_empty_days` (30 by
+/// default, 0 keeps them), before the trash is shown.
+fn auto_empty_trash(roots: &[roots::TriesRoot], days: Option<u64>) {
+    let days = days.unwrap_or(30);
+    if days > 0 {
+        for root in roots {
+            let _ = trash::empty_trash(
+                &root.path,
+                Some(std::time::Duration::from_secs(days * 86400)),
+            );
+        }
+    }
+}
+
 /// Handles the --worktree flag: creates a git worktree in the tries dir.
 fn handle_worktree(
     branch_name: &str,
@@ -175,20 +213,30 @@ fn handle_clone(
         .status();
 
     match status {
-        Ok(s) if s.success() => print_cd_or_editor(&new_path, open_editor, editor_cmd),
+        Ok(s) if s.success() => {
+            let metadata = metadata::TryMetadata {
+                source: Some(url.to_string()),
+                ..Default::default()
+            };
+            if let Err(e) = metadata.save(&new_path) {
+                eprintln!("Warning: could not save the source: {}", e);
+            }
+            print_cd_or_editor(&new_path, open_editor, editor_cmd)
+        }
         _ => eprintln!("Error: Failed to clone the repository."),
     }
 
     Ok(())
 }
 
-/// Creates a new folder in the tries directory.
+/// Creates a new folder in the tries directory, scaffolded from `template` if given.
 fn handle_new_folder(
     name: &str,
     tries_dir: &std::path::Path,
     apply_date_prefix: Option<bool>,
     open_editor: bool,
     editor_cmd: &Option<String>,
+    template: Option<&Template>,
 ) -> Result<()> {
     let mut new_name = name.to_string();
     let date_prefix = generate_prefix_date();
@@ -197,7 +245,15 @@ fn handle_new_folder(
     }
 
     let new_path = tries_dir.join(&new_name);
-    fs::create_dir_all(&new_path)?;
+    if let Some(template) = template {
+        if new_path.exists() {
+            anyhow::bail!("'{}' already exists", new_name);
+        }
+        fs::create_dir_all(&new_path)?;
+        template.apply(&new_path)?;
+    } else {
+        fs::create_dir_all(&new_path)?;
+    }
     print_cd_or_editor(&new_path, open_editor, editor_cmd);
     Ok(())
 }
@@ -211,14 +267,19 @@ fn main() -> Result<()> {
             std::process::exit(if err.use_stderr() { 1 } else { 0 });
         }
     };
-    let AppConfig {
-        tries_dir,
-        theme,
-        editor_cmd,
-        config_path,
-        apply_date_prefix,
-        transparent_background,
-    } = load_configuration();
+    let mut app_config = load_configuration();
+    if let Some(root) = &cli.root {
+        app_config.select_root(root).map_err(anyhow::Error::msg)?;
+    }
+    // Listing and jumping look in every root unless --root picks one
+    let roots = if app_config.show_all_roots {
+        app_config.roots.clone()
+    } else {
+        vec![app_config.roots[app_config.active_root].clone()]
+    };
+    let mut tries_dir = app_config.tries_dir.clone();
+    let editor_cmd = app_config.editor_cmd.clone();
+    let apply_date_prefix = app_config.apply_date_prefix;
 
     if !tries_dir.exists() {
         fs::create_dir_all(&tries_dir)?;
@@ -244,6 +305,67 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if let Some(command) = &cli.command {
+        match command {
+            Command::List(args) => {
+                list::run_list(&roots, &app_config.project_detectors, args)?;
+            }
+            Command::Prune(args) => {
+                prune::run_prune(
+                    &tries_dir,
+                    &app_config.project_detectors,
+                    args,
+                    app_config.max_age_days,
+                    &pins::Pins::load(&pins::pins_path()),
+                    app_config.keep_pinned,
+                )?;
+            }
+            Command::Clean(args) => {
+                clean::run_clean(&tries_dir, &app_config.project_detectors, args)?;
+            }
+            Command::Trash { action } => {
+                auto_empty_trash(&roots, app_config.trash_auto_empty_days);
+                trash::run_trash(&tries_dir, action)?;
+            }
+            Command::Archive(args) => archive::run_archive(
+                &tries_dir,
+                &app_config.archive_dir,
+                args,
+                app_config.archive_format,
+            )?,
+            Command::Restore(args) => {
+                archive::run_restore(&tries_dir, &app_config.archive_dir, args)?;
+            }
+            Command::New(args) => {
+                let template = match &args.template {
+                    Some(name) => Some(
+                        templates::find_template(&templates::templates_dir(), name)?
+                            .with_vars(&args.vars)?,
+                    ),
+                    None => None,
+                };
+                handle_new_folder(
+                    &args.name,
+                    &tries_dir,
+                    apply_date_prefix,
+                    false,
+                    &editor_cmd,
+                    template.as_ref(),
+                )?;
+            }
+            Command::Templates => templates::run_templates(&templates::templates_dir())?,
+            Command::Mv(args) => rename::run_mv(&tries_dir, args)?,
+            Command::Fork(args) => {
+                let path = fork::run_fork(&tries_dir, &app_config.project_detectors, args)?;
+                print_cd_or_editor(&path, false, &editor_cmd);
+            }
+            Command::Theme { action } => {
+                user_themes::run_theme(&user_themes::themes_dir(), &app_config.themes, action)?;
+            }
+        }
+        return Ok(());
+    }
+
     if cli.setup.is_none() {
         detect_and_setup_shell()?;
     }
@@ -261,7 +383,7 @@ fn main() -> Result<()> {
             };
 
             (
-                utils::matching_folders(folder_name, &tries_dir),
+                roots::matching_folders_in_roots(folder_name, &roots),
                 Some(folder_name.to_string()),
             )
         }
@@ -282,33 +404,33 @@ fn main() -> Result<()> {
             );
         }
     } else {
+        auto_empty_trash(&roots, app_config.trash_auto_empty_days);
+        if app_config.follows_background() {
+            app_config.select_background_theme(terminal_background::detect_background());
+        }
         enable_raw_mode()?;
         let mut stderr = io::stderr();
         execute!(stderr, EnterAlternateScreen)?;
         let backend = CrosstermBackend::new(stderr);
         let mut terminal = Terminal::new(backend)?;
 
-        let app = App::new(
-            tries_dir.clone(),
-            theme,
-            editor_cmd.clone(),
-            config_path.clone(),
-            apply_date_prefix,
-            transparent_background.unwrap_or(true),
-            query,
-        );
-        let res = run_app(&mut terminal, app);
+        let mut app = App::new(&app_config, query);
+        let res = run_app(&mut terminal, &mut app);
 
         disable_raw_mode()?;
         execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
         terminal.show_cursor()?;
 
-        (selection_result, open_editor) = res?;
+        res?;
+        selection_result = app.final_selection;
+        open_editor = app.wants_editor;
+        // New experiments go to the root picked in the TUI
+        tries_dir = app.base_path;
     }
 
     match selection_result {
-        SelectionResult::Folder(selection) => {
-            let target_path = tries_dir.join(&selection);
+        SelectionResult::Folder(target_path) => {
+            history::record_visit(&target_path);
             print_cd_or_editor(&target_path, open_editor, &editor_cmd);
         }
         SelectionResult::New(selection) => {
@@ -329,9 +451,30 @@ fn main() -> Result<()> {
                     apply_date_prefix,
                     open_editor,
                     &editor_cmd,
+                    None,
                 )?;
             }
         }
+        SelectionResult::NewFromTemplate(selection, template) => {
+            let template = templates::find_template(&templates::templates_dir(), &template)?;
+            handle_new_folder(
+                &selection,
+                &tries_dir,
+                apply_date_prefix,
+                open_editor,
+                &editor_cmd,
+                Some(&template),
+            )?;
+        }
+        SelectionResult::Folders(selections) => {
+            if let Some(cmd) = &editor_cmd {
+                let paths: Vec<String> = selections
+                    .iter()
+                    .map(|s| format!("'{}'", s.to_string_lossy()))
+                    .collect();
+                println!("{} {}", cmd, paths.join(" "));
+            }
+        }
         SelectionResult::None => {}
     }
 
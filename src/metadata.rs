@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Metadata file kept inside every annotated try folder, so it follows the
+/// folder when it is moved, trashed or archived.
+pub const METADATA_FILE_NAME: &str = ".try-rs.toml";
+
+/// Notes attached to an experiment.
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct TryMetadata {
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Where the experiment came from, e.g. the cloned repository URL
+    pub source: Option<String>,
+}
+
+impl TryMetadata {
+    /// Reads the metadata of a try folder, empty if there is none.
+    pub fn load(folder: &Path) -> TryMetadata {
+        fs::read_to_string(folder.join(METADATA_FILE_NAME))
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the metadata file, or removes it when everything is empty.
+    pub fn save(&self, folder: &Path) -> std::io::Result<()> {
+        let path = folder.join(METADATA_FILE_NAME);
+        if self.is_empty() {
+            return match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        let contents = toml::to_string(self).map_err(std::io::Error::other)?;
+        fs::write(path, contents)?;
+        exclude_from_git(folder)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none() && self.tags.is_empty() && self.source.is_none()
+    }
+
+    pub fn has_tag(&self, prefix: &str) -> bool {
+        let prefix = prefix.to_lowercase();
+        self.tags
+            .iter()
+            .any(|tag| tag.to_lowercase().starts_with(&prefix))
+    }
+}
+
+/// Keeps the metadata file out of `git status` in cloned repositories and
+/// worktrees (whose exclude file lives in the main repository).
+fn exclude_from_git(folder: &Path) -> std::io::Result<()> {
+    let git = folder.join(".git");
+    let exclude = if git.is_dir() {
+        git.join("info").join("exclude")
+    } else if git.is_file() {
+        match Command::new("git")
+            .args(["rev-parse", "--git-path", "info/exclude"])
+            .current_dir(folder)
+            .output()
+        {
+            Ok(output) if output.status.success() => {
+                folder.join(String::from_utf8_lossy(&output.stdout).trim())
+            }
+            _ => return Ok(()),
+        }
+    } else {
+        return Ok(());
+    };
+    let current = fs::read_to_string(&exclude).unwrap_or_default();
+    if current
+        .lines()
+        .any(|line| line.trim() == METADATA_FILE_NAME)
+    {
+        return Ok(());
+    }
+    if let Some(info_dir) = exclude.parent() {
+        fs::create_dir_all(info_dir)?;
+    }
+    let separator = if current.is_empty() || current.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    fs::write(
+        exclude,
+        format!("{current}{separator}{METADATA_FILE_NAME}\n"),
+    )
+}
+
+/// Splits user input like `"rust, wasm #demo"` into unique tags.
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(|c: char| c == ',' || c.is_whitespace()) {
+        let tag = tag.trim().trim_start_matches('#');
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
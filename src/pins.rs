@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::get_data_dir;
+
+/// Pinned experiments, stored by full path in the data directory.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Pins {
+    #[serde(default)]
+    pub pinned: BTreeSet<String>,
+}
+
+pub fn pins_path() -> PathBuf {
+    get_data_dir().join("pins.toml")
+}
+
+fn key(folder: &Path) -> String {
+    folder.to_string_lossy().to_string()
+}
+
+impl Pins {
+    pub fn load(path: &Path) -> Pins {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string(self).map_err(std::io::Error::other)?;
+        fs::write(path, contents)
+    }
+
+    pub fn is_pinned(&self, folder: &Path) -> bool {
+        self.pinned.contains(&key(folder))
+    }
+
+    /// Pins or unpins `folder` and returns whether it is pinned now.
+    pub fn toggle(&mut self, folder: &Path) -> bool {
+        let key = key(folder);
+        if self.pinned.remove(&key) {
+            false
+        } else {
+            self.pinned.insert(key);
+            true
+        }
+    }
+
+    /// Drops the pin of a folder that is gone, returns whether it was pinned.
+    pub fn unpin(&mut self, folder: &Path) -> bool {
+        self.pinned.remove(&key(folder))
+    }
+
+    /// Keeps the pin when a pinned folder is moved or renamed.
+    pub fn rename(&mut self, from: &Path, to: &Path) {
+        if self.pinned.remove(&key(from)) {
+            self.pinned.insert(key(to));
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use ignore::WalkBuilder;
+use ratatui::prelude::*;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{self, ThemeSet};
+use syntect::parsing::SyntaxSet;
+
+use crate::metadata::METADATA_FILE_NAME;
+use crate::themes::Theme;
+
+/// Files shown below the tree, the first one found wins.
+pub const PREVIEW_FILES: &[&str] = &[
+    "README.md",
+    "README",
+    "readme.md",
+    "Cargo.toml",
+    "package.json",
+    "go.mod",
+    "pyproject.toml",
+    "deno.json",
+    "pom.xml",
+    "build.gradle.kts",
+    "build.gradle",
+    "CMakeLists.txt",
+    "build.zig",
+    "mix.exs",
+    "Gemfile",
+    "composer.json",
+    "Package.swift",
+];
+
+/// Depth of the directory tree in the preview.
+pub const TREE_DEPTH: usize = 2;
+/// Bounds how much of a large folder or file is read for the preview.
+const MAX_TREE_ENTRIES: usize = 200;
+const MAX_FILE_LINES: usize = 300;
+const MAX_FILE_BYTES: u64 = 256 * 1024;
+
+/// A node of the directory tree, in display order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeEntry {
+    /// 1 for the direct children of the folder
+    pub depth: usize,
+    pub name: String,
+    pub is_dir: bool,
+}
+
+/// Walks `folder` up to `max_depth`, directories first, honouring
+/// `.gitignore`/`.ignore` files and skipping `.git`.
+pub fn tree_entries(folder: &Path, max_depth: usize) -> Vec<TreeEntry> {
+    WalkBuilder::new(folder)
+        .max_depth(Some(max_depth))
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|e| e.file_name() != ".git" && e.file_name() != METADATA_FILE_NAME)
+        .sort_by_file_path(|a, b| b.is_dir().cmp(&a.is_dir()).then_with(|| a.cmp(b)))
+        .build()
+        .flatten()
+        .filter(|e| e.depth() > 0)
+        .take(MAX_TREE_ENTRIES)
+        .map(|e| TreeEntry {
+            depth: e.depth(),
+            name: e.file_name().to_string_lossy().to_string(),
+            is_dir: e.file_type().is_some_and(|t| t.is_dir()),
+        })
+        .collect()
+}
+
+/// Draws the entries with `├──`/`└──` connectors.
+pub fn tree_lines(entries: &[TreeEntry], theme: &Theme) -> Vec<Line<'static>> {
+    // For every ancestor level, whether it still has siblings below
+    let mut open: Vec<bool> = Vec::new();
+    let mut lines = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let is_last = entries[i + 1..]
+            .iter()
+            .find(|e| e.depth <= entry.depth)
+            .is_none_or(|e| e.depth < entry.depth);
+        open.truncate(entry.depth - 1);
+        let mut prefix: String = open
+            .iter()
+            .map(|&more| if more { "│  " } else { "   " })
+            .collect();
+        prefix.push_str(if is_last { "└─ " } else { "├─ " });
+        open.push(!is_last);
+
+        let (icon, color) = if entry.is_dir {
+            ("󰝰 ", theme.icon_folder)
+        } else {
+            ("󰈙 ", theme.icon_file)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(prefix, Style::default().fg(theme.helpers_colors)),
+            Span::styled(icon, Style::default().fg(color)),
+            Span::raw(entry.name.clone()),
+        ]));
+    }
+    lines
+}
+
+/// The README or manifest shown in the preview, if the folder has one.
+pub fn preview_file(folder: &Path) -> Option<PathBuf> {
+    PREVIEW_FILES
+        .iter()
+        .map(|name| folder.join(name))
+        .find(|path| path.is_file())
+}
+
+struct Highlighter {
+    syntaxes: SyntaxSet,
+    dark: highlighting::Theme,
+    light: highlighting::Theme,
+}
+
+/// Syntaxes and colors are bundled in the binary and parsed on first use.
+fn highlighter() -> &'static Highlighter {
+    static HIGHLIGHTER: OnceLock<Highlighter> = OnceLock::new();
+    HIGHLIGHTER.get_or_init(|| {
+        let mut themes = ThemeSet::load_defaults();
+        Highlighter {
+            syntaxes: two_face::syntax::extra_newlines(),
+            dark: themes
+                .themes
+                .remove("base16-eighties.dark")
+                .unwrap_or_default(),
+            light: themes.themes.remove("InspiredGitHub").unwrap_or_default(),
+        }
+    })
+}
+
+/// Reads the start of `path` and highlights it by file name or extension, in
+/// colors readable on a `light` or a dark background. Binary files and
+/// unknown syntaxes are shown as plain text. Only NUL bytes mark a file as
+/// binary, invalid UTF-8 (e.g. a character cut at the read limit) is replaced.
+pub fn highlight_file(path: &Path, light: bool) -> Vec<Line<'static>> {
+    let mut bytes = Vec::new();
+    let Ok(file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+    if file.take(MAX_FILE_BYTES).read_to_end(&mut bytes).is_err() {
+        return Vec::new();
+    }
+    if bytes.contains(&0) {
+        return vec![Line::from("(binary file)")];
+    }
+    let text = String::from_utf8_lossy(&bytes);
+
+    let highlighter = highlighter();
+    let syntaxes = &highlighter.syntaxes;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let syntax = syntaxes
+        .find_syntax_by_extension(&file_name)
+        .or_else(|| {
+            path.extension()
+                .and_then(|ext| syntaxes.find_syntax_by_extension(&ext.to_string_lossy()))
+        })
+        .or_else(|| {
+            file_name
+                .eq_ignore_ascii_case("readme")
+                .then(|| syntaxes.find_syntax_by_extension("md"))
+                .flatten()
+        })
+        .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+
+    let mut lines = Vec::new();
+    let colors = if light {
+        &highlighter.light
+    } else {
+        &highlighter.dark
+    };
+    let mut highlight = HighlightLines::new(syntax, colors);
+    for line in syntect::util::LinesWithEndings::from(&text).take(MAX_FILE_LINES) {
+        let Ok(ranges) = highlight.highlight_line(line, syntaxes) else {
+            lines.push(Line::from(line.trim_end().replace('\t', "    ")));
+            continue;
+        };
+        let spans: Vec<Span<'static>> = ranges
+            .into_iter()
+            .map(|(style, piece)| {
+                let fg = style.foreground;
+                Span::styled(
+                    piece.trim_end_matches(['\n', '\r']).replace('\t', "    "),
+                    Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b)),
+                )
+            })
+            .collect();
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// Tree and file part of the preview, cached per entry by the TUI.
+pub fn folder_preview(folder: &Path, theme: &Theme) -> Vec<Line<'static>> {
+    let entries = tree_entries(folder, TREE_DEPTH);
+    let mut lines = tree_lines(&entries, theme);
+    if let Some(file) = preview_file(folder) {
+        let name = file
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("── {} ──", name),
+            Style::default()
+                .fg(theme.preview_title)
+                .add_modifier(Modifier::BOLD),
+        )));
+        let depth = theme.color_depth;
+        lines.extend(
+            highlight_file(&file, theme.is_light())
+                .into_iter()
+                .map(|mut line| {
+                    for span in &mut line.spans {
+                        span.style.fg = span.style.fg.map(|c| depth.quantize(c));
+                    }
+                    line
+                }),
+        );
+    }
+    lines
+}
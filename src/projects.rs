@@ -0,0 +1,184 @@
+use ratatui::style::Color;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::utils::glob_match;
+
+/// Theme color a project icon is drawn with.
+/// The project-specific roles map to the dedicated `icon_*` theme fields,
+/// the generic ones map to the palette-derived colors every theme provides.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColorRole {
+    Rust,
+    Maven,
+    Flutter,
+    Go,
+    Python,
+    Mise,
+    Accent,
+    Red,
+    Warm,
+    Cool,
+    Green,
+    Yellow,
+    Purple,
+    Subtle,
+    /// A fixed color that does not change with the theme
+    Fixed(Color),
+}
+
+impl ColorRole {
+    /// Parses a role name (e.g. `"green"`, which follows the theme) or a fixed
+    /// color (`"#a1b2c3"`, `"lightblue"`).
+    pub fn parse(s: &str) -> Option<ColorRole> {
+        let role = match s.to_ascii_lowercase().as_str() {
+            "rust" => ColorRole::Rust,
+            "maven" => ColorRole::Maven,
+            "flutter" => ColorRole::Flutter,
+            "go" => ColorRole::Go,
+            "python" => ColorRole::Python,
+            "mise" => ColorRole::Mise,
+            "accent" => ColorRole::Accent,
+            "red" => ColorRole::Red,
+            "warm" => ColorRole::Warm,
+            "cool" => ColorRole::Cool,
+            "green" => ColorRole::Green,
+            "yellow" => ColorRole::Yellow,
+            "purple" => ColorRole::Purple,
+            "subtle" => ColorRole::Subtle,
+            _ => return Color::from_str(s).ok().map(ColorRole::Fixed),
+        };
+        Some(role)
+    }
+}
+
+/// Describes how to recognize one kind of project inside a try folder.
+#[derive(Clone, Debug)]
+pub struct ProjectDetector {
+    /// Name shown in the legend and used to identify the type
+    pub name: String,
+    /// File name patterns (`*` and `?` wildcards) checked in the folder root
+    pub markers: Vec<String>,
+    /// Nerd Font glyph, including its trailing space
+    pub icon: String,
+    pub color: ColorRole,
+    /// Build output and dependency directories, skipped when forking and cleaning
+    pub artifacts: Vec<String>,
+}
+
+impl ProjectDetector {
+    fn builtin(name: &str, markers: &[&str], icon: &str, color: ColorRole) -> Self {
+        Self {
+            name: name.to_string(),
+            markers: markers.iter().map(|m| m.to_string()).collect(),
+            icon: icon.to_string(),
+            color,
+            artifacts: Vec::new(),
+        }
+    }
+
+    fn with_artifacts(mut self, artifacts: &[&str]) -> Self {
+        self.artifacts = artifacts.iter().map(|a| a.to_string()).collect();
+        self
+    }
+
+    /// Returns true if any of the marker patterns matches one of the file names.
+    pub fn matches(&self, file_names: &[String]) -> bool {
+        self.markers
+            .iter()
+            .any(|marker| file_names.iter().any(|name| glob_match(marker, name)))
+    }
+
+    /// The built-in detectors, in the order their icons are shown.
+    pub fn all() -> Vec<ProjectDetector> {
+        vec![
+            Self::builtin("Rust", &["Cargo.toml"], " ", ColorRole::Rust)
+                .with_artifacts(&["target"]),
+            Self::builtin("Maven", &["pom.xml"], " ", ColorRole::Maven)
+                .with_artifacts(&["target"]),
+            Self::builtin(
+                "Gradle",
+                &["build.gradle", "build.gradle.kts", "settings.gradle*"],
+                " ",
+                ColorRole::Cool,
+            )
+            .with_artifacts(&["build", ".gradle"]),
+            Self::builtin("Flutter", &["pubspec.yaml"], " ", ColorRole::Flutter)
+                .with_artifacts(&["build", ".dart_tool"]),
+            // Go builds into GOCACHE and installs into GOPATH, outside the
+            // project, and `vendor/` holds sources
+            Self::builtin("Go", &["go.mod"], " ", ColorRole::Go),
+            Self::builtin(
+                "Python",
+                &["pyproject.toml", "requirements.txt"],
+                " ",
+                ColorRole::Python,
+            )
+            .with_artifacts(&[
+                ".venv",
+                "venv",
+                "__pycache__",
+                ".pytest_cache",
+                ".mypy_cache",
+            ]),
+            Self::builtin("Node", &["package.json"], " ", ColorRole::Green).with_artifacts(&[
+                "node_modules",
+                ".next",
+                ".turbo",
+            ]),
+            Self::builtin(
+                "Deno",
+                &["deno.json", "deno.jsonc"],
+                " ",
+                ColorRole::Subtle,
+            )
+            .with_artifacts(&["node_modules"]),
+            Self::builtin("Zig", &["build.zig"], " ", ColorRole::Yellow).with_artifacts(&[
+                "zig-out",
+                ".zig-cache",
+                "zig-cache",
+            ]),
+            Self::builtin("CMake", &["CMakeLists.txt"], " ", ColorRole::Accent)
+                .with_artifacts(&["build"]),
+            Self::builtin(
+                ".NET",
+                &["*.csproj", "*.fsproj", "*.sln"],
+                "󰪮 ",
+                ColorRole::Purple,
+            )
+            .with_artifacts(&["bin", "obj"]),
+            Self::builtin("Elixir", &["mix.exs"], " ", ColorRole::Purple)
+                .with_artifacts(&["_build", "deps"]),
+            Self::builtin(
+                "Haskell",
+                &["stack.yaml", "cabal.project", "*.cabal"],
+                " ",
+                ColorRole::Accent,
+            )
+            .with_artifacts(&["dist-newstyle", ".stack-work"]),
+            Self::builtin("Ruby", &["Gemfile"], " ", ColorRole::Red).with_artifacts(&[".bundle"]),
+            Self::builtin("PHP", &["composer.json"], " ", ColorRole::Purple)
+                .with_artifacts(&["vendor"]),
+            Self::builtin("Swift", &["Package.swift"], " ", ColorRole::Warm)
+                .with_artifacts(&[".build"]),
+            Self::builtin("Mise", &["mise.toml"], "󰬔 ", ColorRole::Mise),
+        ]
+    }
+}
+
+/// Returns the names of all detectors matching the folder at `path`.
+pub fn detect_project_types(path: &Path, detectors: &[ProjectDetector]) -> Vec<String> {
+    let Ok(read_dir) = fs::read_dir(path) else {
+        return Vec::new();
+    };
+    let file_names: Vec<String> = read_dir
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    detectors
+        .iter()
+        .filter(|d| d.matches(&file_names))
+        .map(|d| d.name.clone())
+        .collect()
+}
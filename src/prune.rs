@@ -0,0 +1,145 @@
+use anyhow::{Result, bail};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use crate::cli::PruneArgs;
+use crate::entries::{TryEntry, load_entries};
+use crate::pins::Pins;
+use crate::projects::ProjectDetector;
+use crate::utils;
+
+/// The last sign of life of an experiment: its modification time, or the
+/// date prefix of its name if that is more recent.
+pub fn last_activity(entry: &TryEntry) -> SystemTime {
+    match utils::extract_prefix_date(&entry.name) {
+        Some((created, _)) => created.max(entry.modified),
+        None => entry.modified,
+    }
+}
+
+/// Returns the entries with no activity within `max_age`, oldest first.
+pub fn stale_entries(entries: Vec<TryEntry>, max_age: Duration, now: SystemTime) -> Vec<TryEntry> {
+    let mut stale: Vec<TryEntry> = entries
+        .into_iter()
+        .filter(|e| {
+            now.duration_since(last_activity(e))
+                .unwrap_or(Duration::ZERO)
+                > max_age
+        })
+        .collect();
+    stale.sort_by_key(last_activity);
+    stale
+}
+
+/// Interprets the answer to the confirmation prompt and returns the indices
+/// of the entries to remove. `y`/`yes` removes everything, a list of numbers
+/// (1-based) keeps those entries and removes the rest, anything else aborts.
+pub fn parse_confirmation(answer: &str, count: usize) -> Option<Vec<usize>> {
+    let answer = answer.trim();
+    if answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes") {
+        return Some((0..count).collect());
+    }
+    let keep: Vec<usize> = answer
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<usize>().ok().filter(|n| (1..=count).contains(n)))
+        .collect::<Option<_>>()?;
+    if keep.is_empty() {
+        return None;
+    }
+    Some((0..count).filter(|i| !keep.contains(&(i + 1))).collect())
+}
+
+fn format_age(age: Duration) -> String {
+    format!("{}d", age.as_secs() / 86400)
+}
+
+/// Handles `try-rs prune`. Pinned experiments are skipped when `keep_pinned`
+/// is set, unless `--force` is given.
+pub fn run_prune(
+    tries_dir: &Path,
+    project_detectors: &[ProjectDetector],
+    args: &PruneArgs,
+    max_age_days: Option<u64>,
+    pins: &Pins,
+    keep_pinned: bool,
+) -> Result<()> {
+    let Some(days) = args.older_than.or(max_age_days) else {
+        bail!("No age limit given: pass --older-than <DAYS> or set max_age_days in config.toml");
+    };
+
+    let now = SystemTime::now();
+    let mut stale = stale_entries(
+        load_entries(tries_dir, project_detectors),
+        Duration::from_secs(days * 86400),
+        now,
+    );
+    if keep_pinned && !args.force {
+        let before = stale.len();
+        stale.retain(|e| !pins.is_pinned(&tries_dir.join(&e.name)));
+        if stale.len() < before {
+            eprintln!(
+                "Keeping {} pinned experiments (use --force to include them).",
+                before - stale.len()
+            );
+        }
+    }
+    if stale.is_empty() {
+        eprintln!("No experiments older than {} days.", days);
+        return Ok(());
+    }
+
+    eprintln!("Experiments without activity for more than {} days:", days);
+    for (i, entry) in stale.iter().enumerate() {
+        let age = now
+            .duration_since(last_activity(entry))
+            .unwrap_or(Duration::ZERO);
+        let worktree = if entry.is_worktree { " [worktree]" } else { "" };
+        println!(
+            "{:>3}. {}{} ({})",
+            i + 1,
+            entry.name,
+            worktree,
+            format_age(age)
+        );
+    }
+
+    if args.dry_run {
+        eprintln!("Dry run: nothing was removed.");
+        return Ok(());
+    }
+
+    let to_remove = if args.yes {
+        (0..stale.len()).collect()
+    } else {
+        eprint!(
+            "Remove {} experiments? [y/N] or numbers to keep (e.g. \"1 3\"): ",
+            stale.len()
+        );
+        io::stderr().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        match parse_confirmation(&input, stale.len()) {
+            Some(indices) => indices,
+            None => {
+                eprintln!("Aborted.");
+                return Ok(());
+            }
+        }
+    };
+
+    let mut removed = 0;
+    for i in to_remove {
+        let entry = &stale[i];
+        match utils::remove_try_folder(&tries_dir.join(&entry.name)) {
+            Ok(()) => {
+                removed += 1;
+                eprintln!("Removed: {}", entry.name);
+            }
+            Err(msg) => eprintln!("{} ({})", msg, entry.name),
+        }
+    }
+    eprintln!("Pruned {} experiments.", removed);
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use anyhow::{Result, bail};
+use std::path::{Path, PathBuf};
+
+use crate::cli::MvArgs;
+use crate::history::{self, History};
+use crate::pins::{self, Pins};
+use crate::utils;
+
+/// Builds the new folder name for a rename. A date prefix typed by the user is
+/// kept as is; otherwise the old prefix is carried over, or replaced by `today`
+/// when `redate` is set.
+pub fn renamed_folder_name(old: &str, new: &str, redate: bool, today: &str) -> String {
+    let new = new.trim();
+    let (prefix, name) = match new.split_once(' ') {
+        Some((prefix, name)) if utils::extract_prefix_date(new).is_some() => (Some(prefix), name),
+        _ => (
+            old.split_once(' ')
+                .map(|(prefix, _)| prefix)
+                .filter(|_| utils::extract_prefix_date(old).is_some()),
+            new,
+        ),
+    };
+    match (prefix, redate) {
+        (_, true) => format!("{} {}", today, name),
+        (Some(prefix), false) => format!("{} {}", prefix, name),
+        (None, false) => name.to_string(),
+    }
+}
+
+/// Renames the try folder `old` to `new` inside `tries_dir`, moving git
+/// worktrees with `git worktree move` so the worktree link survives.
+pub fn rename_experiment(tries_dir: &Path, old: &str, new: &str) -> Result<PathBuf, String> {
+    if new.is_empty() || new.contains(['/', '\\']) || new.starts_with('.') {
+        return Err(format!("Error renaming: '{}' is not a valid name", new));
+    }
+    let from = tries_dir.join(old);
+    let to = tries_dir.join(new);
+    if from != to {
+        utils::move_try_folder(&from, &to)?;
+    }
+    Ok(to)
+}
+
+/// Carries the pin and visit history of a renamed folder over to its new path.
+pub fn remember_rename(from: &Path, to: &Path) {
+    let pins_path = pins::pins_path();
+    let mut pins = Pins::load(&pins_path);
+    if pins.is_pinned(from) {
+        pins.rename(from, to);
+        let _ = pins.save(&pins_path);
+    }
+    let history_path = history::history_path();
+    let mut history = History::load(&history_path);
+    history.rename(from, to);
+    let _ = history.save(&history_path);
+}
+
+/// Handles `try-rs mv`.
+pub fn run_mv(tries_dir: &Path, args: &MvArgs) -> Result<()> {
+    let old = if tries_dir.join(&args.old).is_dir() {
+        args.old.clone()
+    } else {
+        let matches = utils::matching_folders(&args.old, &tries_dir.to_path_buf());
+        match matches.as_slice() {
+            [name] => name.clone(),
+            [] => bail!("No experiment named '{}'", args.old),
+            _ => bail!(
+                "'{}' is ambiguous, use the full name: {}",
+                args.old,
+                matches.join(", ")
+            ),
+        }
+    };
+    let new_name = match &args.new {
+        Some(new) => new.clone(),
+        None if args.redate => utils::extract_prefix_date(&old)
+            .map(|(_, name)| name)
+            .unwrap_or_else(|| old.clone()),
+        None => bail!("Give a new name or --redate"),
+    };
+    let new = renamed_folder_name(&old, &new_name, args.redate, &utils::generate_prefix_date());
+    let to = rename_experiment(tries_dir, &old, &new).map_err(anyhow::Error::msg)?;
+    remember_rename(&tries_dir.join(&old), &to);
+    eprintln!("Renamed: {} -> {}", old, new);
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::utils::{self, expand_path};
+
+/// Name of the root given by `tries_path` (or `TRY_PATH`).
+pub const DEFAULT_ROOT: &str = "default";
+
+/// A named tries directory (`[roots.<name>]` in config.toml).
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct RootConfig {
+    pub path: String,
+}
+
+/// A tries directory the experiments are loaded from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TriesRoot {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Builds the roots: `tries_path` first as the `default` root (falling back to
+/// `default_path` when no roots are configured), then the configured roots by
+/// name. A root pointing to a directory already listed is skipped.
+pub fn tries_roots(
+    tries_path: Option<PathBuf>,
+    default_path: PathBuf,
+    configured: &BTreeMap<String, RootConfig>,
+) -> Vec<TriesRoot> {
+    let mut roots = Vec::new();
+    if let Some(path) = tries_path.or_else(|| configured.is_empty().then_some(default_path)) {
+        roots.push(TriesRoot {
+            name: DEFAULT_ROOT.to_string(),
+            path,
+        });
+    }
+    for (name, root) in configured {
+        let path = expand_path(&root.path);
+        if !roots.iter().any(|r| r.path == path || r.name == *name) {
+            roots.push(TriesRoot {
+                name: name.clone(),
+                path,
+            });
+        }
+    }
+    roots
+}
+
+/// Index of the root `name`, with the available names as error.
+pub fn find_root(roots: &[TriesRoot], name: &str) -> Result<usize, String> {
+    roots.iter().position(|r| r.name == name).ok_or_else(|| {
+        let names: Vec<&str> = roots.iter().map(|r| r.name.as_str()).collect();
+        format!("Unknown root '{}' (available: {})", name, names.join(", "))
+    })
+}
+
+/// Folders named `name` (with or without their date prefix) in every root.
+pub fn matching_folders_in_roots(name: &str, roots: &[TriesRoot]) -> Vec<PathBuf> {
+    roots
+        .iter()
+        .flat_map(|root| {
+            utils::matching_folders(name, &root.path)
+                .into_iter()
+                .map(|folder| root.path.join(folder))
+        })
+        .collect()
+}
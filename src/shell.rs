@@ -5,17 +5,76 @@ use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
 
+/// Subcommands whose stdout is meant for the user (or a pipe) instead of being
+/// evaluated by the shell wrapper.
+pub const PASSTHROUGH_COMMANDS: &[&str] = &[
+    "list",
+    "prune",
+    "clean",
+    "trash",
+    "archive",
+    "restore",
+    "templates",
+    "mv",
+    "theme",
+];
+
+/// Subcommands printing the `cd` into the experiment they create. Their
+/// output is evaluated even when they are given flags such as `--template` or `--method`.
+pub const CAPTURE_COMMANDS: &[&str] = &["new", "fork"];
+
 /// Returns the shell integration script content for the given shell type.
 /// This is used by --setup-stdout to print the content to stdout.
 pub fn get_shell_content(shell: &Shell) -> String {
     let completions = get_completions_script(shell);
+    let passthrough_sh = PASSTHROUGH_COMMANDS.join("|");
+    let passthrough_fish = PASSTHROUGH_COMMANDS.join(" ");
+    let passthrough_quoted = PASSTHROUGH_COMMANDS
+        .iter()
+        .map(|c| format!("'{c}'"))
+        .collect::<Vec<_>>();
+    let passthrough_ps = passthrough_quoted.join(", ");
+    let passthrough_nu = passthrough_quoted.join(" ");
+    let capture_sh = CAPTURE_COMMANDS.join("|");
+    let capture_fish = CAPTURE_COMMANDS.join(" ");
+    let capture_quoted = CAPTURE_COMMANDS
+        .iter()
+        .map(|c| format!("'{c}'"))
+        .collect::<Vec<_>>();
+    let capture_ps = capture_quoted.join(", ");
+    let capture_nu = capture_quoted.join(" ");
     match shell {
         Shell::Fish => {
             format!(
                 r#"function try-rs
-    # Pass flags/options directly to stdout without capturing
+    # The subcommand, after a leading `--root <name>`
+    set -l cmd ''
+    if set -q argv[1]
+        set cmd $argv[1]
+    end
+    if test "$cmd" = --root; and set -q argv[3]
+        set cmd $argv[3]
+    else if string match -q -- '--root=*' $cmd; and set -q argv[2]
+        set cmd $argv[2]
+    end
+
+    # Subcommands like `list` print for the user, pass them through
+    if contains -- $cmd {passthrough_fish}
+        command try-rs $argv
+        return
+    end
+
+    # Pass flags/options directly to stdout without capturing, except `--root`
+    # and the flags of subcommands creating an experiment (`new --template`)
     for arg in $argv
-        if string match -q -- '-*' $arg
+        if test "$arg" = --root; or string match -q -- '--root=*' $arg
+            continue
+        end
+        if contains -- $arg -h --help
+            command try-rs $argv
+            return
+        end
+        if string match -q -- '-*' $arg; and not contains -- $cmd {capture_fish}
             command try-rs $argv
             return
         end
@@ -36,10 +95,30 @@ end
         Shell::Zsh => {
             format!(
                 r#"try-rs() {{
-    # Pass flags/options directly to stdout without capturing
+    # The subcommand, after a leading `--root <name>`
+    local cmd="$1"
+    case "$1" in
+        --root) cmd="$3" ;;
+        --root=*) cmd="$2" ;;
+    esac
+
+    # Subcommands like `list` print for the user, pass them through
+    case "$cmd" in
+        {passthrough_sh}) command try-rs "$@"; return ;;
+    esac
+
+    # Pass flags/options directly to stdout without capturing, except `--root`
+    # and the flags of subcommands creating an experiment (`new --template`)
     for arg in "$@"; do
         case "$arg" in
-            -*) command try-rs "$@"; return ;;
+            --root|--root=*) ;;
+            -h|--help) command try-rs "$@"; return ;;
+            -*)
+                case "$cmd" in
+                    {capture_sh}) ;;
+                    *) command try-rs "$@"; return ;;
+                esac
+                ;;
         esac
     done
 
@@ -59,10 +138,30 @@ end
         Shell::Bash => {
             format!(
                 r#"try-rs() {{
-    # Pass flags/options directly to stdout without capturing
+    # The subcommand, after a leading `--root <name>`
+    local cmd="$1"
+    case "$1" in
+        --root) cmd="$3" ;;
+        --root=*) cmd="$2" ;;
+    esac
+
+    # Subcommands like `list` print for the user, pass them through
+    case "$cmd" in
+        {passthrough_sh}) command try-rs "$@"; return ;;
+    esac
+
+    # Pass flags/options directly to stdout without capturing, except `--root`
+    # and the flags of subcommands creating an experiment (`new --template`)
     for arg in "$@"; do
         case "$arg" in
-            -*) command try-rs "$@"; return ;;
+            --root|--root=*) ;;
+            -h|--help) command try-rs "$@"; return ;;
+            -*)
+                case "$cmd" in
+                    {capture_sh}) ;;
+                    *) command try-rs "$@"; return ;;
+                esac
+                ;;
         esac
     done
 
@@ -83,9 +182,27 @@ end
             format!(
                 r#"# try-rs integration for PowerShell
 function try-rs {{
-    # Pass flags/options directly to stdout without capturing
+    # The subcommand, after a leading `--root <name>`
+    $cmd = $args[0]
+    if ($args[0] -eq '--root') {{
+        $cmd = $args[2]
+    }} elseif ($args[0] -like '--root=*') {{
+        $cmd = $args[1]
+    }}
+
+    # Subcommands like `list` print for the user, pass them through
+    if ($cmd -and @({passthrough_ps}) -contains $cmd) {{
+        & try-rs.exe @args
+        return
+    }}
+
+    # Pass flags/options directly to stdout without capturing, except `--root`
+    # and the flags of subcommands creating an experiment (`new --template`)
     foreach ($a in $args) {{
-        if ($a -like '-*') {{
+        if ($a -eq '--root' -or $a -like '--root=*') {{
+            continue
+        }}
+        if (@('-h', '--help') -contains $a -or ($a -like '-*' -and @({capture_ps}) -notcontains $cmd)) {{
             & try-rs.exe @args
             return
         }}
@@ -106,9 +223,29 @@ function try-rs {{
         Shell::NuShell => {
             format!(
                 r#"def --wrapped try-rs [...args] {{
-    # Pass flags/options directly to stdout without capturing
+    # The subcommand, after a leading `--root <name>`
+    let first = ($args.0? | default '')
+    let cmd = if $first == '--root' {{
+        $args.2? | default ''
+    }} else if ($first | str starts-with '--root=') {{
+        $args.1? | default ''
+    }} else {{
+        $first
+    }}
+
+    # Subcommands like `list` print for the user, pass them through
+    if ($cmd in [{passthrough_nu}]) {{
+        ^try-rs.exe ...$args
+        return
+    }}
+
+    # Pass flags/options directly to stdout without capturing, except `--root`
+    # and the flags of subcommands creating an experiment (`new --template`)
     for arg in $args {{
-        if ($arg | str starts-with '-') {{
+        if ($arg == '--root') or ($arg | str starts-with '--root=') {{
+            continue
+        }}
+        if ($arg in ['-h' '--help']) or (($arg | str starts-with '-') and not ($cmd in [{capture_nu}])) {{
             ^try-rs.exe ...$args
             return
         }}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::UNIX_EPOCH;
+
+use crate::config::get_data_dir;
+use crate::utils::glob_match;
+
+/// Disk usage of one try folder, in bytes.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct FolderSize {
+    pub total: u64,
+    /// Part of `total` in build artifacts (`target/`, `node_modules/`, ...)
+    pub artifacts: u64,
+}
+
+/// Sums the files below `path`, counting directories matching one of the
+/// `artifacts` patterns separately. Symlinks are not followed.
+pub fn folder_size(path: &Path, artifacts: &[String]) -> FolderSize {
+    let mut size = FolderSize::default();
+    let mut stack = vec![(path.to_path_buf(), false)];
+    while let Some((dir, in_artifact)) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let is_artifact = in_artifact
+                    || artifacts
+                        .iter()
+                        .any(|a| glob_match(a, &entry.file_name().to_string_lossy()));
+                stack.push((entry.path(), is_artifact));
+            } else if file_type.is_file() {
+                let len = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                size.total += len;
+                if in_artifact {
+                    size.artifacts += len;
+                }
+            }
+        }
+    }
+    size
+}
+
+/// Formats a byte count for the size column, e.g. `340M` or `1.2G`.
+pub fn format_short(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    let mb = bytes as f64 / MB;
+    if mb >= 1000.0 {
+        format!("{:.1}G", mb / 1024.0)
+    } else if mb >= 1.0 {
+        format!("{:.0}M", mb)
+    } else {
+        format!("{:.0}K", bytes as f64 / 1024.0)
+    }
+}
+
+/// Formats a byte count for the preview, e.g. `340 MB` or `1.2 GB`.
+pub fn format_long(bytes: u64) -> String {
+    let short = format_short(bytes);
+    let (number, unit) = short.split_at(short.len() - 1);
+    format!("{} {}B", number, unit)
+}
+
+/// Unix timestamp (seconds) of the newest mtime among `folder`, the artifact
+/// directories directly inside it and their subdirectories. Builds and
+/// installs write below `target/` or `node_modules/` without touching the
+/// folder itself.
+pub fn contents_mtime(folder: &Path, artifacts: &[String]) -> i64 {
+    let mtime = |path: &Path| {
+        fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0)
+    };
+    let subdirs = |dir: &Path| -> Vec<fs::DirEntry> {
+        fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut newest = mtime(folder);
+    if artifacts.is_empty() {
+        return newest;
+    }
+    for entry in subdirs(folder) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !artifacts.iter().any(|a| glob_match(a, &name)) {
+            continue;
+        }
+        newest = newest.max(mtime(&entry.path()));
+        for inner in subdirs(&entry.path()) {
+            newest = newest.max(mtime(&inner.path()));
+        }
+    }
+    newest
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct CachedSize {
+    /// The `contents_mtime` of the folder the size was computed for
+    pub mtime: i64,
+    #[serde(flatten)]
+    pub size: FolderSize,
+}
+
+/// Sizes from previous runs, keyed by the full path of the folder.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct SizeCache {
+    #[serde(default)]
+    pub folders: BTreeMap<String, CachedSize>,
+}
+
+pub fn size_cache_path() -> PathBuf {
+    get_data_dir().join("sizes.toml")
+}
+
+impl SizeCache {
+    pub fn load(path: &Path) -> SizeCache {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the cache, forgetting folders that no longer exist.
+    pub fn save(&mut self, path: &Path) -> std::io::Result<()> {
+        self.folders.retain(|folder, _| Path::new(folder).exists());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string(self).map_err(std::io::Error::other)?;
+        fs::write(path, contents)
+    }
+
+    /// The cached size, with the `contents_mtime` it was measured for.
+    pub fn get(&self, folder: &Path) -> Option<CachedSize> {
+        self.folders.get(folder.to_string_lossy().as_ref()).copied()
+    }
+
+    pub fn insert(&mut self, folder: &Path, mtime: i64, size: FolderSize) {
+        self.folders.insert(
+            folder.to_string_lossy().to_string(),
+            CachedSize { mtime, size },
+        );
+    }
+}
+
+/// A folder to measure, with its artifact patterns.
+pub struct SizeJob {
+    pub path: PathBuf,
+    pub artifacts: Vec<String>,
+}
+
+/// Size of each entry by path, filled in by the worker pool.
+pub type FolderSizes = Arc<Mutex<HashMap<PathBuf, FolderSize>>>;
+
+/// Fills `sizes` from the cache and measures the changed folders in a pool
+/// of background threads. Changed folders show their previous size until
+/// measured. The cache file is updated once all are done.
+pub fn spawn_size_workers(jobs: Vec<SizeJob>, sizes: &FolderSizes, cache_path: PathBuf) {
+    let sizes = Arc::clone(sizes);
+    let workers = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(2)
+        .min(4);
+    thread::spawn(move || {
+        let mut cache = SizeCache::load(&cache_path);
+        let mut pending = Vec::new();
+        for job in jobs {
+            let mtime = contents_mtime(&job.path, &job.artifacts);
+            let cached = cache.get(&job.path);
+            if let (Some(cached), Ok(mut sizes)) = (cached, sizes.lock()) {
+                sizes.insert(job.path.clone(), cached.size);
+            }
+            if cached.is_none_or(|cached| cached.mtime != mtime) {
+                pending.push((job, mtime));
+            }
+        }
+        if pending.is_empty() {
+            return;
+        }
+
+        let queue = Arc::new(Mutex::new(pending));
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                let sizes = Arc::clone(&sizes);
+                thread::spawn(move || {
+                    let mut measured = Vec::new();
+                    while let Some((job, mtime)) = queue.lock().ok().and_then(|mut q| q.pop()) {
+                        let size = folder_size(&job.path, &job.artifacts);
+                        if let Ok(mut sizes) = sizes.lock() {
+                            sizes.insert(job.path.clone(), size);
+                        }
+                        measured.push((job.path, mtime, size));
+                    }
+                    measured
+                })
+            })
+            .collect();
+        for handle in handles {
+            for (path, mtime, size) in handle.join().unwrap_or_default() {
+                cache.insert(&path, mtime, size);
+            }
+        }
+        let _ = cache.save(&cache_path);
+    });
+}
@@ -0,0 +1,246 @@
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::get_config_dir;
+use crate::utils;
+
+/// Name of the manifest file inside a directory template.
+pub const MANIFEST_FILE_NAME: &str = "template.toml";
+
+/// The `template.toml` (or `<name>.toml`) describing a template.
+#[derive(Deserialize, Default, Clone, Debug)]
+pub struct TemplateManifest {
+    pub description: Option<String>,
+    /// Variables with their default values, referenced as `{{key}}`
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    #[serde(default)]
+    pub files: Vec<TemplateFile>,
+    /// Shell commands run inside the new folder, e.g. `cargo init` or `git init`
+    #[serde(default)]
+    pub commands: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TemplateFile {
+    pub path: String,
+    #[serde(default)]
+    pub content: String,
+}
+
+/// A template from the templates directory: either `<name>.toml` or a
+/// `<name>/` directory whose files are copied (with an optional manifest).
+#[derive(Clone, Debug)]
+pub struct Template {
+    pub name: String,
+    pub manifest: TemplateManifest,
+    /// Directory whose content is copied into the new folder
+    pub dir: Option<PathBuf>,
+}
+
+pub fn templates_dir() -> PathBuf {
+    get_config_dir().join("templates")
+}
+
+/// Loads every template in `dir`, sorted by name. Templates with an invalid
+/// manifest are skipped; `find_template` reports their error.
+pub fn load_templates(dir: &Path) -> Vec<Template> {
+    let mut templates = Vec::new();
+    if let Ok(read_dir) = fs::read_dir(dir) {
+        for entry in read_dir.flatten() {
+            if let Ok(template) = load_template(&entry.path()) {
+                templates.push(template);
+            }
+        }
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
+fn load_template(path: &Path) -> Result<Template> {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if path.is_dir() {
+        let manifest_path = path.join(MANIFEST_FILE_NAME);
+        let manifest = if manifest_path.exists() {
+            parse_manifest(&manifest_path)?
+        } else {
+            TemplateManifest::default()
+        };
+        Ok(Template {
+            name: file_name,
+            manifest,
+            dir: Some(path.to_path_buf()),
+        })
+    } else if let Some(name) = file_name.strip_suffix(".toml") {
+        Ok(Template {
+            name: name.to_string(),
+            manifest: parse_manifest(path)?,
+            dir: None,
+        })
+    } else {
+        bail!("'{}' is not a template", file_name)
+    }
+}
+
+fn parse_manifest(path: &Path) -> Result<TemplateManifest> {
+    let contents = fs::read_to_string(path)?;
+    toml::from_str(&contents).with_context(|| format!("invalid template '{}'", path.display()))
+}
+
+/// Finds a template by name in `dir`.
+pub fn find_template(dir: &Path, name: &str) -> Result<Template> {
+    for candidate in [dir.join(name), dir.join(format!("{name}.toml"))] {
+        if candidate.exists() {
+            return load_template(&candidate);
+        }
+    }
+    let available: Vec<String> = load_templates(dir).into_iter().map(|t| t.name).collect();
+    if available.is_empty() {
+        bail!(
+            "Template '{}' not found, no templates in {}",
+            name,
+            dir.display()
+        );
+    }
+    bail!(
+        "Template '{}' not found, available: {}",
+        name,
+        available.join(", ")
+    )
+}
+
+impl Template {
+    /// Overrides variables with `key=value` pairs from the command line.
+    pub fn with_vars(mut self, vars: &[String]) -> Result<Self> {
+        for var in vars {
+            let Some((key, value)) = var.split_once('=') else {
+                bail!("Invalid variable '{}', expected key=value", var);
+            };
+            self.manifest
+                .variables
+                .insert(key.trim().to_string(), value.to_string());
+        }
+        Ok(self)
+    }
+
+    /// Fills the freshly created folder at `path`: copies the template directory,
+    /// writes the manifest files and runs the post-create commands.
+    pub fn apply(&self, path: &Path) -> Result<()> {
+        let vars = self.variables_for(path);
+        if let Some(dir) = &self.dir {
+            copy_template_dir(dir, path, &vars)?;
+        }
+        for file in &self.manifest.files {
+            let target = path.join(render(&file.path, &vars));
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&target, render(&file.content, &vars))?;
+        }
+        for command in &self.manifest.commands {
+            let command = render(command, &vars);
+            eprintln!("$ {}", command);
+            // stdout is evaluated by the shell wrapper, send the output to stderr
+            let status = shell_command(&command)
+                .current_dir(path)
+                .stdout(std::io::stderr())
+                .status();
+            match status {
+                Ok(s) if s.success() => {}
+                _ => eprintln!("Warning: '{}' failed", command),
+            }
+        }
+        Ok(())
+    }
+
+    /// Built-in variables (`name`, `folder`, `date`, `path`) followed by the
+    /// template's own, which may reference the built-ins.
+    fn variables_for(&self, path: &Path) -> BTreeMap<String, String> {
+        let folder = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let name = utils::extract_prefix_date(&folder)
+            .map(|(_, name)| name)
+            .unwrap_or_else(|| folder.clone());
+        let mut vars = BTreeMap::from([
+            ("name".to_string(), name),
+            ("folder".to_string(), folder),
+            ("date".to_string(), utils::generate_prefix_date()),
+            ("path".to_string(), path.to_string_lossy().to_string()),
+        ]);
+        for (key, value) in &self.manifest.variables {
+            let value = render(value, &vars);
+            vars.insert(key.clone(), value);
+        }
+        vars
+    }
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(not(unix))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+/// Replaces every `{{key}}` with its value. Unknown keys are left untouched.
+pub fn render(text: &str, vars: &BTreeMap<String, String>) -> String {
+    let mut result = text.to_string();
+    for (key, value) in vars {
+        result = result.replace(&format!("{{{{{key}}}}}"), value);
+    }
+    result
+}
+
+/// Copies a directory template, rendering variables in file names and in
+/// text files. The manifest itself is not copied.
+fn copy_template_dir(src: &Path, dest: &Path, vars: &BTreeMap<String, String>) -> Result<()> {
+    for entry in fs::read_dir(src)?.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if src.join(MANIFEST_FILE_NAME) == entry.path() {
+            continue;
+        }
+        let target = dest.join(render(&file_name, vars));
+        if entry.file_type()?.is_dir() {
+            fs::create_dir_all(&target)?;
+            copy_template_dir(&entry.path(), &target, vars)?;
+        } else {
+            let bytes = fs::read(entry.path())?;
+            match String::from_utf8(bytes) {
+                Ok(text) => fs::write(&target, render(&text, vars))?,
+                Err(e) => fs::write(&target, e.into_bytes())?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Handles `try-rs templates`.
+pub fn run_templates(dir: &Path) -> Result<()> {
+    let templates = load_templates(dir);
+    if templates.is_empty() {
+        eprintln!("No templates in {}", dir.display());
+    }
+    for template in templates {
+        match &template.manifest.description {
+            Some(description) => println!("{}\t{}", template.name, description),
+            None => println!("{}", template.name),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use crate::color_depth::is_light_rgb;
+
+/// Brightness of the terminal background, picks `theme_light` or `theme_dark`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Background {
+    Light,
+    Dark,
+}
+
+/// How long the terminal gets to answer the background color query.
+pub const QUERY_TIMEOUT: Duration = Duration::from_millis(150);
+
+impl Background {
+    fn from_rgb(rgb: (u8, u8, u8)) -> Self {
+        if is_light_rgb(rgb) {
+            Background::Light
+        } else {
+            Background::Dark
+        }
+    }
+}
+
+/// The background from the environment: `TRY_BACKGROUND` (`light` or `dark`),
+/// then `COLORFGBG` (`fg;bg` color indices, set by rxvt and Konsole).
+pub fn background_from_env(env: &dyn Fn(&str) -> Option<String>) -> Option<Background> {
+    if let Some(hint) = env("TRY_BACKGROUND") {
+        match hint.to_lowercase().as_str() {
+            "light" => return Some(Background::Light),
+            "dark" => return Some(Background::Dark),
+            _ => {}
+        }
+    }
+    let colorfgbg = env("COLORFGBG")?;
+    let bg: u8 = colorfgbg.rsplit(';').next()?.parse().ok()?;
+    // White (7) and the bright colors but bright black (8) are light
+    Some(if bg == 7 || bg >= 9 {
+        Background::Light
+    } else {
+        Background::Dark
+    })
+}
+
+/// Parses the answer to `OSC 11 ; ?`, e.g. `ESC ] 11 ; rgb:ffff/ffff/ffff ESC \`.
+pub fn parse_osc11_response(response: &[u8]) -> Option<(u8, u8, u8)> {
+    let text = String::from_utf8_lossy(response);
+    let start = text.find("]11;")? + 4;
+    let rest = &text[start..];
+    let end = rest.find(['\x07', '\x1b']).unwrap_or(rest.len());
+    let spec = rest[..end].strip_prefix("rgb:")?;
+    let mut channels = spec.split('/').map(|channel| {
+        // 1 to 4 hex digits, scaled to 8 bits
+        let value = u32::from_str_radix(channel, 16).ok()?;
+        let max = (1u32 << (4 * channel.len().clamp(1, 4))) - 1;
+        Some((value * 255 / max) as u8)
+    });
+    let rgb = (channels.next()??, channels.next()??, channels.next()??);
+    channels.next().is_none().then_some(rgb)
+}
+
+/// Asks the terminal for its background color (OSC 11). A device attributes
+/// query (DA1) follows, which every terminal answers, so the read ends as soon
+/// as the terminal has answered both or shown that it ignores OSC 11.
+#[cfg(unix)]
+pub fn query_background(timeout: Duration) -> Option<Background> {
+    use std::fs::OpenOptions;
+    use std::io::{Read, Write};
+    use std::os::unix::io::AsRawFd;
+    use std::time::Instant;
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    crossterm::terminal::enable_raw_mode().ok()?;
+    let mut response = Vec::new();
+    if tty.write_all(b"\x1b]11;?\x1b\\\x1b[c").is_ok() && tty.flush().is_ok() {
+        let deadline = Instant::now() + timeout;
+        let mut buffer = [0u8; 256];
+        // Until the DA1 answer (`ESC [ ? ... c`) has been read
+        while !response
+            .windows(3)
+            .position(|w| w == b"\x1b[?")
+            .is_some_and(|i| response[i..].contains(&b'c'))
+        {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            let mut poll_fd = libc::pollfd {
+                fd: tty.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let ready =
+                unsafe { libc::poll(&mut poll_fd, 1, remaining.as_millis() as libc::c_int) };
+            if ready <= 0 {
+                break;
+            }
+            match tty.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => response.extend_from_slice(&buffer[..n]),
+            }
+        }
+    }
+    let _ = crossterm::terminal::disable_raw_mode();
+    parse_osc11_response(&response).map(Background::from_rgb)
+}
+
+#[cfg(not(unix))]
+pub fn query_background(_timeout: Duration) -> Option<Background> {
+    None
+}
+
+/// The background of the terminal: the environment hint, else the answer of
+/// the terminal, `None` when neither is known.
+pub fn detect_background() -> Option<Background> {
+    background_from_env(&|name| std::env::var(name).ok())
+        .or_else(|| query_background(QUERY_TIMEOUT))
+}
@@ -0,0 +1,289 @@
+use anyhow::{Context, Result, anyhow, bail};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::user_themes::{PaletteFile, ThemeFile};
+
+/// Color scheme formats `try-rs theme import` understands.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SchemeFormat {
+    /// base16 or base24 YAML (`base00` ... `base0F`)
+    Base16,
+    /// Alacritty TOML (`[colors.primary]`, `[colors.normal]`, ...)
+    Alacritty,
+    /// kitty `.conf` (`background`, `color0` ... `color15`)
+    Kitty,
+    /// WezTerm TOML (`[colors]` with `ansi` and `brights`)
+    WezTerm,
+}
+
+/// Guesses the format from the extension, then from the content.
+pub fn detect_format(path: &Path, contents: &str) -> Result<SchemeFormat> {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "yaml" | "yml" => return Ok(SchemeFormat::Base16),
+        "conf" => return Ok(SchemeFormat::Kitty),
+        _ => {}
+    }
+    if let Ok(table) = contents.parse::<toml::Table>() {
+        let colors = table.get("colors").and_then(|c| c.as_table());
+        if colors.is_some_and(|c| c.contains_key("ansi")) {
+            return Ok(SchemeFormat::WezTerm);
+        }
+        if colors.is_some_and(|c| c.contains_key("primary") || c.contains_key("normal")) {
+            return Ok(SchemeFormat::Alacritty);
+        }
+    }
+    if contents
+        .lines()
+        .any(|l| l.trim_start().starts_with("base00"))
+    {
+        return Ok(SchemeFormat::Base16);
+    }
+    if contents
+        .lines()
+        .any(|l| l.trim_start().starts_with("color0 "))
+    {
+        return Ok(SchemeFormat::Kitty);
+    }
+    bail!(
+        "unrecognized color scheme '{}' (expected base16/base24 YAML, Alacritty or WezTerm TOML, or kitty .conf)",
+        path.display()
+    )
+}
+
+/// Normalizes `#abc123`, `abc123` and `0xabc123` to `#abc123`.
+pub fn normalize_hex(value: &str) -> Result<String> {
+    let trimmed = value.trim().trim_matches(|c| c == '"' || c == '\'');
+    let hex = trimmed
+        .strip_prefix('#')
+        .or_else(|| trimmed.strip_prefix("0x"))
+        .unwrap_or(trimmed);
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!("invalid color '{}'", value.trim());
+    }
+    Ok(format!("#{}", hex.to_lowercase()))
+}
+
+/// Reads the flat `key: value` pairs of a base16/base24 YAML file. Nested
+/// keys (the `palette:` block of newer schemes) are read as if top-level.
+fn parse_flat_yaml(contents: &str) -> BTreeMap<String, String> {
+    let mut values = BTreeMap::new();
+    for line in contents.lines() {
+        let line = line.split(" #").next().unwrap_or_default().trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+            if !value.is_empty() {
+                values.insert(key.trim().to_string(), value.to_string());
+            }
+        }
+    }
+    values
+}
+
+/// Maps a base16 scheme onto the palette following the base16 styling
+/// guidelines (base0D functions in blue, base08 variables in red, ...).
+/// The extra colors of base24 are not needed.
+pub fn parse_base16(contents: &str) -> Result<ThemeFile> {
+    let values = parse_flat_yaml(contents);
+    let color = |key: &str| -> Result<String> {
+        let value = values
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+            .ok_or_else(|| anyhow!("missing {}", key))?;
+        normalize_hex(value).with_context(|| format!("in {}", key))
+    };
+    let background = color("base00")?;
+    Ok(ThemeFile {
+        name: values.get("name").or_else(|| values.get("scheme")).cloned(),
+        background: Some(background.clone()),
+        palette: Some(PaletteFile {
+            accent1: Some(color("base0D")?),
+            accent2: Some(color("base08")?),
+            warm: Some(color("base09")?),
+            cool: Some(color("base0C")?),
+            green: Some(color("base0B")?),
+            yellow: Some(color("base0A")?),
+            purple: Some(color("base0E")?),
+            overlay: Some(color("base03")?),
+            subtext: Some(color("base04")?),
+            surface: Some(color("base02")?),
+            text: Some(color("base05")?),
+            base: Some(background),
+        }),
+        ..ThemeFile::default()
+    })
+}
+
+/// Colors of a terminal scheme, the 16 ANSI colors by index.
+#[derive(Default)]
+struct TerminalColors {
+    name: Option<String>,
+    background: Option<String>,
+    foreground: Option<String>,
+    selection: Option<String>,
+    ansi: [Option<String>; 16],
+}
+
+impl TerminalColors {
+    /// Maps the ANSI colors onto the palette. Terminals have no orange, the
+    /// warm role takes bright red.
+    fn into_theme_file(self) -> Result<ThemeFile> {
+        let ansi = |i: usize| -> Result<String> {
+            let value = self.ansi[i]
+                .as_deref()
+                .or_else(|| {
+                    if i >= 8 {
+                        self.ansi[i - 8].as_deref()
+                    } else {
+                        None
+                    }
+                })
+                .ok_or_else(|| anyhow!("missing ANSI color {}", i))?;
+            normalize_hex(value).with_context(|| format!("in ANSI color {}", i))
+        };
+        let required = |value: &Option<String>, key: &str| -> Result<String> {
+            let value = value.as_deref().ok_or_else(|| anyhow!("missing {}", key))?;
+            normalize_hex(value).with_context(|| format!("in {}", key))
+        };
+        let background = required(&self.background, "background")?;
+        let surface = match &self.selection {
+            Some(selection) => normalize_hex(selection).context("in selection background")?,
+            None => ansi(8)?,
+        };
+        Ok(ThemeFile {
+            name: self.name.clone(),
+            background: Some(background.clone()),
+            palette: Some(PaletteFile {
+                accent1: Some(ansi(4)?),
+                accent2: Some(ansi(1)?),
+                warm: Some(ansi(9)?),
+                cool: Some(ansi(6)?),
+                green: Some(ansi(2)?),
+                yellow: Some(ansi(3)?),
+                purple: Some(ansi(5)?),
+                overlay: Some(ansi(8)?),
+                subtext: Some(ansi(7)?),
+                surface: Some(surface),
+                text: Some(required(&self.foreground, "foreground")?),
+                base: Some(background),
+            }),
+            ..ThemeFile::default()
+        })
+    }
+}
+
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+fn toml_str(table: Option<&toml::Table>, key: &str) -> Option<String> {
+    table?.get(key)?.as_str().map(str::to_string)
+}
+
+pub fn parse_alacritty(contents: &str) -> Result<ThemeFile> {
+    let table: toml::Table = toml::from_str(contents).context("invalid Alacritty TOML")?;
+    let colors = table
+        .get("colors")
+        .and_then(|c| c.as_table())
+        .ok_or_else(|| anyhow!("missing [colors]"))?;
+    let section = |name: &str| colors.get(name).and_then(|s| s.as_table());
+    let mut scheme = TerminalColors {
+        background: toml_str(section("primary"), "background"),
+        foreground: toml_str(section("primary"), "foreground"),
+        selection: toml_str(section("selection"), "background")
+            // `CellForeground` and friends are not colors
+            .filter(|s| normalize_hex(s).is_ok()),
+        ..TerminalColors::default()
+    };
+    for (i, name) in ANSI_NAMES.iter().enumerate() {
+        scheme.ansi[i] = toml_str(section("normal"), name);
+        scheme.ansi[i + 8] = toml_str(section("bright"), name);
+    }
+    scheme.into_theme_file()
+}
+
+pub fn parse_kitty(contents: &str) -> Result<ThemeFile> {
+    let mut scheme = TerminalColors::default();
+    for line in contents.lines() {
+        let line = line.trim();
+        // kitty themes carry their name in a `## name: ...` header
+        if let Some(name) = line.strip_prefix("## name:") {
+            scheme.name = Some(name.trim().to_string());
+            continue;
+        }
+        let Some((key, value)) = line.split_once(char::is_whitespace) else {
+            continue;
+        };
+        let value = Some(value.trim().to_string());
+        match key {
+            "background" => scheme.background = value,
+            "foreground" => scheme.foreground = value,
+            "selection_background" => scheme.selection = value,
+            _ => {
+                if let Some(i) = key
+                    .strip_prefix("color")
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .filter(|i| *i < 16)
+                {
+                    scheme.ansi[i] = value;
+                }
+            }
+        }
+    }
+    scheme.into_theme_file()
+}
+
+pub fn parse_wezterm(contents: &str) -> Result<ThemeFile> {
+    let table: toml::Table = toml::from_str(contents).context("invalid WezTerm TOML")?;
+    let colors = table.get("colors").and_then(|c| c.as_table());
+    let mut scheme = TerminalColors {
+        name: toml_str(table.get("metadata").and_then(|m| m.as_table()), "name"),
+        background: toml_str(colors, "background"),
+        foreground: toml_str(colors, "foreground"),
+        selection: toml_str(colors, "selection_bg"),
+        ..TerminalColors::default()
+    };
+    for (key, offset) in [("ansi", 0), ("brights", 8)] {
+        let list = colors
+            .and_then(|c| c.get(key))
+            .and_then(|l| l.as_array())
+            .cloned()
+            .unwrap_or_default();
+        for (i, value) in list.iter().take(8).enumerate() {
+            scheme.ansi[offset + i] = value.as_str().map(str::to_string);
+        }
+    }
+    scheme.into_theme_file()
+}
+
+/// Reads the color scheme at `path` as a theme file.
+pub fn import_scheme(path: &Path) -> Result<ThemeFile> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("cannot read '{}'", path.display()))?;
+    let theme_file = match detect_format(path, &contents)? {
+        SchemeFormat::Base16 => parse_base16(&contents),
+        SchemeFormat::Alacritty => parse_alacritty(&contents),
+        SchemeFormat::Kitty => parse_kitty(&contents),
+        SchemeFormat::WezTerm => parse_wezterm(&contents),
+    };
+    theme_file.with_context(|| format!("cannot import '{}'", path.display()))
+}
+
+/// File name for a theme: lower case, words joined by dashes.
+pub fn theme_file_name(name: &str) -> String {
+    let slug: Vec<String> = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+    format!("{}.toml", slug.join("-"))
+}
@@ -0,0 +1,302 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, List, ListItem, ListState, Paragraph, StatefulWidget, Widget, Wrap,
+};
+
+use crate::projects::ProjectDetector;
+use crate::themes::{PALETTE_ROLES, Theme};
+
+/// Size of the `try-rs theme show` sample when the terminal size is unknown.
+pub const SAMPLE_SIZE: (u16, u16) = (100, 20);
+
+/// SGR parameters of a foreground or background color.
+fn sgr_color(color: Color, foreground: bool) -> String {
+    let base = if foreground { 30 } else { 40 };
+    let named = |index: u8| match index {
+        0..8 => (base + index as u16).to_string(),
+        _ => (base + 60 + index as u16 - 8).to_string(),
+    };
+    match color {
+        Color::Reset => (base + 9).to_string(),
+        Color::Black => named(0),
+        Color::Red => named(1),
+        Color::Green => named(2),
+        Color::Yellow => named(3),
+        Color::Blue => named(4),
+        Color::Magenta => named(5),
+        Color::Cyan => named(6),
+        Color::Gray => named(7),
+        Color::DarkGray => named(8),
+        Color::LightRed => named(9),
+        Color::LightGreen => named(10),
+        Color::LightYellow => named(11),
+        Color::LightBlue => named(12),
+        Color::LightMagenta => named(13),
+        Color::LightCyan => named(14),
+        Color::White => named(15),
+        Color::Indexed(i) => format!("{};5;{}", base + 8, i),
+        Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+    }
+}
+
+/// Escape sequence switching to `fg`, `bg` and `modifier` from a reset state.
+fn sgr(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut params = vec!["0".to_string()];
+    for (flag, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+    ] {
+        if modifier.contains(flag) {
+            params.push(code.to_string());
+        }
+    }
+    if fg != Color::Reset {
+        params.push(sgr_color(fg, true));
+    }
+    if bg != Color::Reset {
+        params.push(sgr_color(bg, false));
+    }
+    format!("\x1b[{}m", params.join(";"))
+}
+
+/// The buffer as lines of text with ANSI escape sequences, to print it
+/// outside of the TUI.
+pub fn buffer_to_ansi(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut current = None;
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            if cell.skip {
+                continue;
+            }
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if current != Some(style) {
+                out.push_str(&sgr(cell.fg, cell.bg, cell.modifier));
+                current = Some(style);
+            }
+            out.push_str(cell.symbol());
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// One colored block per palette role, as shown by `try-rs theme list`.
+pub fn theme_swatches(theme: &Theme) -> String {
+    let mut out = String::new();
+    for (_, fields) in PALETTE_ROLES {
+        if let Some(color) = theme.color(fields[0]) {
+            out.push_str(&sgr(color, Color::Reset, Modifier::empty()));
+            out.push_str("██");
+        }
+    }
+    out.push_str("\x1b[0m");
+    out
+}
+
+/// `try-rs theme list`: the themes by group with their palette.
+pub fn theme_list(themes: &[Theme]) -> String {
+    let width = themes
+        .iter()
+        .map(|t| t.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for (label, light) in [("Dark", false), ("Light", true)] {
+        let group: Vec<&Theme> = themes.iter().filter(|t| t.is_light() == light).collect();
+        if group.is_empty() {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&format!("{}:\n", label));
+        for theme in group {
+            out.push_str(&format!(
+                "  {:<width$}  {}\n",
+                theme.name,
+                theme_swatches(theme),
+                width = width
+            ));
+        }
+    }
+    out
+}
+
+/// A static sample of the main layout (search, folders, preview, legends)
+/// drawn with `theme`, for `try-rs theme show`.
+pub fn sample_buffer(theme: &Theme, width: u16, height: u16) -> Buffer {
+    let area = Rect::new(0, 0, width, height);
+    let mut buffer = Buffer::empty(area);
+    if let Some(background) = theme.background {
+        buffer.set_style(area, Style::default().bg(background));
+    }
+    let style = |color: Color| Style::default().fg(color);
+    let boxed = |title: &'static str, title_color: Color, border_color: Color| {
+        Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled(title, style(title_color)))
+            .border_style(style(border_color))
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .split(area);
+    let content_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+        .split(chunks[1]);
+    let search_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(20), Constraint::Length(45)])
+        .split(chunks[0]);
+    let right_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(4)])
+        .split(content_chunks[1]);
+
+    Paragraph::new("rust")
+        .style(style(theme.search_title))
+        .block(boxed(
+            " Search/New ",
+            theme.search_title,
+            theme.search_border,
+        ))
+        .render(search_chunks[0], &mut buffer);
+    Paragraph::new(Line::from(vec![
+        Span::styled("󰋊 ", style(theme.title_rs)),
+        Span::styled("Used: ", style(theme.helpers_colors)),
+        Span::styled("1.2 GB", style(theme.status_message)),
+        Span::styled(" | ", style(theme.helpers_colors)),
+        Span::styled("Free: ", style(theme.helpers_colors)),
+        Span::styled("84.0 GB", style(theme.status_message)),
+    ]))
+    .block(boxed(" Disk ", theme.disk_title, theme.disk_border))
+    .alignment(Alignment::Center)
+    .render(search_chunks[1], &mut buffer);
+
+    let detectors = ProjectDetector::all();
+    let icon = |name: &str| {
+        detectors
+            .iter()
+            .find(|d| d.name == name)
+            .map(|d| Span::styled(d.icon.clone(), style(theme.role_color(d.color))))
+            .unwrap_or_default()
+    };
+    let entries = [
+        (
+            "2025-03-14",
+            "rust-parser",
+            vec![icon("Rust"), Span::styled(" ", style(theme.icon_git))],
+            "2h ago",
+        ),
+        (
+            "2025-03-10",
+            "rustlings",
+            vec![Span::styled("󰙅 ", style(theme.icon_worktree))],
+            "4d ago",
+        ),
+        ("2025-02-27", "trust-dns-demo", vec![icon("Go")], "2w ago"),
+        (
+            "2025-01-05",
+            "rusty-notebook",
+            vec![icon("Python")],
+            "2mo ago",
+        ),
+    ];
+    let items: Vec<ListItem> = entries
+        .into_iter()
+        .map(|(date, name, icons, age)| {
+            let mut spans = vec![
+                Span::styled(" 󰝰 ", style(theme.icon_folder)),
+                Span::styled(date, style(theme.list_date)),
+                Span::raw(format!(" {:<16}", name)),
+            ];
+            spans.extend(icons);
+            spans.push(Span::styled(format!("  {}", age), style(theme.list_date)));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let mut state = ListState::default();
+    state.select(Some(0));
+    StatefulWidget::render(
+        List::new(items)
+            .block(boxed(
+                " Folders (4, by frecency) ",
+                theme.folder_title,
+                theme.folder_border,
+            ))
+            .highlight_style(theme.highlight_style())
+            .highlight_symbol("→ "),
+        content_chunks[0],
+        &mut buffer,
+        &mut state,
+    );
+
+    Paragraph::new(vec![
+        Line::from(Span::styled("├── src", style(theme.icon_folder))),
+        Line::from(Span::styled("│   └── main.rs", style(theme.icon_file))),
+        Line::from(Span::styled("└── Cargo.toml", style(theme.icon_file))),
+        Line::from(""),
+        Line::from(Span::styled(
+            "── Cargo.toml ──",
+            style(theme.preview_title).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(vec![
+            Span::styled("name", style(theme.title_try)),
+            Span::raw(" = "),
+            Span::styled("\"rust-parser\"", style(theme.icon_worktree)),
+        ]),
+    ])
+    .block(boxed(
+        " Preview ",
+        theme.preview_title,
+        theme.preview_border,
+    ))
+    .render(right_chunks[0], &mut buffer);
+
+    let mut legend = vec![
+        icon("Rust"),
+        Span::styled("Rust ", style(theme.helpers_colors)),
+    ];
+    legend.extend([
+        Span::styled("󰙅 ", style(theme.icon_worktree)),
+        Span::styled("Git-Worktree ", style(theme.helpers_colors)),
+        Span::styled(" ", style(theme.icon_git)),
+        Span::styled("Git ", style(theme.helpers_colors)),
+    ]);
+    Paragraph::new(Line::from(legend))
+        .block(boxed(
+            " Legends ",
+            theme.legends_title,
+            theme.legends_border,
+        ))
+        .wrap(Wrap { trim: true })
+        .render(right_chunks[1], &mut buffer);
+
+    Paragraph::new(Line::from(vec![
+        Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" Select | "),
+        Span::styled("Ctrl-T", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" Theme | "),
+        Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" Quit"),
+    ]))
+    .style(style(theme.helpers_colors))
+    .alignment(Alignment::Center)
+    .render(chunks[2], &mut buffer);
+    buffer
+}
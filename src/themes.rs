@@ -1,6 +1,9 @@
-use ratatui::style::Color;
+use ratatui::style::{Color, Modifier, Style};
 
-#[derive(Clone)]
+use crate::color_depth::{ColorDepth, color_rgb, is_light_rgb};
+use crate::projects::ColorRole;
+
+#[derive(Clone, Debug)]
 pub struct Theme {
     pub name: String,
     // Background color (None means transparent/terminal default)
@@ -46,6 +49,10 @@ pub struct Theme {
     pub icon_git: Color,
     pub icon_folder: Color,
     pub icon_file: Color,
+    // Colors the terminal shows, the fields above are already reduced to it
+    pub color_depth: ColorDepth,
+    // Meant for a light terminal background, kept when the colors are reduced
+    pub light: bool,
 }
 
 impl Default for Theme {
@@ -120,6 +127,8 @@ impl Theme {
             icon_git: p.accent2,
             icon_folder: p.yellow,
             icon_file: p.subtext,
+            color_depth: ColorDepth::TrueColor,
+            light: color_rgb(background.unwrap_or(p.base)).is_some_and(is_light_rgb),
         }
     }
 
@@ -579,6 +588,72 @@ impl Theme {
         }
     }
 
+    pub fn catppuccin_latte() -> Self {
+        Self::from_palette(
+            "Catppuccin Latte",
+            Some(Color::Rgb(239, 241, 245)),
+            Palette {
+                accent1: Color::Rgb(30, 102, 245),         // Blue
+                accent2: Color::Rgb(210, 15, 57),          // Red
+                warm: Color::Rgb(254, 100, 11),            // Peach
+                cool: Color::Rgb(23, 146, 153),            // Teal
+                green: Color::Rgb(64, 160, 43),            // Green
+                yellow: Color::Rgb(223, 142, 29),          // Yellow
+                purple: Color::Rgb(136, 57, 239),          // Mauve
+                overlay: Color::Rgb(140, 143, 161),        // Overlay1
+                subtext: Color::Rgb(108, 111, 133),        // Subtext0
+                surface: Color::Rgb(204, 208, 218),        // Surface0
+                text: Color::Rgb(76, 79, 105),             // Text
+                base: Color::Rgb(239, 241, 245),           // Base
+            },
+        )
+    }
+
+    pub fn solarized_light() -> Self {
+        Self {
+            icon_gitmodules: Color::Rgb(211, 54, 130),    // Magenta
+            ..Self::from_palette(
+                "Solarized Light",
+                Some(Color::Rgb(253, 246, 227)),
+                Palette {
+                    accent1: Color::Rgb(38, 139, 210),     // Blue
+                    accent2: Color::Rgb(220, 50, 47),      // Red
+                    warm: Color::Rgb(203, 75, 22),         // Orange
+                    cool: Color::Rgb(42, 161, 152),        // Cyan
+                    green: Color::Rgb(133, 153, 0),        // Green
+                    yellow: Color::Rgb(181, 137, 0),       // Yellow
+                    purple: Color::Rgb(108, 113, 196),     // Violet
+                    overlay: Color::Rgb(147, 161, 161),    // Base1
+                    subtext: Color::Rgb(101, 123, 131),    // Base00
+                    surface: Color::Rgb(238, 232, 213),    // Base2
+                    text: Color::Rgb(88, 110, 117),        // Base01
+                    base: Color::Rgb(253, 246, 227),       // Base3
+                },
+            )
+        }
+    }
+
+    pub fn github_light() -> Self {
+        Self::from_palette(
+            "GitHub Light",
+            Some(Color::Rgb(255, 255, 255)),
+            Palette {
+                accent1: Color::Rgb(9, 105, 218),          // Accent
+                accent2: Color::Rgb(207, 34, 46),          // Danger
+                warm: Color::Rgb(188, 76, 0),              // Severe
+                cool: Color::Rgb(27, 124, 131),            // Teal
+                green: Color::Rgb(26, 127, 55),            // Success
+                yellow: Color::Rgb(154, 103, 0),           // Attention
+                purple: Color::Rgb(130, 80, 223),          // Done
+                overlay: Color::Rgb(140, 149, 159),        // Border (muted)
+                subtext: Color::Rgb(101, 109, 118),        // Fg muted
+                surface: Color::Rgb(221, 244, 255),        // Accent subtle
+                text: Color::Rgb(31, 35, 40),              // Fg default
+                base: Color::Rgb(246, 248, 250),           // Canvas subtle
+            },
+        )
+    }
+
     pub fn all() -> Vec<Theme> {
         vec![
             Theme::default_theme(),
@@ -597,6 +672,168 @@ impl Theme {
             Theme::black_and_white(),
             Theme::matrix(),
             Theme::tron(),
+            Theme::catppuccin_latte(),
+            Theme::solarized_light(),
+            Theme::github_light(),
         ]
     }
 }
+
+/// Theme fields each palette role is mapped to, as in `Theme::from_palette`.
+pub const PALETTE_ROLES: &[(&str, &[&str])] = &[
+    ("accent1", &["title_try", "preview_title", "icon_flutter"]),
+    ("accent2", &["title_rs", "popup_text", "icon_maven", "icon_git"]),
+    ("warm", &["search_title", "icon_rust", "icon_mise"]),
+    ("cool", &["icon_go"]),
+    ("green", &["folder_title", "icon_worktree"]),
+    ("yellow", &["disk_title", "status_message", "icon_python", "icon_folder"]),
+    ("purple", &["legends_title", "icon_gitmodules"]),
+    (
+        "overlay",
+        &[
+            "search_border",
+            "folder_border",
+            "disk_border",
+            "preview_border",
+            "legends_border",
+            "helpers_colors",
+        ],
+    ),
+    ("subtext", &["list_date", "icon_worktree_lock", "icon_file"]),
+    ("surface", &["list_highlight_bg"]),
+    ("text", &["list_highlight_fg"]),
+    ("base", &["popup_bg"]),
+];
+
+/// Generates the by-name access to the color fields used by theme files.
+macro_rules! theme_fields {
+    ($($field:ident),* $(,)?) => {
+        /// Names of the color fields, as written in theme files.
+        pub const FIELDS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+        pub fn color(&self, field: &str) -> Option<Color> {
+            match field {
+                $(stringify!($field) => Some(self.$field),)*
+                _ => None,
+            }
+        }
+
+        pub fn color_mut(&mut self, field: &str) -> Option<&mut Color> {
+            match field {
+                $(stringify!($field) => Some(&mut self.$field),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+impl Theme {
+    theme_fields!(
+        title_try,
+        title_rs,
+        search_title,
+        search_border,
+        folder_title,
+        folder_border,
+        disk_title,
+        disk_border,
+        preview_title,
+        preview_border,
+        legends_title,
+        legends_border,
+        list_date,
+        list_highlight_bg,
+        list_highlight_fg,
+        helpers_colors,
+        status_message,
+        popup_bg,
+        popup_text,
+        icon_rust,
+        icon_maven,
+        icon_flutter,
+        icon_go,
+        icon_python,
+        icon_mise,
+        icon_worktree,
+        icon_worktree_lock,
+        icon_gitmodules,
+        icon_git,
+        icon_folder,
+        icon_file,
+    );
+
+    /// The theme as the terminal can show it: colors reduced to `depth`, and
+    /// for `NoColor` no background.
+    pub fn with_color_depth(mut self, depth: ColorDepth) -> Self {
+        for field in Self::FIELDS {
+            if let Some(slot) = self.color_mut(field) {
+                *slot = depth.quantize(*slot);
+            }
+        }
+        self.background = match depth {
+            ColorDepth::NoColor => None,
+            _ => self.background.map(|c| depth.quantize(c)),
+        };
+        self.color_depth = depth;
+        self
+    }
+
+    /// Style of the selected entry of a list. Without a highlight background
+    /// (`NoColor`) the entry is shown in reverse video.
+    pub fn highlight_style(&self) -> Style {
+        let style = Style::default()
+            .bg(self.list_highlight_bg)
+            .fg(self.list_highlight_fg)
+            .add_modifier(Modifier::BOLD);
+        if self.list_highlight_bg == Color::Reset {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style
+        }
+    }
+
+    /// Whether the theme is meant for a light terminal background.
+    pub fn is_light(&self) -> bool {
+        self.light
+    }
+
+    /// Whether the background (or popup) color is light, which sets `light`.
+    pub fn has_light_colors(&self) -> bool {
+        color_rgb(self.background.unwrap_or(self.popup_bg)).is_some_and(is_light_rgb)
+    }
+
+    /// Sets every field of a palette role (`accent1`, `overlay`, ...).
+    /// Returns false for an unknown role.
+    pub fn set_role(&mut self, role: &str, color: Color) -> bool {
+        let Some((_, fields)) = PALETTE_ROLES.iter().find(|(name, _)| *name == role) else {
+            return false;
+        };
+        for field in *fields {
+            if let Some(slot) = self.color_mut(field) {
+                *slot = color;
+            }
+        }
+        true
+    }
+
+    /// Resolves a color role against this theme.
+    pub fn role_color(&self, role: ColorRole) -> Color {
+        match role {
+            ColorRole::Rust => self.icon_rust,
+            ColorRole::Maven => self.icon_maven,
+            ColorRole::Flutter => self.icon_flutter,
+            ColorRole::Go => self.icon_go,
+            ColorRole::Python => self.icon_python,
+            ColorRole::Mise => self.icon_mise,
+            ColorRole::Accent => self.title_try,
+            ColorRole::Red => self.title_rs,
+            ColorRole::Warm => self.search_title,
+            ColorRole::Cool => self.icon_go,
+            ColorRole::Green => self.icon_worktree,
+            ColorRole::Yellow => self.icon_folder,
+            ColorRole::Purple => self.icon_gitmodules,
+            ColorRole::Subtle => self.icon_file,
+            ColorRole::Fixed(color) => self.color_depth.quantize(color),
+        }
+    }
+}
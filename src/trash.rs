@@ -0,0 +1,263 @@
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use crate::cli::TrashAction;
+use crate::utils;
+
+/// Folder inside the tries directory holding soft-deleted experiments.
+pub const TRASH_DIR_NAME: &str = ".trash";
+
+/// Sidecar metadata stored next to every trashed folder as `<id>.toml`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TrashItem {
+    /// Name of the folder inside the trash (and of the sidecar file)
+    #[serde(skip)]
+    pub id: String,
+    /// Folder name in the tries directory before it was trashed
+    pub original_name: String,
+    /// RFC 3339 timestamp of the deletion
+    pub deleted_at: String,
+    /// Set when the folder was a git worktree, so restoring can re-register it
+    pub worktree: Option<WorktreeInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WorktreeInfo {
+    /// The git directory shared by all worktrees of the repository
+    pub common_dir: PathBuf,
+    /// Branch checked out in the worktree, `None` for a detached HEAD
+    pub branch: Option<String>,
+    pub head: String,
+}
+
+impl TrashItem {
+    pub fn deleted_at(&self) -> Option<DateTime<Local>> {
+        DateTime::parse_from_rfc3339(&self.deleted_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&Local))
+    }
+}
+
+pub fn trash_dir(tries_dir: &Path) -> PathBuf {
+    tries_dir.join(TRASH_DIR_NAME)
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn worktree_info(path: &Path) -> Result<WorktreeInfo> {
+    let common_dir = git(
+        path,
+        &["rev-parse", "--path-format=absolute", "--git-common-dir"],
+    )?;
+    let branch = git(path, &["symbolic-ref", "-q", "--short", "HEAD"]).ok();
+    let head = git(path, &["rev-parse", "HEAD"])?;
+    Ok(WorktreeInfo {
+        common_dir: PathBuf::from(common_dir),
+        branch,
+        head,
+    })
+}
+
+/// Moves a try folder into the trash and writes its sidecar file.
+/// Worktrees are unregistered from their repository after the move.
+pub fn move_to_trash(tries_dir: &Path, name: &str) -> Result<TrashItem> {
+    let source = tries_dir.join(name);
+    let trash = trash_dir(tries_dir);
+    fs::create_dir_all(&trash)?;
+
+    let worktree = if utils::is_git_worktree(&source) {
+        Some(worktree_info(&source)?)
+    } else {
+        None
+    };
+
+    let now = Local::now();
+    let base_id = format!("{}-{}", now.format("%Y%m%d%H%M%S"), name);
+    let mut id = base_id.clone();
+    let mut n = 1;
+    while trash.join(&id).exists() || trash.join(format!("{id}.toml")).exists() {
+        n += 1;
+        id = format!("{base_id}-{n}");
+    }
+
+    let item = TrashItem {
+        id: id.clone(),
+        original_name: name.to_string(),
+        deleted_at: now.to_rfc3339(),
+        worktree,
+    };
+    let sidecar = trash.join(format!("{id}.toml"));
+    fs::write(&sidecar, toml::to_string(&item)?)?;
+    if let Err(e) = fs::rename(&source, trash.join(&id)) {
+        let _ = fs::remove_file(&sidecar);
+        return Err(e).with_context(|| format!("could not move '{}' to the trash", name));
+    }
+
+    // The worktree folder is gone from its registered location, let git forget it
+    if let Some(info) = &item.worktree {
+        let _ = git(&info.common_dir, &["worktree", "prune"]);
+    }
+    Ok(item)
+}
+
+/// Returns all trashed items, most recently deleted first.
+pub fn list_trash(tries_dir: &Path) -> Vec<TrashItem> {
+    let trash = trash_dir(tries_dir);
+    let mut items = Vec::new();
+    if let Ok(read_dir) = fs::read_dir(&trash) {
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "toml") {
+                continue;
+            }
+            let Some(id) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+                continue;
+            };
+            if let Ok(contents) = fs::read_to_string(&path)
+                && let Ok(mut item) = toml::from_str::<TrashItem>(&contents)
+                && trash.join(&id).is_dir()
+            {
+                item.id = id;
+                items.push(item);
+            }
+        }
+    }
+    items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    items
+}
+
+/// Finds a trashed item by id, or the most recently trashed one with that original name.
+pub fn find_trash_item(tries_dir: &Path, name: &str) -> Option<TrashItem> {
+    let items = list_trash(tries_dir);
+    items
+        .iter()
+        .find(|i| i.id == name)
+        .or_else(|| items.iter().find(|i| i.original_name == name))
+        .cloned()
+}
+
+/// Moves a trashed folder back to its original name, re-registering worktrees.
+pub fn restore(tries_dir: &Path, item: &TrashItem) -> Result<()> {
+    let trash = trash_dir(tries_dir);
+    let trashed = trash.join(&item.id);
+    let target = tries_dir.join(&item.original_name);
+    if target.exists() {
+        bail!("'{}' already exists", item.original_name);
+    }
+
+    match &item.worktree {
+        None => fs::rename(&trashed, &target)?,
+        Some(info) => {
+            // Let git create a fresh worktree without touching the files, then move
+            // the trashed content in and rebuild the index from HEAD.
+            let target_str = target.to_string_lossy();
+            let args = match &info.branch {
+                Some(branch) => vec!["worktree", "add", "--no-checkout", &target_str, branch],
+                None => vec![
+                    "worktree",
+                    "add",
+                    "--no-checkout",
+                    "--detach",
+                    &target_str,
+                    &info.head,
+                ],
+            };
+            git(&info.common_dir, &args)?;
+            for entry in fs::read_dir(&trashed)?.flatten() {
+                if entry.file_name() != ".git" {
+                    fs::rename(entry.path(), target.join(entry.file_name()))?;
+                }
+            }
+            git(&target, &["reset", "-q"])?;
+            fs::remove_dir_all(&trashed)?;
+        }
+    }
+    let _ = fs::remove_file(trash.join(format!("{}.toml", item.id)));
+    Ok(())
+}
+
+/// Removes a trashed item for good.
+pub fn delete_permanently(tries_dir: &Path, item: &TrashItem) -> Result<()> {
+    let trash = trash_dir(tries_dir);
+    fs::remove_dir_all(trash.join(&item.id))?;
+    fs::remove_file(trash.join(format!("{}.toml", item.id)))?;
+    Ok(())
+}
+
+/// Permanently deletes trashed items older than `older_than` (all when `None`).
+/// Returns the number of removed items.
+pub fn empty_trash(tries_dir: &Path, older_than: Option<Duration>) -> Result<usize> {
+    let now = Local::now();
+    let mut removed = 0;
+    for item in list_trash(tries_dir) {
+        let expired = match (older_than, item.deleted_at()) {
+            (None, _) => true,
+            (Some(max_age), Some(deleted_at)) => {
+                (now - deleted_at).to_std().unwrap_or(Duration::ZERO) > max_age
+            }
+            (Some(_), None) => false,
+        };
+        if expired {
+            delete_permanently(tries_dir, &item)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Handles `try-rs trash <action>`.
+pub fn run_trash(tries_dir: &Path, action: &TrashAction) -> Result<()> {
+    match action {
+        TrashAction::List => {
+            for item in list_trash(tries_dir) {
+                let deleted_at = item
+                    .deleted_at()
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+                let worktree = if item.worktree.is_some() {
+                    " [worktree]"
+                } else {
+                    ""
+                };
+                println!(
+                    "{}\t{}\t{}{}",
+                    item.id, item.original_name, deleted_at, worktree
+                );
+            }
+        }
+        TrashAction::Restore { name } => {
+            let Some(item) = find_trash_item(tries_dir, name) else {
+                bail!("'{}' is not in the trash", name);
+            };
+            restore(tries_dir, &item)?;
+            eprintln!("Restored: {}", item.original_name);
+        }
+        TrashAction::Empty { older_than } => {
+            let removed = empty_trash(
+                tries_dir,
+                older_than.map(|d| Duration::from_secs(d * 86400)),
+            )?;
+            eprintln!("Deleted {} trashed experiments.", removed);
+        }
+    }
+    Ok(())
+}
@@ -1,14 +1,15 @@
 use anyhow::Result;
 use chrono::Local;
 use crossterm::event::{self, Event, KeyCode};
-use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use ratatui::{prelude::*, widgets::*};
 
 use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
     fs,
     io::{self},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
         Arc,
         atomic::{AtomicU64, Ordering},
@@ -17,39 +18,76 @@ use std::{
     time::SystemTime,
 };
 
+pub use crate::entries::TryEntry;
 pub use crate::themes::Theme;
 use crate::{
-    config::{get_file_config_toml_name, save_config},
+    archive::{self, ArchiveFormat},
+    clean::{self, Artifact},
+    config::{AppConfig, SortOrder, get_file_config_toml_name, save_config},
+    entries::{SearchQuery, load_roots_entries},
+    fork::{self, ForkMethod},
+    git_status::{self, GitStatus, GitStatuses},
+    history::{self, History},
+    keybindings::{Action, Keymap, VimPending},
+    metadata::{self, TryMetadata},
+    pins::{self, Pins},
+    preview,
+    projects::ProjectDetector,
+    rename,
+    roots::TriesRoot,
+    sizes::{self, FolderSizes, SizeJob},
+    templates::{self, Template},
+    trash::{self, TrashItem},
     utils::{self, SelectionResult},
 };
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum AppMode {
+    /// Typing into the search box
     Normal,
-    DeleteConfirm,
+    /// Vim-style list navigation (`vim_mode`), `/` or `i` switch to `Normal`
+    Navigate,
+    BatchConfirm,
+    MoveTarget,
     ThemeSelect,
     ConfigSavePrompt,
     ConfigSaveLocationSelect,
     About,
+    Trash,
+    TemplateSelect,
+    EditNotes,
+    Rename,
+    Fork,
+    RootSelect,
+}
+
+/// Action applied to the marked entries (or the selected one if none are marked).
+#[derive(Clone, Copy, PartialEq)]
+pub enum BatchAction {
+    Delete,
+    Archive,
+    Move,
+    /// Remove the build artifacts, keeping the folder
+    Clean,
 }
 
-#[derive(Clone)]
-pub struct TryEntry {
+/// An entry affected by a batch action, with the flags shown in the confirmation popup.
+pub struct BatchTarget {
     pub name: String,
-    pub display_name: String,
-    pub modified: SystemTime,
-    pub created: SystemTime,
-    pub score: i64,
-    pub is_git: bool,
+    pub path: PathBuf,
     pub is_worktree: bool,
-    pub is_worktree_locked: bool,
-    pub is_gitmodules: bool,
-    pub is_mise: bool,
-    pub is_cargo: bool,
-    pub is_maven: bool,
-    pub is_flutter: bool,
-    pub is_go: bool,
-    pub is_python: bool,
+    pub has_changes: bool,
+    pub unpushed: usize,
+    pub is_pinned: bool,
+    /// Build artifacts removed by `BatchAction::Clean`
+    pub artifacts: Vec<Artifact>,
+}
+
+impl BatchAction {
+    /// Delete and archive take the folder out of the list, pinned ones need forcing.
+    pub fn protects_pinned(self) -> bool {
+        matches!(self, BatchAction::Delete | BatchAction::Archive)
+    }
 }
 
 pub struct App {
@@ -78,70 +116,83 @@ pub struct App {
 
     pub cached_free_space_mb: Option<u64>,
     pub folder_size_mb: Arc<AtomicU64>,
+    pub git_statuses: GitStatuses,
+    /// Size of each entry, filled in by a pool of background workers
+    pub folder_sizes: FolderSizes,
+    /// Number of known sizes when the entries were last sorted by size
+    pub sizes_sorted: usize,
+
+    /// Tree and file preview of the entry and theme in `preview_key`
+    pub preview_lines: Vec<Line<'static>>,
+    pub preview_key: Option<(PathBuf, String)>,
+    pub preview_scroll: u16,
+
+    pub project_detectors: Vec<ProjectDetector>,
+    pub keymap: Keymap,
+    pub vim_mode: bool,
+    pub vim_pending: VimPending,
+
+    /// Trashed items of the listed roots with their root, most recent first
+    pub trash_items: Vec<(PathBuf, TrashItem)>,
+    pub trash_list_state: ListState,
+    /// Items trashed during this session with their root, most recent last (for undo)
+    pub undo_stack: Vec<(PathBuf, TrashItem)>,
+
+    /// Paths of the entries marked for a batch action
+    pub marked: HashSet<PathBuf>,
+    pub batch_action: BatchAction,
+    pub batch_targets: Vec<BatchTarget>,
+    /// Destination directory or root name typed in `AppMode::MoveTarget`
+    pub move_target: String,
+
+    pub archive_dir: PathBuf,
+    pub archive_format: ArchiveFormat,
+
+    pub templates: Vec<Template>,
+    pub template_list_state: ListState,
+
+    pub sort_order: SortOrder,
+    pub history: History,
+    /// Frecency of each entry by path, refreshed when the entries are loaded
+    pub frecency: HashMap<PathBuf, f64>,
+
+    pub pins: Pins,
+
+    /// Entry whose notes are edited in `AppMode::EditNotes`
+    pub notes_target: PathBuf,
+    /// Description, tags and source being edited
+    pub notes_fields: [String; 3],
+    pub notes_focus: usize,
+
+    /// Entry renamed in `AppMode::Rename`
+    pub rename_target: PathBuf,
+    /// New name typed in `AppMode::Rename`, without the date prefix
+    pub rename_input: String,
+    pub rename_redate: bool,
+
+    /// Entry forked in `AppMode::Fork`
+    pub fork_source: PathBuf,
+    /// Name of the fork, without the date prefix
+    pub fork_input: String,
+    pub fork_method: ForkMethod,
+
+    pub roots: Vec<TriesRoot>,
+    /// Root the list is restricted to, `None` lists every root
+    pub root_filter: Option<usize>,
+    pub root_list_state: ListState,
 }
 
 impl App {
-    pub fn new(
-        path: PathBuf,
-        theme: Theme,
-        editor_cmd: Option<String>,
-        config_path: Option<PathBuf>,
-        apply_date_prefix: Option<bool>,
-        transparent_background: bool,
-        query: Option<String>,
-    ) -> Self {
-        let mut entries = Vec::new();
-        if let Ok(read_dir) = fs::read_dir(&path) {
-            for entry in read_dir.flatten() {
-                if let Ok(metadata) = entry.metadata()
-                    && metadata.is_dir()
-                {
-                    let name = entry.file_name().to_string_lossy().to_string();
-                    let git_path = entry.path().join(".git");
-                    let is_git = git_path.exists();
-                    let is_worktree = git_path.is_file();
-                    let is_worktree_locked = utils::is_git_worktree_locked(&entry.path());
-                    let is_gitmodules = entry.path().join(".gitmodules").exists();
-                    let is_mise = entry.path().join("mise.toml").exists();
-                    let is_cargo = entry.path().join("Cargo.toml").exists();
-                    let is_maven = entry.path().join("pom.xml").exists();
-
-                    let created;
-                    let display_name;
-                    if let Some((date_prefix, remainder)) = utils::extract_prefix_date(&name) {
-                        created = date_prefix;
-                        display_name = remainder;
-                    } else {
-                        created = metadata.created().unwrap_or(SystemTime::UNIX_EPOCH);
-                        display_name = name.clone();
-                    }
-                    let is_flutter = entry.path().join("pubspec.yaml").exists();
-                    let is_go = entry.path().join("go.mod").exists();
-                    let is_python = entry.path().join("pyproject.toml").exists()
-                        || entry.path().join("requirements.txt").exists();
-                    entries.push(TryEntry {
-                        name,
-                        display_name,
-                        modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
-                        created,
-                        score: 0,
-                        is_git,
-                        is_worktree,
-                        is_worktree_locked,
-                        is_gitmodules,
-                        is_mise,
-                        is_cargo,
-                        is_maven,
-                        is_flutter,
-                        is_go,
-                        is_python,
-                    });
-                }
-            }
-        }
-        entries.sort_by(|a, b| b.modified.cmp(&a.modified));
+    pub fn new(config: &AppConfig, query: Option<String>) -> Self {
+        let path = config.tries_dir.clone();
+        let project_detectors = config.project_detectors.clone();
+        let root_filter = (!config.show_all_roots).then_some(config.active_root);
+        let entries = load_roots_entries(
+            &visible_roots(&config.roots, root_filter),
+            &project_detectors,
+        );
 
-        let themes = Theme::all();
+        let themes = config.themes.clone();
 
         let mut theme_state = ListState::default();
         theme_state.select(Some(0));
@@ -153,22 +204,66 @@ impl App {
             selected_index: 0,
             should_quit: false,
             final_selection: SelectionResult::None,
-            mode: AppMode::Normal,
-            status_message: None,
+            mode: if config.vim_mode {
+                AppMode::Navigate
+            } else {
+                AppMode::Normal
+            },
+            status_message: config.warnings.first().map(|w| {
+                let first_line = w.lines().next().unwrap_or_default();
+                format!("Warning: {} (details on exit)", first_line)
+            }),
             base_path: path.clone(),
-            theme,
-            editor_cmd,
+            theme: config.theme.clone(),
+            editor_cmd: config.editor_cmd.clone(),
             wants_editor: false,
-            apply_date_prefix,
-            transparent_background,
+            apply_date_prefix: config.apply_date_prefix,
+            transparent_background: config.transparent_background.unwrap_or(true),
             available_themes: themes,
             theme_list_state: theme_state,
             original_theme: None,
             original_transparent_background: None,
-            config_path,
+            config_path: config.config_path.clone(),
             config_location_state: ListState::default(),
             cached_free_space_mb: utils::get_free_disk_space_mb(&path),
             folder_size_mb: Arc::new(AtomicU64::new(0)),
+            git_statuses: GitStatuses::default(),
+            folder_sizes: FolderSizes::default(),
+            sizes_sorted: 0,
+            preview_lines: Vec::new(),
+            preview_key: None,
+            preview_scroll: 0,
+            project_detectors,
+            keymap: config.keymap.clone(),
+            vim_mode: config.vim_mode,
+            vim_pending: VimPending::default(),
+            trash_items: Vec::new(),
+            trash_list_state: ListState::default(),
+            undo_stack: Vec::new(),
+            marked: HashSet::new(),
+            batch_action: BatchAction::Delete,
+            batch_targets: Vec::new(),
+            move_target: String::new(),
+            archive_dir: config.archive_dir.clone(),
+            archive_format: config.archive_format,
+            templates: templates::load_templates(&templates::templates_dir()),
+            template_list_state: ListState::default(),
+            sort_order: config.sort_order,
+            history: History::load(&history::history_path()),
+            frecency: HashMap::new(),
+            pins: Pins::load(&pins::pins_path()),
+            notes_target: PathBuf::new(),
+            notes_fields: Default::default(),
+            notes_focus: 0,
+            rename_target: PathBuf::new(),
+            rename_input: String::new(),
+            rename_redate: false,
+            fork_source: PathBuf::new(),
+            fork_input: String::new(),
+            fork_method: ForkMethod::Copy,
+            roots: config.roots.clone(),
+            root_filter,
+            root_list_state: ListState::default(),
         };
 
         // Spawn background thread to calculate folder size
@@ -179,12 +274,129 @@ impl App {
             folder_size_arc.store(size, Ordering::Relaxed);
         });
 
+        app.refresh_git_status();
+        app.refresh_sizes();
+        app.sort_entries();
         app.update_search();
         app
     }
 
+    /// Rebuilds the cached preview when the selection or the theme changed.
+    fn refresh_preview(&mut self, path: &Path) {
+        let key = (path.to_path_buf(), self.theme.name.clone());
+        if self.preview_key.as_ref() != Some(&key) {
+            self.preview_lines = preview::folder_preview(path, &self.theme);
+            self.preview_key = Some(key);
+            self.preview_scroll = 0;
+        }
+    }
+
+    pub fn scroll_preview(&mut self, lines: i32) {
+        self.preview_scroll = self.preview_scroll.saturating_add_signed(lines as i16);
+    }
+
+    /// Recomputes the git status of the repositories in the background.
+    fn refresh_git_status(&mut self) {
+        let repos = self
+            .all_entries
+            .iter()
+            .filter(|e| e.is_git || e.is_worktree)
+            .map(|e| e.path.clone())
+            .collect();
+        git_status::spawn_git_status(repos, &self.git_statuses);
+    }
+
+    /// Measures the entries in the background, reusing the cached size of
+    /// folders not modified since.
+    fn refresh_sizes(&mut self) {
+        let jobs = self
+            .all_entries
+            .iter()
+            .map(|e| SizeJob {
+                path: e.path.clone(),
+                artifacts: self
+                    .project_detectors
+                    .iter()
+                    .filter(|d| e.project_types.contains(&d.name))
+                    .flat_map(|d| d.artifacts.iter().cloned())
+                    .collect(),
+            })
+            .collect();
+        sizes::spawn_size_workers(jobs, &self.folder_sizes, sizes::size_cache_path());
+    }
+
+    /// Orders `all_entries` according to `sort_order`.
+    fn sort_entries(&mut self) {
+        let now = history::unix_now();
+        self.frecency = self
+            .all_entries
+            .iter()
+            .map(|e| {
+                let frecency = self.history.frecency(&e.path, now);
+                (e.path.clone(), frecency)
+            })
+            .collect();
+        match self.sort_order {
+            // load_entries already returns the most recently modified first
+            SortOrder::Mtime => {}
+            SortOrder::Created => self.all_entries.sort_by_key(|e| Reverse(e.created)),
+            SortOrder::Frecency => {
+                let frecency = &self.frecency;
+                self.all_entries.sort_by(|a, b| {
+                    frecency[&b.path]
+                        .total_cmp(&frecency[&a.path])
+                        .then(b.modified.cmp(&a.modified))
+                });
+            }
+            SortOrder::Size => {
+                let sizes = self
+                    .folder_sizes
+                    .lock()
+                    .map(|s| s.clone())
+                    .unwrap_or_default();
+                self.sizes_sorted = sizes.len();
+                // Entries not measured yet go last, most recently modified first
+                self.all_entries
+                    .sort_by_key(|e| Reverse(sizes.get(&e.path).map(|s| s.total)));
+            }
+        }
+    }
+
+    /// Sorts the entries again, keeping the selected entry selected.
+    fn resort(&mut self) {
+        let selected = self
+            .filtered_entries
+            .get(self.selected_index)
+            .map(|e| e.path.clone());
+        self.sort_entries();
+        self.update_search();
+        if let Some(path) = selected {
+            self.select_path(&path);
+        }
+    }
+
+    /// Switches to the next sort order (Ctrl-S).
+    pub fn cycle_sort_order(&mut self) {
+        self.sort_order = self.sort_order.next();
+        self.resort();
+        self.status_message = Some(format!("Sorted by {}", self.sort_order.label()));
+    }
+
+    /// Sorts by size again once the workers measured more entries.
+    pub fn sort_new_sizes(&mut self) {
+        if self.sort_order == SortOrder::Size
+            && self
+                .folder_sizes
+                .lock()
+                .is_ok_and(|s| s.len() != self.sizes_sorted)
+        {
+            self.resort();
+        }
+    }
+
     pub fn update_search(&mut self) {
         let matcher = SkimMatcherV2::default();
+        let query = SearchQuery::parse(&self.query);
 
         if self.query.is_empty() {
             self.filtered_entries = self.all_entries.clone();
@@ -193,9 +405,12 @@ impl App {
                 .all_entries
                 .iter()
                 .filter_map(|entry| {
-                    matcher.fuzzy_match(&entry.name, &self.query).map(|score| {
+                    query.score(entry, &matcher).map(|score| {
                         let mut e = entry.clone();
                         e.score = score;
+                        if self.sort_order == SortOrder::Frecency {
+                            e.score += history::frecency_bonus(self.frecency[&entry.path]);
+                        }
                         e
                     })
                 })
@@ -203,57 +418,702 @@ impl App {
 
             self.filtered_entries.sort_by(|a, b| b.score.cmp(&a.score));
         }
+        // Pinned entries form their own block at the top, in the same order
+        self.filtered_entries
+            .sort_by_key(|e| !self.pins.is_pinned(&e.path));
         self.selected_index = 0;
     }
 
-    pub fn delete_selected(&mut self) {
-        if let Some(entry_name) = self
+    /// Number of pinned entries at the top of `filtered_entries`.
+    pub fn pinned_count(&self) -> usize {
+        self.filtered_entries
+            .iter()
+            .take_while(|e| self.pins.is_pinned(&e.path))
+            .count()
+    }
+
+    /// Selects the entry at `path`, or the first one if it is not listed.
+    fn select_path(&mut self, path: &Path) {
+        self.selected_index = self
+            .filtered_entries
+            .iter()
+            .position(|e| e.path == path)
+            .unwrap_or(0);
+    }
+
+    /// Pins or unpins the selected entry and keeps it selected.
+    pub fn toggle_pin(&mut self) {
+        let Some((name, path)) = self
             .filtered_entries
             .get(self.selected_index)
-            .map(|e| e.name.clone())
-        {
-            let path_to_remove = self.base_path.join(&entry_name);
-
-            // Only use git worktree remove if it's actually a worktree (not main working tree)
-            if utils::is_git_worktree(&path_to_remove) {
-                match utils::remove_git_worktree(&path_to_remove) {
-                    Ok(output) => {
-                        if output.status.success() {
-                            self.all_entries.retain(|e| e.name != entry_name);
-                            self.update_search();
-                            self.status_message =
-                                Some(format!("Worktree removed: {path_to_remove:?}"));
-                        } else {
-                            self.status_message = Some(format!(
-                                "Error deleting: {}",
-                                String::from_utf8_lossy(&output.stderr)
-                                    .lines()
-                                    .take(1)
-                                    .collect::<String>()
-                            ));
-                        }
-                    }
-                    Err(e) => {
-                        self.status_message = Some(format!("Error removing worktree: {}", e));
-                    }
+            .map(|e| (e.name.clone(), e.path.clone()))
+        else {
+            return;
+        };
+        let pinned = self.pins.toggle(&path);
+        self.status_message = Some(match self.pins.save(&pins::pins_path()) {
+            Ok(()) if pinned => format!("Pinned: {}", name),
+            Ok(()) => format!("Unpinned: {}", name),
+            Err(e) => format!("Error saving pins: {}", e),
+        });
+        self.update_search();
+        self.select_path(&path);
+    }
+
+    /// Opens the notes editor for the selected entry.
+    pub fn open_notes(&mut self) {
+        let Some(entry) = self.filtered_entries.get(self.selected_index) else {
+            return;
+        };
+        let metadata = &entry.metadata;
+        self.notes_target = entry.path.clone();
+        self.notes_fields = [
+            metadata.description.clone().unwrap_or_default(),
+            metadata.tags.join(", "),
+            metadata.source.clone().unwrap_or_default(),
+        ];
+        self.notes_focus = 0;
+        self.mode = AppMode::EditNotes;
+    }
+
+    /// Writes the edited notes to the `.try-rs.toml` of the target folder.
+    pub fn save_notes(&mut self) {
+        let field = |s: &String| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        let metadata = TryMetadata {
+            description: field(&self.notes_fields[0]),
+            tags: metadata::parse_tags(&self.notes_fields[1]),
+            source: field(&self.notes_fields[2]),
+        };
+        let path = std::mem::take(&mut self.notes_target);
+        self.status_message = Some(match metadata.save(&path) {
+            Ok(()) => format!("Saved notes: {}", folder_name(&path)),
+            Err(e) => format!("Error saving notes: {}", e),
+        });
+        self.mode = self.home_mode();
+        self.reload_entries();
+        self.select_path(&path);
+    }
+
+    /// Opens the rename prompt for the selected entry.
+    pub fn open_rename(&mut self) {
+        let Some(entry) = self.filtered_entries.get(self.selected_index) else {
+            return;
+        };
+        self.rename_target = entry.path.clone();
+        self.rename_input = entry.display_name.clone();
+        self.rename_redate = false;
+        self.mode = AppMode::Rename;
+    }
+
+    /// Folder name the rename prompt would produce.
+    pub fn rename_result(&self) -> String {
+        rename::renamed_folder_name(
+            &folder_name(&self.rename_target),
+            &self.rename_input,
+            self.rename_redate,
+            &utils::generate_prefix_date(),
+        )
+    }
+
+    pub fn confirm_rename(&mut self) {
+        let new = self.rename_result();
+        let old = folder_name(&self.rename_target);
+        let root = self
+            .rename_target
+            .parent()
+            .unwrap_or(&self.base_path)
+            .to_path_buf();
+        match rename::rename_experiment(&root, &old, &new) {
+            Ok(to) => {
+                rename::remember_rename(&self.rename_target, &to);
+                self.status_message = Some(format!("Renamed: {} -> {}", old, new));
+                if self.marked.remove(&self.rename_target) {
+                    self.marked.insert(to.clone());
+                }
+                self.pins = Pins::load(&pins::pins_path());
+                self.history = History::load(&history::history_path());
+                self.reload_entries();
+                self.select_path(&to);
+            }
+            Err(e) => self.status_message = Some(e),
+        }
+        self.mode = self.home_mode();
+    }
+
+    /// Opens the fork prompt for the selected entry. Git repositories default
+    /// to a worktree.
+    pub fn open_fork(&mut self) {
+        let Some(entry) = self.filtered_entries.get(self.selected_index) else {
+            return;
+        };
+        self.fork_source = entry.path.clone();
+        self.fork_input = format!("{}-fork", entry.display_name);
+        self.fork_method = if self.fork_is_git() {
+            ForkMethod::Worktree
+        } else {
+            ForkMethod::Copy
+        };
+        self.mode = AppMode::Fork;
+    }
+
+    pub fn fork_is_git(&self) -> bool {
+        self.fork_source.join(".git").exists()
+    }
+
+    /// Cycles worktree -> clone -> copy, only copy is offered outside git and
+    /// never for a worktree, whose copy would share its git data.
+    pub fn cycle_fork_method(&mut self) {
+        self.fork_method = match self.fork_method {
+            _ if !self.fork_is_git() => ForkMethod::Copy,
+            ForkMethod::Worktree if utils::is_git_worktree(&self.fork_source) => ForkMethod::Clone,
+            ForkMethod::Clone if utils::is_git_worktree(&self.fork_source) => ForkMethod::Worktree,
+            ForkMethod::Worktree => ForkMethod::Clone,
+            ForkMethod::Clone => ForkMethod::Copy,
+            ForkMethod::Copy => ForkMethod::Worktree,
+        };
+    }
+
+    pub fn fork_result(&self) -> String {
+        fork::fork_name(
+            &folder_name(&self.fork_source),
+            Some(&self.fork_input),
+            &utils::generate_prefix_date(),
+        )
+    }
+
+    pub fn confirm_fork(&mut self) {
+        let new = self.fork_result();
+        let source = folder_name(&self.fork_source);
+        // The fork goes next to the original, in the same root
+        let root = self
+            .fork_source
+            .parent()
+            .unwrap_or(&self.base_path)
+            .to_path_buf();
+        match fork::fork_experiment(
+            &root,
+            &source,
+            &new,
+            self.fork_method,
+            &self.project_detectors,
+        ) {
+            Ok(to) => {
+                self.status_message = Some(format!(
+                    "Forked ({}): {} -> {}",
+                    self.fork_method.label(),
+                    source,
+                    new
+                ));
+                self.reload_entries();
+                self.select_path(&to);
+            }
+            Err(e) => self.status_message = Some(format!("Error forking: {:#}", e)),
+        }
+        self.mode = self.home_mode();
+    }
+
+    /// Rescans the tries directories, e.g. after restoring from the trash.
+    pub fn reload_entries(&mut self) {
+        self.all_entries = load_roots_entries(
+            &visible_roots(&self.roots, self.root_filter),
+            &self.project_detectors,
+        );
+        let all_entries = &self.all_entries;
+        self.marked
+            .retain(|path| all_entries.iter().any(|e| &e.path == path));
+        self.refresh_git_status();
+        self.refresh_sizes();
+        self.preview_key = None;
+        self.sort_entries();
+        self.update_search();
+    }
+
+    /// Marks or unmarks the selected entry and moves the cursor by `step`.
+    pub fn toggle_mark(&mut self, step: isize) {
+        let Some(entry) = self.filtered_entries.get(self.selected_index) else {
+            return;
+        };
+        if !self.marked.remove(&entry.path) {
+            self.marked.insert(entry.path.clone());
+        }
+        self.selected_index = self
+            .selected_index
+            .saturating_add_signed(step)
+            .min(self.filtered_entries.len().saturating_sub(1));
+    }
+
+    /// Paths of the entries a batch action applies to: the marked ones in list
+    /// order, or the selected entry when nothing is marked.
+    pub fn action_targets(&self) -> Vec<PathBuf> {
+        if self.marked.is_empty() {
+            self.filtered_entries
+                .get(self.selected_index)
+                .map(|e| vec![e.path.clone()])
+                .unwrap_or_default()
+        } else {
+            self.all_entries
+                .iter()
+                .filter(|e| self.marked.contains(&e.path))
+                .map(|e| e.path.clone())
+                .collect()
+        }
+    }
+
+    /// Collects the targets of `action` and asks for confirmation.
+    pub fn start_batch(&mut self, action: BatchAction) {
+        let paths = self.action_targets();
+        self.start_batch_on(action, &paths);
+    }
+
+    /// Asks for confirmation of `action` on `paths`, leaving the marks alone.
+    fn start_batch_on(&mut self, action: BatchAction, paths: &[PathBuf]) {
+        self.batch_targets = self
+            .all_entries
+            .iter()
+            .filter(|e| paths.contains(&e.path))
+            .map(|e| {
+                let path = e.path.clone();
+                // Cleaning keeps the sources, the git state does not matter
+                let status = (action != BatchAction::Clean && (e.is_git || e.is_worktree))
+                    .then(|| git_status::git_status(&path))
+                    .flatten()
+                    .unwrap_or_default();
+                let artifacts = if action == BatchAction::Clean {
+                    clean::experiment_artifacts(&path, &self.project_detectors)
+                } else {
+                    Vec::new()
                 };
-            } else {
-                // Regular directory or main git repo - just delete it
-                match fs::remove_dir_all(&path_to_remove) {
-                    Ok(_) => {
-                        self.all_entries.retain(|e| e.name != entry_name);
-                        self.update_search();
-                        self.status_message =
-                            Some(format!("Deleted: {}", path_to_remove.display()));
-                    }
-                    Err(e) => {
-                        self.status_message = Some(format!("Error deleting: {}", e));
-                    }
+                BatchTarget {
+                    name: e.name.clone(),
+                    is_worktree: e.is_worktree,
+                    has_changes: status.is_dirty(),
+                    unpushed: status.unpushed_commits(),
+                    is_pinned: self.pins.is_pinned(&path),
+                    artifacts,
+                    path,
                 }
+            })
+            .collect();
+        if action == BatchAction::Clean {
+            self.batch_targets.retain(|t| !t.artifacts.is_empty());
+            if self.batch_targets.is_empty() {
+                self.status_message = Some("No build artifacts to clean".to_string());
+            }
+        }
+        if self.batch_targets.is_empty() {
+            self.mode = self.home_mode();
+            return;
+        }
+        self.batch_action = action;
+        self.mode = AppMode::BatchConfirm;
+    }
+
+    /// Applies the confirmed batch action to every target. Pinned entries are
+    /// skipped by delete and archive unless `force` is set.
+    pub fn run_batch(&mut self, force: bool) {
+        let dest = self.move_destination();
+        if self.batch_action == BatchAction::Move
+            && let Err(e) = fs::create_dir_all(&dest)
+        {
+            self.status_message = Some(format!("Error moving: {}", e));
+            self.mode = self.home_mode();
+            return;
+        }
+
+        let mut done = 0;
+        let mut skipped = 0;
+        let mut freed = 0;
+        let mut cleaned = Vec::new();
+        let mut errors = Vec::new();
+        for target in std::mem::take(&mut self.batch_targets) {
+            if target.is_pinned && self.batch_action.protects_pinned() && !force {
+                skipped += 1;
+                continue;
+            }
+            let root = target
+                .path
+                .parent()
+                .unwrap_or(&self.base_path)
+                .to_path_buf();
+            let result = match self.batch_action {
+                BatchAction::Delete => trash::move_to_trash(&root, &target.name)
+                    .map(|item| self.undo_stack.push((root, item)))
+                    .map_err(|e| format!("Error deleting: {}", e)),
+                BatchAction::Archive => archive::archive_folder(
+                    &root,
+                    &self.archive_dir,
+                    &target.name,
+                    self.archive_format,
+                )
+                .map(|_| {
+                    self.pins.unpin(&target.path);
+                })
+                .map_err(|e| format!("Error archiving: {:#}", e)),
+                BatchAction::Move => utils::move_try_folder(&target.path, &dest.join(&target.name))
+                    .map(|()| rename::remember_rename(&target.path, &dest.join(&target.name))),
+                BatchAction::Clean => clean::remove_artifacts(&target.artifacts)
+                    .map(|bytes| {
+                        freed += bytes;
+                        cleaned.push(target.path.clone());
+                    })
+                    .map_err(|e| format!("Error cleaning: {}", e)),
             };
+            match result {
+                Ok(()) => {
+                    self.marked.remove(&target.path);
+                    done += 1;
+                }
+                Err(msg) => errors.push(format!("{} ({})", msg, target.name)),
+            }
+        }
+        if self.batch_action == BatchAction::Move {
+            self.pins = Pins::load(&pins::pins_path());
+            self.history = History::load(&history::history_path());
+        } else if self.batch_action == BatchAction::Archive {
+            let _ = self.pins.save(&pins::pins_path());
         }
-        self.mode = AppMode::Normal;
+        if !cleaned.is_empty() {
+            clean::forget_sizes(&cleaned);
+        }
+        self.reload_entries();
+
+        self.status_message = Some(if let Some(first) = errors.first() {
+            format!(
+                "{} of {} failed: {}",
+                errors.len(),
+                done + errors.len(),
+                first
+            )
+        } else {
+            match self.batch_action {
+                BatchAction::Delete if done == 1 => {
+                    format!(
+                        "Moved to trash: {} (Ctrl-Z to undo)",
+                        self.undo_stack
+                            .last()
+                            .map(|(_, i)| i.original_name.as_str())
+                            .unwrap_or_default()
+                    )
+                }
+                BatchAction::Delete => format!("Moved {} folders to trash (Ctrl-Z to undo)", done),
+                BatchAction::Archive => format!(
+                    "Archived {} folder{} to {}",
+                    done,
+                    if done == 1 { "" } else { "s" },
+                    self.archive_dir.to_string_lossy()
+                ),
+                BatchAction::Move => {
+                    format!("Moved {} folders to {}", done, dest.to_string_lossy())
+                }
+                BatchAction::Clean => format!(
+                    "Freed {} of build artifacts in {} folder{}",
+                    sizes::format_long(freed),
+                    done,
+                    if done == 1 { "" } else { "s" }
+                ),
+            }
+        });
+        if skipped > 0 {
+            self.status_message = Some(if done == 0 && errors.is_empty() {
+                "Pinned folders are kept, confirm with F to force".to_string()
+            } else {
+                format!(
+                    "{} - kept {} pinned",
+                    self.status_message.take().unwrap_or_default(),
+                    skipped
+                )
+            });
+        }
+        self.mode = self.home_mode();
+    }
+
+    /// Mode the popups return to: the list navigation with `vim_mode`,
+    /// otherwise the search box.
+    pub fn home_mode(&self) -> AppMode {
+        if self.vim_mode {
+            AppMode::Navigate
+        } else {
+            AppMode::Normal
+        }
+    }
+
+    /// Handles counts, `gg`, `dd` and the navigate-only actions. Returns
+    /// false for the keys the list handles the same way in both modes.
+    pub fn navigate_key(&mut self, action: Option<Action>, typed: Option<char>) -> bool {
+        if let Some(operator) = self.vim_pending.operator {
+            let count = self.vim_pending.count;
+            self.vim_pending = VimPending::default();
+            match (operator, typed) {
+                ('g', Some('g')) => self.go_to(count.unwrap_or(1)),
+                ('d', Some('d')) => self.delete_lines(count.unwrap_or(1)),
+                // Any other key cancels the command
+                _ => {}
+            }
+            return true;
+        }
+        if action.is_none()
+            && let Some(c) = typed
+        {
+            if self.vim_pending.push_digit(c) {
+                return true;
+            }
+            if matches!(c, 'g' | 'd') {
+                self.vim_pending.operator = Some(c);
+                return true;
+            }
+        }
+
+        let had_count = self.vim_pending.count;
+        let count = self.vim_pending.take_count();
+        match action {
+            Some(Action::Up) => self.selected_index = self.selected_index.saturating_sub(count),
+            Some(Action::Down) => {
+                self.selected_index = (self.selected_index + count)
+                    .min(self.filtered_entries.len().saturating_sub(1));
+            }
+            Some(Action::First) => self.selected_index = 0,
+            Some(Action::Last) => match had_count {
+                Some(line) => self.go_to(line),
+                None => self.selected_index = self.filtered_entries.len().saturating_sub(1),
+            },
+            Some(Action::Search) => self.mode = AppMode::Normal,
+            // Esc drops a pending count first
+            Some(Action::Cancel) if had_count.is_some() => {}
+            _ => return false,
+        }
+        true
+    }
+
+    /// Selects the `line`-th entry (1-based), or the last one.
+    fn go_to(&mut self, line: usize) {
+        self.selected_index = line
+            .saturating_sub(1)
+            .min(self.filtered_entries.len().saturating_sub(1));
+    }
+
+    /// `dd`: deletes the marked entries, or `count` entries from the selection.
+    fn delete_lines(&mut self, count: usize) {
+        if count > 1 {
+            let paths: Vec<PathBuf> = self
+                .filtered_entries
+                .iter()
+                .skip(self.selected_index)
+                .take(count)
+                .map(|e| e.path.clone())
+                .collect();
+            self.start_batch_on(BatchAction::Delete, &paths);
+        } else {
+            self.start_batch(BatchAction::Delete);
+        }
+    }
+
+    /// Opens the root selector, on the root the list is restricted to.
+    pub fn open_root_select(&mut self) {
+        if self.roots.len() < 2 {
+            self.status_message =
+                Some("Only one root, add [roots.<name>] to config.toml".to_string());
+            return;
+        }
+        // Index 0 lists every root
+        self.root_list_state
+            .select(Some(self.root_filter.map_or(0, |i| i + 1)));
+        self.mode = AppMode::RootSelect;
+    }
+
+    /// Restricts the list to the root picked in the selector, which also
+    /// becomes the root new experiments are created in.
+    pub fn confirm_root_select(&mut self) {
+        self.root_filter = self
+            .root_list_state
+            .selected()
+            .and_then(|i| i.checked_sub(1))
+            .filter(|&i| i < self.roots.len());
+        if let Some(i) = self.root_filter {
+            let path = self.roots[i].path.clone();
+            // A configured archive stays, the default one follows the root
+            if self.archive_dir == self.base_path.join(archive::ARCHIVE_DIR_NAME) {
+                self.archive_dir = path.join(archive::ARCHIVE_DIR_NAME);
+            }
+            self.base_path = path;
+        }
+        self.mode = self.home_mode();
+        self.reload_entries();
+    }
+
+    /// Directory typed in the move prompt: a root name or a path.
+    pub fn move_destination(&self) -> PathBuf {
+        let target = self.move_target.trim();
+        self.roots
+            .iter()
+            .find(|r| r.name == target)
+            .map(|r| r.path.clone())
+            .unwrap_or_else(|| utils::expand_path(target))
+    }
+
+    /// Restores the most recently trashed entry of this session.
+    pub fn undo_delete(&mut self) {
+        let Some((root, item)) = self.undo_stack.pop() else {
+            self.status_message = Some("Nothing to undo".to_string());
+            return;
+        };
+        match trash::restore(&root, &item) {
+            Ok(()) => {
+                self.reload_entries();
+                self.status_message = Some(format!("Restored: {}", item.original_name));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Error restoring: {}", e));
+            }
+        }
+    }
+
+    /// Lists the trash of every listed root, deleted entries stay in the
+    /// trash of the root they were deleted from.
+    pub fn open_trash(&mut self) {
+        self.trash_items = visible_roots(&self.roots, self.root_filter)
+            .into_iter()
+            .flat_map(|root| {
+                trash::list_trash(&root.path)
+                    .into_iter()
+                    .map(move |item| (root.path.clone(), item))
+            })
+            .collect();
+        self.trash_items
+            .sort_by(|(_, a), (_, b)| b.deleted_at.cmp(&a.deleted_at));
+        self.trash_list_state
+            .select(if self.trash_items.is_empty() {
+                None
+            } else {
+                Some(0)
+            });
+        self.mode = AppMode::Trash;
+    }
+
+    /// Restores (`forever == false`) or permanently deletes the selected trash item.
+    pub fn handle_trash_selected(&mut self, forever: bool) {
+        let Some((root, item)) = self
+            .trash_list_state
+            .selected()
+            .and_then(|i| self.trash_items.get(i))
+            .cloned()
+        else {
+            return;
+        };
+        let result = if forever {
+            trash::delete_permanently(&root, &item)
+        } else {
+            trash::restore(&root, &item)
+        };
+        match result {
+            Ok(()) => {
+                self.undo_stack.retain(|(_, i)| i.id != item.id);
+                self.status_message = Some(if forever {
+                    format!("Deleted forever: {}", item.original_name)
+                } else {
+                    format!("Restored: {}", item.original_name)
+                });
+                if !forever {
+                    self.reload_entries();
+                }
+            }
+            Err(e) => self.status_message = Some(format!("Error: {}", e)),
+        }
+        self.open_trash();
+    }
+}
+
+/// The roots listed: all of them, or the one the list is restricted to.
+fn visible_roots(roots: &[TriesRoot], filter: Option<usize>) -> Vec<TriesRoot> {
+    match filter.and_then(|i| roots.get(i)) {
+        Some(root) => vec![root.clone()],
+        None => roots.to_vec(),
+    }
+}
+
+fn folder_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Compact list badges: commits ahead/behind, changed and untracked files.
+fn git_badges(status: &GitStatus, theme: &Theme) -> Vec<Span<'static>> {
+    let mut badges = Vec::new();
+    let mut push = |text: String, color: Color| {
+        badges.push(Span::styled(
+            format!("{} ", text),
+            Style::default().fg(color),
+        ));
+    };
+    if status.ahead > 0 {
+        push(format!("↑{}", status.ahead), theme.icon_worktree);
+    }
+    if status.behind > 0 {
+        push(format!("↓{}", status.behind), theme.title_rs);
+    }
+    if status.changed > 0 {
+        push(format!("~{}", status.changed), theme.icon_folder);
+    }
+    if status.untracked > 0 {
+        push(format!("?{}", status.untracked), theme.helpers_colors);
+    }
+    badges
+}
+
+/// Branch, working tree state and last commit shown in the preview.
+fn git_preview_lines(status: &GitStatus, theme: &Theme) -> Vec<Line<'static>> {
+    let mut branch = vec![
+        Span::styled(" ", Style::default().fg(theme.icon_git)),
+        Span::styled(
+            status
+                .branch
+                .clone()
+                .unwrap_or_else(|| "(detached)".to_string()),
+            Style::default()
+                .fg(theme.popup_text)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if let Some(upstream) = &status.upstream {
+        branch.push(Span::styled(
+            format!(" → {} ↑{} ↓{}", upstream, status.ahead, status.behind),
+            Style::default().fg(theme.helpers_colors),
+        ));
+    } else if status.unpushed_commits() > 0 {
+        branch.push(Span::styled(
+            format!(" ↑{} unpushed", status.unpushed_commits()),
+            Style::default().fg(theme.title_rs),
+        ));
+    }
+    let state = if status.is_dirty() {
+        Span::styled(
+            format!(
+                "~{} changed  ?{} untracked",
+                status.changed, status.untracked
+            ),
+            Style::default().fg(theme.icon_folder),
+        )
+    } else {
+        Span::styled("clean", Style::default().fg(theme.icon_worktree))
+    };
+    let mut lines = vec![Line::from(branch), Line::from(state)];
+    if let Some(commit) = &status.last_commit {
+        let date = chrono::DateTime::from_timestamp(commit.time, 0)
+            .map(|dt| {
+                dt.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", date), Style::default().fg(theme.list_date)),
+            Span::styled(
+                commit.subject.clone(),
+                Style::default().fg(theme.helpers_colors),
+            ),
+        ]));
     }
+    lines
 }
 
 fn draw_popup(f: &mut Frame, title: &str, message: &str, theme: &Theme) {
@@ -299,86 +1159,409 @@ fn draw_popup(f: &mut Frame, title: &str, message: &str, theme: &Theme) {
         )
         .alignment(Alignment::Center);
 
-    f.render_widget(paragraph, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
+fn draw_theme_select(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .split(area);
+
+    let popup_area = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .split(popup_layout[1])[1];
+
+    f.render_widget(Clear, popup_area);
+
+    // Split popup into theme list and transparency option
+    let inner_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(popup_area);
+
+    let block = Block::default()
+        .title(" Select Theme ")
+        .borders(Borders::ALL)
+        .style(Style::default().bg(app.theme.popup_bg));
+
+    // The themes are sorted dark first, a header starts each group when both exist
+    let light_start = app.available_themes.iter().position(|t| t.is_light());
+    let grouped = light_start.is_some_and(|i| i > 0);
+    let header = |label: &str| {
+        ListItem::new(format!("── {} ──", label)).style(
+            Style::default()
+                .fg(app.theme.helpers_colors)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let mut items: Vec<ListItem> = Vec::new();
+    for (i, t) in app.available_themes.iter().enumerate() {
+        if grouped && i == 0 {
+            items.push(header("Dark"));
+        }
+        if grouped && Some(i) == light_start {
+            items.push(header("Light"));
+        }
+        items.push(
+            ListItem::new(t.name.clone()).style(Style::default().fg(app.theme.list_highlight_fg)),
+        );
+    }
+    let headers_before = |i: usize| match light_start {
+        Some(start) if grouped => 1 + usize::from(i >= start),
+        _ => 0,
+    };
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(app.theme.highlight_style())
+        .highlight_symbol(">> ");
+
+    let mut list_state = app.theme_list_state;
+    list_state.select(
+        app.theme_list_state
+            .selected()
+            .map(|i| i + headers_before(i)),
+    );
+    f.render_stateful_widget(list, inner_layout[0], &mut list_state);
+    *app.theme_list_state.offset_mut() = list_state.offset();
+
+    // Draw transparency checkbox
+    let checkbox = if app.transparent_background {
+        "[x]"
+    } else {
+        "[ ]"
+    };
+    let transparency_text = format!(" {} Transparent Background (Space to toggle)", checkbox);
+    let transparency_block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().bg(app.theme.popup_bg));
+    let transparency_paragraph = Paragraph::new(transparency_text)
+        .style(Style::default().fg(app.theme.list_highlight_fg))
+        .block(transparency_block);
+    f.render_widget(transparency_paragraph, inner_layout[1]);
+}
+
+fn draw_template_select(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .split(area);
+
+    let popup_area = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .split(popup_layout[1])[1];
+
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" New '{}' from template ", app.query))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(app.theme.popup_bg));
+
+    let mut items = vec![
+        ListItem::new("(empty folder)").style(Style::default().fg(app.theme.list_highlight_fg)),
+    ];
+    items.extend(app.templates.iter().map(|t| {
+        let mut spans = vec![Span::styled(
+            t.name.clone(),
+            Style::default().fg(app.theme.list_highlight_fg),
+        )];
+        if let Some(description) = &t.manifest.description {
+            spans.push(Span::styled(
+                format!("  {}", description),
+                Style::default().fg(app.theme.helpers_colors),
+            ));
+        }
+        ListItem::new(Line::from(spans))
+    }));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(app.theme.highlight_style())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, popup_area, &mut app.template_list_state);
+}
+
+fn draw_root_select(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    let height = (app.roots.len() as u16 + 3).min(area.height);
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .split(area);
+
+    let popup_area = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .split(popup_layout[1])[1];
+
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(" Roots ")
+        .borders(Borders::ALL)
+        .style(Style::default().bg(app.theme.popup_bg));
+
+    let mut items =
+        vec![ListItem::new("(all roots)").style(Style::default().fg(app.theme.list_highlight_fg))];
+    items.extend(app.roots.iter().map(|root| {
+        ListItem::new(Line::from(vec![
+            Span::styled(
+                root.name.clone(),
+                Style::default().fg(app.theme.list_highlight_fg),
+            ),
+            Span::styled(
+                format!("  {}", root.path.to_string_lossy()),
+                Style::default().fg(app.theme.helpers_colors),
+            ),
+        ]))
+    }));
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(app.theme.highlight_style())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, popup_area, &mut app.root_list_state);
+}
+
+fn draw_notes_editor(f: &mut Frame, app: &App) {
+    let area = f.area();
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Length(12),
+            Constraint::Percentage(30),
+        ])
+        .split(area);
+
+    let popup_area = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .split(popup_layout[1])[1];
+
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" Notes: {} ", folder_name(&app.notes_target)))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(app.theme.popup_bg));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let labels = [" Description ", " Tags ", " Source "];
+    for (i, label) in labels.into_iter().enumerate() {
+        let focused = i == app.notes_focus;
+        let text = if focused {
+            format!("{}_", app.notes_fields[i])
+        } else {
+            app.notes_fields[i].clone()
+        };
+        let border_color = if focused {
+            app.theme.search_border
+        } else {
+            app.theme.helpers_colors
+        };
+        let field = Paragraph::new(text)
+            .style(Style::default().fg(app.theme.popup_text))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(label)
+                    .border_style(Style::default().fg(border_color)),
+            );
+        f.render_widget(field, rows[i]);
+    }
+
+    let help = Paragraph::new("Tab next field | Enter save | Esc cancel")
+        .style(Style::default().fg(app.theme.helpers_colors))
+        .alignment(Alignment::Center);
+    f.render_widget(help, rows[3]);
+}
+
+fn draw_config_location_select(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(40),
+            Constraint::Length(8),
+            Constraint::Percentage(40),
+        ])
+        .split(area);
+
+    let popup_area = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .split(popup_layout[1])[1];
+
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(" Select Config Location ")
+        .borders(Borders::ALL)
+        .style(Style::default().bg(app.theme.popup_bg));
+
+    let config_name = get_file_config_toml_name();
+    let items = vec![
+        ListItem::new(format!("System Config (~/.config/try-rs/{})", config_name))
+            .style(Style::default().fg(app.theme.list_highlight_fg)),
+        ListItem::new(format!("Home Directory (~/{})", config_name))
+            .style(Style::default().fg(app.theme.list_highlight_fg)),
+    ];
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(app.theme.highlight_style())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, popup_area, &mut app.config_location_state);
 }
 
-fn draw_theme_select(f: &mut Frame, app: &mut App) {
+fn draw_trash_view(f: &mut Frame, app: &mut App) {
     let area = f.area();
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage(25),
-            Constraint::Percentage(50),
-            Constraint::Percentage(25),
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
         ])
         .split(area);
 
     let popup_area = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Percentage(25),
-            Constraint::Percentage(50),
-            Constraint::Percentage(25),
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
         ])
         .split(popup_layout[1])[1];
 
     f.render_widget(Clear, popup_area);
 
-    // Split popup into theme list and transparency option
     let inner_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(3), Constraint::Length(3)])
         .split(popup_area);
 
     let block = Block::default()
-        .title(" Select Theme ")
+        .title(" Trash ")
         .borders(Borders::ALL)
         .style(Style::default().bg(app.theme.popup_bg));
 
-    let items: Vec<ListItem> = app
-        .available_themes
-        .iter()
-        .map(|t| {
-            ListItem::new(t.name.clone()).style(Style::default().fg(app.theme.list_highlight_fg))
-        })
-        .collect();
+    let items: Vec<ListItem> = if app.trash_items.is_empty() {
+        vec![ListItem::new(" (empty) ").style(Style::default().fg(Color::DarkGray))]
+    } else {
+        let several_roots = visible_roots(&app.roots, app.root_filter).len() > 1;
+        app.trash_items
+            .iter()
+            .map(|(root, item)| {
+                let deleted_at = item
+                    .deleted_at()
+                    .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                let mut spans = vec![
+                    Span::styled(deleted_at, Style::default().fg(app.theme.list_date)),
+                    Span::styled(
+                        format!(" {}", item.original_name),
+                        Style::default().fg(app.theme.list_highlight_fg),
+                    ),
+                ];
+                if item.worktree.is_some() {
+                    spans.push(Span::styled(
+                        " 󰙅",
+                        Style::default().fg(app.theme.icon_worktree),
+                    ));
+                }
+                if several_roots && let Some(root) = app.roots.iter().find(|r| &r.path == root) {
+                    spans.push(Span::styled(
+                        format!(" ({})", root.name),
+                        Style::default().fg(app.theme.helpers_colors),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    };
 
     let list = List::new(items)
         .block(block)
-        .highlight_style(
-            Style::default()
-                .bg(app.theme.list_highlight_bg)
-                .fg(app.theme.list_highlight_fg)
-                .add_modifier(Modifier::BOLD),
-        )
+        .highlight_style(app.theme.highlight_style())
         .highlight_symbol(">> ");
 
-    f.render_stateful_widget(list, inner_layout[0], &mut app.theme_list_state);
+    f.render_stateful_widget(list, inner_layout[0], &mut app.trash_list_state);
 
-    // Draw transparency checkbox
-    let checkbox = if app.transparent_background {
-        "[x]"
-    } else {
-        "[ ]"
-    };
-    let transparency_text = format!(" {} Transparent Background (Space to toggle)", checkbox);
-    let transparency_block = Block::default()
-        .borders(Borders::ALL)
-        .style(Style::default().bg(app.theme.popup_bg));
-    let transparency_paragraph = Paragraph::new(transparency_text)
-        .style(Style::default().fg(app.theme.list_highlight_fg))
-        .block(transparency_block);
-    f.render_widget(transparency_paragraph, inner_layout[1]);
+    let help = Paragraph::new(" Enter Restore | x Delete forever | Esc Close")
+        .style(Style::default().fg(app.theme.helpers_colors))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default().bg(app.theme.popup_bg)),
+        );
+    f.render_widget(help, inner_layout[1]);
 }
 
-fn draw_config_location_select(f: &mut Frame, app: &mut App) {
+fn draw_batch_confirm(f: &mut Frame, app: &App) {
     let area = f.area();
+    let count = app.batch_targets.len();
+    // Borders, blank line and the question/help lines around the folder list
+    let height = (count as u16 + 5).min(area.height.saturating_sub(2));
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage(40),
-            Constraint::Length(8),
-            Constraint::Percentage(40),
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
         ])
         .split(area);
 
@@ -393,30 +1576,116 @@ fn draw_config_location_select(f: &mut Frame, app: &mut App) {
 
     f.render_widget(Clear, popup_area);
 
-    let block = Block::default()
-        .title(" Select Config Location ")
-        .borders(Borders::ALL)
-        .style(Style::default().bg(app.theme.popup_bg));
-
-    let config_name = get_file_config_toml_name();
-    let items = vec![
-        ListItem::new(format!("System Config (~/.config/try-rs/{})", config_name))
-            .style(Style::default().fg(app.theme.list_highlight_fg)),
-        ListItem::new(format!("Home Directory (~/{})", config_name))
-            .style(Style::default().fg(app.theme.list_highlight_fg)),
-    ];
-
-    let list = List::new(items)
-        .block(block)
-        .highlight_style(
-            Style::default()
-                .bg(app.theme.list_highlight_bg)
-                .fg(app.theme.list_highlight_fg)
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol(">> ");
+    let question = match app.batch_action {
+        BatchAction::Delete if count == 1 => "Move this folder to trash?".to_string(),
+        BatchAction::Delete => format!("Move {} folders to trash?", count),
+        BatchAction::Archive => format!(
+            "Archive {} folder{} as {} and remove the original{}?",
+            count,
+            if count == 1 { "" } else { "s" },
+            app.archive_format.extension(),
+            if count == 1 { "" } else { "s" }
+        ),
+        BatchAction::Move => format!(
+            "Move {} folder{} to {}?",
+            count,
+            if count == 1 { "" } else { "s" },
+            app.move_target.trim()
+        ),
+        BatchAction::Clean => format!(
+            "Remove {} of build artifacts from {} folder{}?",
+            sizes::format_long(
+                app.batch_targets
+                    .iter()
+                    .flat_map(|t| &t.artifacts)
+                    .map(|a| a.bytes)
+                    .sum()
+            ),
+            count,
+            if count == 1 { "" } else { "s" }
+        ),
+    };
 
-    f.render_stateful_widget(list, popup_area, &mut app.config_location_state);
+    let mut lines = vec![Line::from(Span::styled(
+        question,
+        Style::default()
+            .fg(app.theme.popup_text)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    let visible = (height as usize).saturating_sub(5);
+    for target in app.batch_targets.iter().take(visible) {
+        let mut spans = vec![
+            Span::styled(" 󰝰 ", Style::default().fg(app.theme.icon_folder)),
+            Span::styled(
+                target.name.clone(),
+                Style::default().fg(app.theme.popup_text),
+            ),
+        ];
+        if !target.artifacts.is_empty() {
+            spans.push(Span::styled(
+                format!(
+                    "  {}",
+                    clean::artifact_names(&target.path, &target.artifacts)
+                ),
+                Style::default().fg(app.theme.helpers_colors),
+            ));
+        }
+        if target.is_worktree {
+            spans.push(Span::styled(
+                "  󰙅 worktree",
+                Style::default().fg(app.theme.icon_worktree),
+            ));
+        }
+        if target.has_changes {
+            spans.push(Span::styled(
+                "   uncommitted changes",
+                Style::default().fg(app.theme.title_rs),
+            ));
+        }
+        if target.unpushed > 0 {
+            spans.push(Span::styled(
+                format!(
+                    "  ↑ {} unpushed commit{}",
+                    target.unpushed,
+                    if target.unpushed == 1 { "" } else { "s" }
+                ),
+                Style::default().fg(app.theme.title_rs),
+            ));
+        }
+        if target.is_pinned {
+            spans.push(Span::styled(
+                "  󰐃 pinned",
+                Style::default().fg(app.theme.title_try),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    if count > visible {
+        lines.push(Line::from(Span::styled(
+            format!(" ... and {} more", count - visible),
+            Style::default().fg(app.theme.helpers_colors),
+        )));
+    }
+    let protected =
+        app.batch_action.protects_pinned() && app.batch_targets.iter().any(|t| t.is_pinned);
+    lines.push(Line::from(Span::styled(
+        if protected {
+            "(y: skip pinned / F: include pinned / n)"
+        } else {
+            "(y/n)"
+        },
+        Style::default()
+            .fg(app.theme.popup_text)
+            .add_modifier(Modifier::BOLD),
+    )));
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(" WARNING ")
+            .borders(Borders::ALL)
+            .style(Style::default().bg(app.theme.popup_bg)),
+    );
+    f.render_widget(paragraph, popup_area);
 }
 
 fn draw_about_popup(f: &mut Frame, theme: &Theme) {
@@ -500,11 +1769,9 @@ fn draw_about_popup(f: &mut Frame, theme: &Theme) {
     f.render_widget(paragraph, popup_area);
 }
 
-pub fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<io::Stderr>>,
-    mut app: App,
-) -> Result<(SelectionResult, bool)> {
+pub fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stderr>>, app: &mut App) -> Result<()> {
     while !app.should_quit {
+        app.sort_new_sizes();
         terminal.draw(|f| {
             // Render background if not transparent
             if !app.transparent_background {
@@ -514,6 +1781,10 @@ pub fn run_app(
                 }
             }
 
+            if let Some(path) = app.filtered_entries.get(app.selected_index).map(|e| e.path.clone()) {
+                app.refresh_preview(&path);
+            }
+
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
@@ -533,13 +1804,25 @@ pub fn run_app(
                 .constraints([Constraint::Min(20), Constraint::Length(45)])
                 .split(chunks[0]);
 
+            let search_title = if app.vim_mode {
+                let vim_mode = if app.mode == AppMode::Normal {
+                    "INSERT".to_string()
+                } else if app.vim_pending.is_empty() {
+                    "NORMAL".to_string()
+                } else {
+                    format!("NORMAL {}", app.vim_pending)
+                };
+                format!(" Search/New [{}] ", vim_mode)
+            } else {
+                " Search/New ".to_string()
+            };
             let search_text = Paragraph::new(app.query.clone())
                 .style(Style::default().fg(app.theme.search_title))
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
                         .title(Span::styled(
-                            " Search/New ",
+                            search_title,
                             Style::default().fg(app.theme.search_title),
                         ))
                         .border_style(Style::default().fg(app.theme.search_border)),
@@ -589,6 +1872,8 @@ pub fn run_app(
             .alignment(Alignment::Center);
             f.render_widget(memory_info, search_chunks[1]);
 
+            let git_statuses = app.git_statuses.lock().map(|s| s.clone()).unwrap_or_default();
+            let folder_sizes = app.folder_sizes.lock().map(|s| s.clone()).unwrap_or_default();
             let items: Vec<ListItem> = app
                 .filtered_entries
                 .iter()
@@ -608,27 +1893,55 @@ pub fn run_app(
                     let date_text = date_str.to_string();
                     let date_width = date_text.chars().count();
 
-                    // Build icon list: (flag, icon_str, color)
-                    let icons: &[(bool, &str, Color)] = &[
-                        (entry.is_cargo,                " ", app.theme.icon_rust),
-                        (entry.is_maven,                " ", app.theme.icon_maven),
-                        (entry.is_flutter,              " ", app.theme.icon_flutter),
-                        (entry.is_go,                   " ", app.theme.icon_go),
-                        (entry.is_python,               " ", app.theme.icon_python),
-                        (entry.is_mise,                 "󰬔 ", app.theme.icon_mise),
+                    // Build icon list: project types first, then git state
+                    let mut icons: Vec<(&str, Color)> = app
+                        .project_detectors
+                        .iter()
+                        .filter(|d| entry.project_types.contains(&d.name))
+                        .map(|d| (d.icon.as_str(), app.theme.role_color(d.color)))
+                        .collect();
+                    if app.pins.is_pinned(&entry.path) {
+                        icons.insert(0, ("󰐃 ", app.theme.title_try));
+                    }
+                    let git_icons: [(bool, &str, Color); 4] = [
                         (entry.is_worktree,             "󰙅 ", app.theme.icon_worktree),
                         (entry.is_worktree_locked,      " ", app.theme.icon_worktree_lock),
                         (entry.is_gitmodules,           " ", app.theme.icon_gitmodules),
                         (entry.is_git,                  " ", app.theme.icon_git),
                     ];
-                    let icons_width: usize = icons.iter().filter(|(f, _, _)| *f).count() * 2;
+                    icons.extend(
+                        git_icons
+                            .into_iter()
+                            .filter(|(flag, _, _)| *flag)
+                            .map(|(_, icon, color)| (icon, color)),
+                    );
+                    let badges = git_statuses
+                        .get(&entry.path)
+                        .map(|status| git_badges(status, &app.theme))
+                        .unwrap_or_default();
+                    let badges_width: usize = badges.iter().map(|b| b.width()).sum();
+                    let icons_width: usize = icons.len() * 2 + badges_width;
                     let icon_width = 2; // folder icon
 
+                    let size_text = folder_sizes
+                        .get(&entry.path)
+                        .map(|s| sizes::format_short(s.total))
+                        .unwrap_or_else(|| "-".to_string());
+                    let size_text = format!("{:>5} ", size_text);
+                    let size_width = size_text.chars().count();
+
                     let created_dt: chrono::DateTime<Local> = entry.created.into();
                     let created_text = created_dt.format("%Y-%m-%d").to_string();
-                    let created_width = created_text.chars().count();
-
-                    let reserved = date_width + icons_width + icon_width + created_width + 2;
+                    let root_text = entry
+                        .root
+                        .as_ref()
+                        .map(|root| format!(" {}", root))
+                        .unwrap_or_default();
+                    // The root label is part of the date column
+                    let created_width = created_text.chars().count() + root_text.chars().count();
+
+                    let reserved =
+                        date_width + size_width + icons_width + icon_width + created_width + 2;
                     let available_for_name = width.saturating_sub(reserved);
                     let name_len = entry.display_name.chars().count();
 
@@ -640,95 +1953,236 @@ pub fn run_app(
                         (
                             entry.display_name.clone(),
                             width.saturating_sub(
-                                icon_width + created_width + 1 + name_len + date_width + icons_width,
+                                icon_width
+                                    + created_width
+                                    + 1
+                                    + name_len
+                                    + date_width
+                                    + size_width
+                                    + icons_width,
                             ),
                         )
                     };
 
+                    let folder_icon = if app.marked.contains(&entry.path) {
+                        Span::styled(" 󰄲 ", Style::default().fg(app.theme.status_message))
+                    } else {
+                        Span::styled(" 󰝰 ", Style::default().fg(app.theme.icon_folder))
+                    };
                     let mut spans = vec![
-                        Span::styled(" 󰝰 ", Style::default().fg(app.theme.icon_folder)),
+                        folder_icon,
                         Span::styled(created_text, Style::default().fg(app.theme.list_date)),
+                        Span::styled(root_text, Style::default().fg(app.theme.title_try)),
                         Span::raw(format!(" {}", display_name)),
                         Span::raw(" ".repeat(padding)),
                     ];
-                    for &(flag, icon, color) in icons {
-                        if flag {
-                            spans.push(Span::styled(icon, Style::default().fg(color)));
-                        }
+                    for (icon, color) in icons {
+                        spans.push(Span::styled(icon, Style::default().fg(color)));
                     }
+                    spans.extend(badges);
+                    spans.push(Span::styled(size_text, Style::default().fg(app.theme.list_date)));
                     spans.push(Span::styled(date_text, Style::default().fg(app.theme.list_date)));
 
                     ListItem::new(Line::from(spans))
                 })
                 .collect();
 
+            let mut title_parts = vec![format!("by {}", app.sort_order.label())];
+            if app.roots.len() > 1 {
+                title_parts.insert(
+                    0,
+                    app.root_filter
+                        .and_then(|i| app.roots.get(i))
+                        .map_or_else(|| "all roots".to_string(), |r| r.name.clone()),
+                );
+            }
+            if !app.marked.is_empty() {
+                title_parts.push(format!("{} marked", app.marked.len()));
+            }
+            let reclaimable: u64 = app
+                .all_entries
+                .iter()
+                .filter_map(|e| folder_sizes.get(&e.path))
+                .map(|s| s.artifacts)
+                .sum();
+            if reclaimable > 0 {
+                title_parts.push(format!("{} reclaimable", sizes::format_long(reclaimable)));
+            }
+            let folders_title = format!(" Folders ({}) ", title_parts.join(", "));
+            // Pinned entries get their own block above the other folders
+            let pinned_count = app.pinned_count();
+            let mut pinned_items = items;
+            let items = pinned_items.split_off(pinned_count);
+            let list_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(if pinned_count > 0 {
+                    [
+                        Constraint::Max(pinned_count as u16 + 2),
+                        Constraint::Min(3),
+                    ]
+                } else {
+                    [Constraint::Length(0), Constraint::Min(0)]
+                })
+                .split(content_chunks[0]);
+            let highlight_style = app.theme.highlight_style();
+
+            if pinned_count > 0 {
+                let pinned_list = List::new(pinned_items)
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title(Span::styled(
+                                " 󰐃 Pinned ",
+                                Style::default().fg(app.theme.folder_title),
+                            ))
+                            .border_style(Style::default().fg(app.theme.folder_border)),
+                    )
+                    .highlight_style(highlight_style)
+                    .highlight_symbol("→ ");
+                let mut pinned_state = ListState::default();
+                pinned_state
+                    .select((app.selected_index < pinned_count).then_some(app.selected_index));
+                f.render_stateful_widget(pinned_list, list_chunks[0], &mut pinned_state);
+            }
+
             let list = List::new(items)
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
                         .title(Span::styled(
-                            " Folders ",
+                            folders_title,
                             Style::default().fg(app.theme.folder_title),
                         ))
                         .border_style(Style::default().fg(app.theme.folder_border)),
                 )
-                .highlight_style(
-                    Style::default()
-                        .bg(app.theme.list_highlight_bg)
-                        .fg(app.theme.list_highlight_fg)
-                        .add_modifier(Modifier::BOLD),
-                )
+                .highlight_style(highlight_style)
                 .highlight_symbol("→ ");
 
             let mut state = ListState::default();
-            state.select(Some(app.selected_index));
-            f.render_stateful_widget(list, content_chunks[0], &mut state);
+            state.select(app.selected_index.checked_sub(pinned_count));
+            f.render_stateful_widget(list, list_chunks[1], &mut state);
+
+            // Icon legend: only the project types present in the tries dir
+            let mut legend_spans = Vec::new();
+            for detector in &app.project_detectors {
+                if app
+                    .all_entries
+                    .iter()
+                    .any(|e| e.project_types.contains(&detector.name))
+                {
+                    legend_spans.push(Span::styled(
+                        detector.icon.as_str(),
+                        Style::default().fg(app.theme.role_color(detector.color)),
+                    ));
+                    legend_spans.push(Span::styled(
+                        format!("{} ", detector.name),
+                        Style::default().fg(app.theme.helpers_colors),
+                    ));
+                }
+            }
+            if !app.pins.pinned.is_empty() {
+                legend_spans.push(Span::styled("󰐃 ", Style::default().fg(app.theme.title_try)));
+                legend_spans.push(Span::styled(
+                    "Pinned ",
+                    Style::default().fg(app.theme.helpers_colors),
+                ));
+            }
+            legend_spans.extend([
+                Span::styled(" ", Style::default().fg(app.theme.icon_worktree_lock)),
+                Span::styled("Locked ", Style::default().fg(app.theme.helpers_colors)),
+                Span::styled("󰙅 ", Style::default().fg(app.theme.icon_worktree)),
+                Span::styled(
+                    "Git-Worktree ",
+                    Style::default().fg(app.theme.helpers_colors),
+                ),
+                Span::styled(" ", Style::default().fg(app.theme.icon_gitmodules)),
+                Span::styled("Git-Submod ", Style::default().fg(app.theme.helpers_colors)),
+                Span::styled(" ", Style::default().fg(app.theme.icon_git)),
+                Span::styled("Git ", Style::default().fg(app.theme.helpers_colors)),
+            ]);
+            let legend_lines = vec![Line::from(legend_spans)];
+
+            // Grow the legend box with the number of wrapped lines (+1 slack for word wrapping)
+            let legend_inner_width = content_chunks[1].width.saturating_sub(2).max(1) as usize;
+            let legend_height = (legend_lines[0].width().div_ceil(legend_inner_width) + 1)
+                .clamp(2, 6) as u16
+                + 2;
 
             // Split right area between Preview and Icon Legend
             let right_chunks = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Min(1), Constraint::Length(4)])
+                .constraints([Constraint::Min(1), Constraint::Length(legend_height)])
                 .split(content_chunks[1]);
 
             if let Some(selected) = app.filtered_entries.get(app.selected_index) {
-                let preview_path = app.base_path.join(&selected.name);
                 let mut preview_lines = Vec::new();
 
-                if let Ok(entries) = fs::read_dir(&preview_path) {
-                    for e in entries
-                        .take(right_chunks[0].height.saturating_sub(2) as usize)
-                        .flatten()
-                    {
-                        let file_name = e.file_name().to_string_lossy().to_string();
-                        let is_dir = e.file_type().map(|t| t.is_dir()).unwrap_or(false);
-                        let (icon, color) = if is_dir {
-                            ("󰝰 ", app.theme.icon_folder)
-                        } else {
-                            ("󰈙 ", app.theme.icon_file)
-                        };
-                        preview_lines.push(Line::from(vec![
-                            Span::styled(icon, Style::default().fg(color)),
-                            Span::raw(file_name),
-                        ]));
+                // Notes first, then the folder content
+                let metadata = &selected.metadata;
+                if let Some(description) = &metadata.description {
+                    preview_lines.push(Line::from(Span::styled(
+                        description.clone(),
+                        Style::default().fg(app.theme.popup_text).add_modifier(Modifier::BOLD),
+                    )));
+                }
+                if !metadata.tags.is_empty() {
+                    let tags: Vec<String> = metadata.tags.iter().map(|t| format!("#{}", t)).collect();
+                    preview_lines.push(Line::from(Span::styled(
+                        tags.join(" "),
+                        Style::default().fg(app.theme.title_try),
+                    )));
+                }
+                if let Some(source) = &metadata.source {
+                    preview_lines.push(Line::from(Span::styled(
+                        format!("󰌷 {}", source),
+                        Style::default().fg(app.theme.helpers_colors),
+                    )));
+                }
+                if !metadata.is_empty() {
+                    preview_lines.push(Line::from(""));
+                }
+                if let Some(size) = folder_sizes.get(&selected.path) {
+                    let mut spans = vec![Span::styled(
+                        format!("󰋊 {}", sizes::format_long(size.total)),
+                        Style::default().fg(app.theme.helpers_colors),
+                    )];
+                    if size.artifacts > 0 {
+                        spans.push(Span::styled(
+                            format!(" ({} of build artifacts)", sizes::format_long(size.artifacts)),
+                            Style::default().fg(app.theme.status_message),
+                        ));
                     }
+                    preview_lines.push(Line::from(spans));
+                    preview_lines.push(Line::from(""));
+                }
+                if let Some(status) = git_statuses.get(&selected.path) {
+                    preview_lines.extend(git_preview_lines(status, &app.theme));
+                    preview_lines.push(Line::from(""));
                 }
+                let notes_height = preview_lines.len();
+                preview_lines.extend(app.preview_lines.iter().cloned());
 
-                if preview_lines.is_empty() {
+                if preview_lines.len() == notes_height {
                     preview_lines.push(Line::from(Span::styled(
                         " (empty) ",
                         Style::default().fg(Color::DarkGray),
                     )));
                 }
-
-                let preview = Paragraph::new(preview_lines).block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .title(Span::styled(
-                            " Preview ",
-                            Style::default().fg(app.theme.preview_title),
-                        ))
-                        .border_style(Style::default().fg(app.theme.preview_border)),
-                );
+                app.preview_scroll = app
+                    .preview_scroll
+                    .min(preview_lines.len().saturating_sub(1) as u16);
+
+                let preview = Paragraph::new(preview_lines)
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title(Span::styled(
+                                " Preview ",
+                                Style::default().fg(app.theme.preview_title),
+                            ))
+                            .border_style(Style::default().fg(app.theme.preview_border)),
+                    )
+                    .scroll((app.preview_scroll, 0));
                 f.render_widget(preview, right_chunks[0]);
             } else {
                 let preview = Block::default()
@@ -741,33 +2195,6 @@ pub fn run_app(
                 f.render_widget(preview, right_chunks[0]);
             }
 
-            // Icon legend
-            let legend_lines = vec![Line::from(vec![
-                Span::styled(" ", Style::default().fg(app.theme.icon_rust)),
-                Span::styled("Rust ", Style::default().fg(app.theme.helpers_colors)),
-                Span::styled(" ", Style::default().fg(app.theme.icon_maven)),
-                Span::styled("Maven ", Style::default().fg(app.theme.helpers_colors)),
-                Span::styled(" ", Style::default().fg(app.theme.icon_flutter)),
-                Span::styled("Flutter ", Style::default().fg(app.theme.helpers_colors)),
-                Span::styled(" ", Style::default().fg(app.theme.icon_go)),
-                Span::styled("Go ", Style::default().fg(app.theme.helpers_colors)),
-                Span::styled(" ", Style::default().fg(app.theme.icon_python)),
-                Span::styled("Python ", Style::default().fg(app.theme.helpers_colors)),
-                Span::styled("󰬔 ", Style::default().fg(app.theme.icon_mise)),
-                Span::styled("Mise ", Style::default().fg(app.theme.helpers_colors)),
-                Span::styled(" ", Style::default().fg(app.theme.icon_worktree_lock)),
-                Span::styled("Locked ", Style::default().fg(app.theme.helpers_colors)),
-                Span::styled("󰙅 ", Style::default().fg(app.theme.icon_worktree)),
-                Span::styled(
-                    "Git-Worktree ",
-                    Style::default().fg(app.theme.helpers_colors),
-                ),
-                Span::styled(" ", Style::default().fg(app.theme.icon_gitmodules)),
-                Span::styled("Git-Submod ", Style::default().fg(app.theme.helpers_colors)),
-                Span::styled(" ", Style::default().fg(app.theme.icon_git)),
-                Span::styled("Git ", Style::default().fg(app.theme.helpers_colors)),
-            ])];
-
             let legend = Paragraph::new(legend_lines)
                 .block(
                     Block::default()
@@ -790,22 +2217,21 @@ pub fn run_app(
                         .add_modifier(Modifier::BOLD),
                 )])
             } else {
-                Line::from(vec![
-                    Span::styled("↑↓", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Nav | "),
-                    Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Select | "),
-                    Span::styled("Ctrl-D", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Del | "),
-                    Span::styled("Ctrl-E", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Edit | "),
-                    Span::styled("Ctrl-T", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Theme | "),
-                    Span::styled("Ctrl-A", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" About | "),
-                    Span::styled("Esc/Ctrl+C", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Quit"),
-                ])
+                let help = app.keymap.help(if app.mode == AppMode::Navigate {
+                    AppMode::Navigate
+                } else {
+                    AppMode::Normal
+                });
+                let mut spans = Vec::new();
+                for (i, (keys, label)) in help.iter().enumerate() {
+                    spans.push(Span::styled(
+                        keys.clone(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ));
+                    let separator = if i + 1 < help.len() { " | " } else { "" };
+                    spans.push(Span::raw(format!(" {}{}", label, separator)));
+                }
+                Line::from(spans)
             };
 
             let help_message = Paragraph::new(help_text)
@@ -814,15 +2240,23 @@ pub fn run_app(
 
             f.render_widget(help_message, chunks[2]);
 
-            if app.mode == AppMode::DeleteConfirm
-                && let Some(selected) = app.filtered_entries.get(app.selected_index)
-            {
-                let msg = format!("Delete '{}'?\n(y/n)", selected.name);
-                draw_popup(f, " WARNING ", &msg, &app.theme);
+            if app.mode == AppMode::BatchConfirm {
+                draw_batch_confirm(f, app);
+            }
+
+            if app.mode == AppMode::MoveTarget {
+                let count = app.action_targets().len();
+                let msg = format!(
+                    "Move {} folder{} to (root name or path):\n{}_\n(Enter confirm, Esc cancel)",
+                    count,
+                    if count == 1 { "" } else { "s" },
+                    app.move_target
+                );
+                draw_popup(f, " Move ", &msg, &app.theme);
             }
 
             if app.mode == AppMode::ThemeSelect {
-                draw_theme_select(f, &mut app);
+                draw_theme_select(f, app);
             }
 
             if app.mode == AppMode::ConfigSavePrompt {
@@ -835,12 +2269,53 @@ pub fn run_app(
             }
 
             if app.mode == AppMode::ConfigSaveLocationSelect {
-                draw_config_location_select(f, &mut app);
+                draw_config_location_select(f, app);
             }
 
             if app.mode == AppMode::About {
                 draw_about_popup(f, &app.theme);
             }
+
+            if app.mode == AppMode::Trash {
+                draw_trash_view(f, app);
+            }
+
+            if app.mode == AppMode::TemplateSelect {
+                draw_template_select(f, app);
+            }
+
+            if app.mode == AppMode::RootSelect {
+                draw_root_select(f, app);
+            }
+
+            if app.mode == AppMode::Rename {
+                let msg = format!(
+                    "Rename '{}' to:\n{}_\n[{}] Use today's date (Tab)\n(Enter confirm, Esc cancel)",
+                    folder_name(&app.rename_target),
+                    app.rename_result(),
+                    if app.rename_redate { "x" } else { " " }
+                );
+                draw_popup(f, " Rename ", &msg, &app.theme);
+            }
+
+            if app.mode == AppMode::Fork {
+                let method = if app.fork_is_git() {
+                    format!("< {} > (Tab)", app.fork_method.label())
+                } else {
+                    "copy without build artifacts".to_string()
+                };
+                let msg = format!(
+                    "Fork '{}' as:\n{}_\nMethod: {}\n(Enter confirm, Esc cancel)",
+                    folder_name(&app.fork_source),
+                    app.fork_result(),
+                    method
+                );
+                draw_popup(f, " Fork ", &msg, &app.theme);
+            }
+
+            if app.mode == AppMode::EditNotes {
+                draw_notes_editor(f, app);
+            }
         })?;
 
         // Poll with 1-second timeout so the screen refreshes periodically
@@ -853,136 +2328,231 @@ pub fn run_app(
             }
             // Clear status message on any key press so it disappears after one redraw
             app.status_message = None;
+            let action = app.keymap.action(app.mode, &key);
+            // Plain characters not bound to an action go to the text fields
+            let typed = match key.code {
+                KeyCode::Char(c)
+                    if !key
+                        .modifiers
+                        .intersects(event::KeyModifiers::CONTROL | event::KeyModifiers::ALT) =>
+                {
+                    Some(c)
+                }
+                _ => None,
+            };
+            if app.mode == AppMode::Navigate && app.navigate_key(action, typed) {
+                continue;
+            }
             match app.mode {
-                AppMode::Normal => match key.code {
-                    KeyCode::Char(c) => {
-                        if c == 'c' && key.modifiers.contains(event::KeyModifiers::CONTROL) {
-                            app.should_quit = true;
-                        } else if c == 'd' && key.modifiers.contains(event::KeyModifiers::CONTROL) {
-                            if !app.filtered_entries.is_empty() {
-                                app.mode = AppMode::DeleteConfirm;
-                            }
-                        } else if c == 'e' && key.modifiers.contains(event::KeyModifiers::CONTROL) {
-                            if app.editor_cmd.is_some() {
-                                if !app.filtered_entries.is_empty() {
-                                    app.final_selection = SelectionResult::Folder(
-                                        app.filtered_entries[app.selected_index].name.clone(),
-                                    );
-                                    app.wants_editor = true;
-                                    app.should_quit = true;
-                                } else if !app.query.is_empty() {
-                                    app.final_selection =
-                                        SelectionResult::Folder(app.query.clone());
-                                    app.wants_editor = true;
-                                    app.should_quit = true;
-                                }
-                            } else {
-                                app.status_message =
-                                    Some("No editor configured in config.toml".to_string());
-                            }
-                        } else if c == 't' && key.modifiers.contains(event::KeyModifiers::CONTROL) {
-                            // Save current theme and transparency before opening selector
-                            app.original_theme = Some(app.theme.clone());
-                            app.original_transparent_background = Some(app.transparent_background);
-                            // Find and select current theme in the list
-                            let current_idx = app
-                                .available_themes
-                                .iter()
-                                .position(|t| t.name == app.theme.name)
-                                .unwrap_or(0);
-                            app.theme_list_state.select(Some(current_idx));
-                            app.mode = AppMode::ThemeSelect;
-                        } else if c == 'a' && key.modifiers.contains(event::KeyModifiers::CONTROL) {
-                            app.mode = AppMode::About;
-                        } else if matches!(c, 'k' | 'p')
-                            && key.modifiers.contains(event::KeyModifiers::CONTROL)
-                        {
-                            if app.selected_index > 0 {
-                                app.selected_index -= 1;
-                            }
-                        } else if matches!(c, 'j' | 'n')
-                            && key.modifiers.contains(event::KeyModifiers::CONTROL)
-                        {
-                            if app.selected_index < app.filtered_entries.len().saturating_sub(1) {
-                                app.selected_index += 1;
+                AppMode::Normal | AppMode::Navigate => match action {
+                    Some(Action::Quit) => app.should_quit = true,
+                    Some(Action::Delete) => app.start_batch(BatchAction::Delete),
+                    Some(Action::Archive) => app.start_batch(BatchAction::Archive),
+                    Some(Action::Clean) => app.start_batch(BatchAction::Clean),
+                    Some(Action::Move) => {
+                        if !app.action_targets().is_empty() {
+                            app.mode = AppMode::MoveTarget;
+                        }
+                    }
+                    Some(Action::OpenEditor) => {
+                        if app.editor_cmd.is_some() {
+                            if !app.marked.is_empty() {
+                                app.final_selection =
+                                    SelectionResult::Folders(app.action_targets());
+                                app.wants_editor = true;
+                                app.should_quit = true;
+                            } else if !app.filtered_entries.is_empty() {
+                                app.final_selection = SelectionResult::Folder(
+                                    app.filtered_entries[app.selected_index].path.clone(),
+                                );
+                                app.wants_editor = true;
+                                app.should_quit = true;
+                            } else if !app.query.is_empty() {
+                                app.final_selection =
+                                    SelectionResult::Folder(app.base_path.join(&app.query));
+                                app.wants_editor = true;
+                                app.should_quit = true;
                             }
-                        } else if c == 'u' && key.modifiers.contains(event::KeyModifiers::CONTROL) {
-                            app.query.clear();
-                            app.update_search();
-                        } else if key.modifiers.is_empty()
-                            || key.modifiers == event::KeyModifiers::SHIFT
-                        {
-                            app.query.push(c);
-                            app.update_search();
+                        } else {
+                            app.status_message =
+                                Some("No editor configured in config.toml".to_string());
                         }
                     }
-                    KeyCode::Backspace => {
-                        app.query.pop();
+                    Some(Action::Theme) => {
+                        // Save current theme and transparency before opening selector
+                        app.original_theme = Some(app.theme.clone());
+                        app.original_transparent_background = Some(app.transparent_background);
+                        // Find and select current theme in the list
+                        let current_idx = app
+                            .available_themes
+                            .iter()
+                            .position(|t| t.name == app.theme.name)
+                            .unwrap_or(0);
+                        app.theme_list_state.select(Some(current_idx));
+                        app.mode = AppMode::ThemeSelect;
+                    }
+                    Some(Action::About) => app.mode = AppMode::About,
+                    Some(Action::Undo) => app.undo_delete(),
+                    Some(Action::Trash) => app.open_trash(),
+                    Some(Action::Pin) => app.toggle_pin(),
+                    Some(Action::Notes) => app.open_notes(),
+                    Some(Action::Rename) => app.open_rename(),
+                    Some(Action::Fork) => app.open_fork(),
+                    Some(Action::Sort) => app.cycle_sort_order(),
+                    Some(Action::Roots) => app.open_root_select(),
+                    Some(Action::ClearInput) => {
+                        app.query.clear();
                         app.update_search();
                     }
-                    KeyCode::Up => {
+                    Some(Action::Mark) => app.toggle_mark(1),
+                    Some(Action::Unmark) => app.toggle_mark(-1),
+                    Some(Action::PreviewUp) => app.scroll_preview(-1),
+                    Some(Action::PreviewDown) => app.scroll_preview(1),
+                    Some(Action::PreviewPageUp) => app.scroll_preview(-10),
+                    Some(Action::PreviewPageDown) => app.scroll_preview(10),
+                    Some(Action::Up) => {
                         if app.selected_index > 0 {
                             app.selected_index -= 1;
                         }
                     }
-                    KeyCode::Down => {
+                    Some(Action::Down) => {
                         if app.selected_index < app.filtered_entries.len().saturating_sub(1) {
                             app.selected_index += 1;
                         }
                     }
-                    KeyCode::Enter => {
+                    Some(Action::Confirm) => {
                         if !app.filtered_entries.is_empty() {
                             app.final_selection = SelectionResult::Folder(
-                                app.filtered_entries[app.selected_index].name.clone(),
+                                app.filtered_entries[app.selected_index].path.clone(),
                             );
+                        } else if !SearchQuery::parse(&app.query).tags.is_empty() {
+                            // A tag filter is not a folder name
+                            app.status_message = Some("No experiment with these tags".to_string());
+                            continue;
                         } else if !app.query.is_empty() {
+                            if !app.templates.is_empty() && !utils::is_git_url(&app.query) {
+                                app.template_list_state.select(Some(0));
+                                app.mode = AppMode::TemplateSelect;
+                                continue;
+                            }
                             app.final_selection = SelectionResult::New(app.query.clone());
                         }
                         app.should_quit = true;
                     }
-                    KeyCode::Esc => app.should_quit = true,
+                    Some(Action::Cancel) => {
+                        if app.mode == AppMode::Normal && app.vim_mode {
+                            // Esc leaves the search box, keeping the filter
+                            app.mode = AppMode::Navigate;
+                        } else if app.marked.is_empty() {
+                            app.should_quit = true;
+                        } else {
+                            app.marked.clear();
+                        }
+                    }
+                    Some(_) => {}
+                    None if app.mode == AppMode::Navigate => {}
+                    None => {
+                        if let Some(c) = typed {
+                            app.query.push(c);
+                            app.update_search();
+                        } else if key.code == KeyCode::Backspace {
+                            app.query.pop();
+                            app.update_search();
+                        }
+                    }
+                },
+
+                AppMode::BatchConfirm => match action {
+                    Some(Action::Confirm) => app.run_batch(false),
+                    Some(Action::Force) => app.run_batch(true),
+                    Some(Action::Cancel) => app.mode = app.home_mode(),
+                    Some(Action::Quit) => app.should_quit = true,
                     _ => {}
                 },
 
-                AppMode::DeleteConfirm => match key.code {
-                    KeyCode::Char('y') | KeyCode::Char('Y') => {
-                        app.delete_selected();
-                    }
-                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-                        app.mode = AppMode::Normal;
+                AppMode::Rename => match action {
+                    Some(Action::Cancel) => app.mode = app.home_mode(),
+                    Some(Action::ClearInput) => app.rename_input.clear(),
+                    Some(Action::Toggle) => app.rename_redate = !app.rename_redate,
+                    Some(Action::Confirm) if !app.rename_input.trim().is_empty() => {
+                        app.confirm_rename();
                     }
-                    KeyCode::Char('c') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
-                        app.should_quit = true;
+                    Some(_) => {}
+                    None => {
+                        if let Some(c) = typed {
+                            app.rename_input.push(c);
+                        } else if key.code == KeyCode::Backspace {
+                            app.rename_input.pop();
+                        }
                     }
-                    _ => {}
                 },
 
-                AppMode::ThemeSelect => match key.code {
-                    KeyCode::Char(' ') => {
-                        // Toggle transparent background
-                        app.transparent_background = !app.transparent_background;
+                AppMode::Fork => match action {
+                    Some(Action::Cancel) => app.mode = app.home_mode(),
+                    Some(Action::ClearInput) => app.fork_input.clear(),
+                    Some(Action::Toggle) => app.cycle_fork_method(),
+                    Some(Action::Confirm) if !app.fork_input.trim().is_empty() => {
+                        app.confirm_fork();
                     }
-                    KeyCode::Esc => {
-                        // Restore original theme and transparency
-                        if let Some(original) = app.original_theme.take() {
-                            app.theme = original;
+                    Some(_) => {}
+                    None => {
+                        if let Some(c) = typed {
+                            app.fork_input.push(c);
+                        } else if key.code == KeyCode::Backspace {
+                            app.fork_input.pop();
                         }
-                        if let Some(original_transparent) = app.original_transparent_background.take() {
-                            app.transparent_background = original_transparent;
+                    }
+                },
+
+                AppMode::EditNotes => match action {
+                    Some(Action::Cancel) => app.mode = app.home_mode(),
+                    Some(Action::Down) => app.notes_focus = (app.notes_focus + 1) % 3,
+                    Some(Action::Up) => app.notes_focus = (app.notes_focus + 2) % 3,
+                    Some(Action::Confirm) => app.save_notes(),
+                    Some(_) => {}
+                    None => {
+                        if let Some(c) = typed {
+                            app.notes_fields[app.notes_focus].push(c);
+                        } else if key.code == KeyCode::Backspace {
+                            app.notes_fields[app.notes_focus].pop();
+                        }
+                    }
+                },
+
+                AppMode::MoveTarget => match action {
+                    Some(Action::Cancel) => app.mode = app.home_mode(),
+                    Some(Action::ClearInput) => app.move_target.clear(),
+                    Some(Action::Confirm) if !app.move_target.trim().is_empty() => {
+                        app.start_batch(BatchAction::Move);
+                    }
+                    Some(_) => {}
+                    None => {
+                        if let Some(c) = typed {
+                            app.move_target.push(c);
+                        } else if key.code == KeyCode::Backspace {
+                            app.move_target.pop();
                         }
-                        app.mode = AppMode::Normal;
                     }
-                    KeyCode::Char('c') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+                },
+
+                AppMode::ThemeSelect => match action {
+                    Some(Action::Toggle) => {
+                        // Toggle transparent background
+                        app.transparent_background = !app.transparent_background;
+                    }
+                    Some(Action::Cancel) => {
                         // Restore original theme and transparency
                         if let Some(original) = app.original_theme.take() {
                             app.theme = original;
                         }
-                        if let Some(original_transparent) = app.original_transparent_background.take() {
+                        if let Some(original_transparent) =
+                            app.original_transparent_background.take()
+                        {
                             app.transparent_background = original_transparent;
                         }
-                        app.mode = AppMode::Normal;
+                        app.mode = app.home_mode();
                     }
-                    KeyCode::Up | KeyCode::Char('k' | 'p') => {
+                    Some(Action::Up) => {
                         let i = match app.theme_list_state.selected() {
                             Some(i) => {
                                 if i > 0 {
@@ -999,7 +2569,7 @@ pub fn run_app(
                             app.theme = theme.clone();
                         }
                     }
-                    KeyCode::Down | KeyCode::Char('j' | 'n') => {
+                    Some(Action::Down) => {
                         let i = match app.theme_list_state.selected() {
                             Some(i) => {
                                 if i < app.available_themes.len() - 1 {
@@ -1016,7 +2586,7 @@ pub fn run_app(
                             app.theme = theme.clone();
                         }
                     }
-                    KeyCode::Enter => {
+                    Some(Action::Confirm) => {
                         // Clear original theme and transparency (we're confirming the new values)
                         app.original_theme = None;
                         app.original_transparent_background = None;
@@ -1037,37 +2607,35 @@ pub fn run_app(
                                     } else {
                                         app.status_message = Some("Theme saved.".to_string());
                                     }
-                                    app.mode = AppMode::Normal;
+                                    app.mode = app.home_mode();
                                 } else {
                                     app.mode = AppMode::ConfigSavePrompt;
                                 }
                             } else {
-                                app.mode = AppMode::Normal;
+                                app.mode = app.home_mode();
                             }
                         } else {
-                            app.mode = AppMode::Normal;
+                            app.mode = app.home_mode();
                         }
                     }
                     _ => {}
                 },
-                AppMode::ConfigSavePrompt => match key.code {
-                    KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                AppMode::ConfigSavePrompt => match action {
+                    Some(Action::Confirm) => {
                         app.mode = AppMode::ConfigSaveLocationSelect;
                         app.config_location_state.select(Some(0));
                     }
-                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-                        app.mode = AppMode::Normal;
+                    Some(Action::Cancel) => {
+                        app.mode = app.home_mode();
                     }
                     _ => {}
                 },
 
-                AppMode::ConfigSaveLocationSelect => match key.code {
-                    KeyCode::Esc | KeyCode::Char('c')
-                        if key.modifiers.contains(event::KeyModifiers::CONTROL) =>
-                    {
-                        app.mode = AppMode::Normal;
+                AppMode::ConfigSaveLocationSelect => match action {
+                    Some(Action::Cancel) => {
+                        app.mode = app.home_mode();
                     }
-                    KeyCode::Up | KeyCode::Char('k' | 'p') => {
+                    Some(Action::Up) => {
                         let i = match app.config_location_state.selected() {
                             Some(i) => {
                                 if i > 0 {
@@ -1080,7 +2648,7 @@ pub fn run_app(
                         };
                         app.config_location_state.select(Some(i));
                     }
-                    KeyCode::Down | KeyCode::Char('j' | 'n') => {
+                    Some(Action::Down) => {
                         let i = match app.config_location_state.selected() {
                             Some(i) => {
                                 if i < 1 {
@@ -1093,7 +2661,7 @@ pub fn run_app(
                         };
                         app.config_location_state.select(Some(i));
                     }
-                    KeyCode::Enter => {
+                    Some(Action::Confirm) => {
                         if let Some(i) = app.config_location_state.selected() {
                             let config_name = get_file_config_toml_name();
                             let path = if i == 0 {
@@ -1123,22 +2691,88 @@ pub fn run_app(
                                 app.status_message = Some("Theme saved!".to_string());
                             }
                         }
-                        app.mode = AppMode::Normal;
+                        app.mode = app.home_mode();
+                    }
+                    _ => {}
+                },
+                AppMode::Trash => match action {
+                    Some(Action::Cancel) => {
+                        app.mode = app.home_mode();
+                    }
+                    Some(Action::Up) => {
+                        if let Some(i) = app.trash_list_state.selected()
+                            && i > 0
+                        {
+                            app.trash_list_state.select(Some(i - 1));
+                        }
+                    }
+                    Some(Action::Down) => {
+                        if let Some(i) = app.trash_list_state.selected()
+                            && i + 1 < app.trash_items.len()
+                        {
+                            app.trash_list_state.select(Some(i + 1));
+                        }
+                    }
+                    Some(Action::Confirm) => app.handle_trash_selected(false),
+                    Some(Action::Delete) => app.handle_trash_selected(true),
+                    _ => {}
+                },
+                AppMode::TemplateSelect => match action {
+                    Some(Action::Cancel) => app.mode = app.home_mode(),
+                    Some(Action::Up) => {
+                        if let Some(i) = app.template_list_state.selected()
+                            && i > 0
+                        {
+                            app.template_list_state.select(Some(i - 1));
+                        }
+                    }
+                    Some(Action::Down) => {
+                        if let Some(i) = app.template_list_state.selected()
+                            && i < app.templates.len()
+                        {
+                            app.template_list_state.select(Some(i + 1));
+                        }
+                    }
+                    Some(Action::Confirm) => {
+                        // Index 0 is the plain empty folder
+                        app.final_selection = match app.template_list_state.selected() {
+                            Some(i) if i > 0 => SelectionResult::NewFromTemplate(
+                                app.query.clone(),
+                                app.templates[i - 1].name.clone(),
+                            ),
+                            _ => SelectionResult::New(app.query.clone()),
+                        };
+                        app.should_quit = true;
                     }
                     _ => {}
                 },
-                AppMode::About => match key.code {
-                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char(' ') => {
-                        app.mode = AppMode::Normal;
+                AppMode::RootSelect => match action {
+                    Some(Action::Cancel) => app.mode = app.home_mode(),
+                    Some(Action::Up) => {
+                        if let Some(i) = app.root_list_state.selected()
+                            && i > 0
+                        {
+                            app.root_list_state.select(Some(i - 1));
+                        }
                     }
-                    KeyCode::Char('c') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
-                        app.mode = AppMode::Normal;
+                    Some(Action::Down) => {
+                        if let Some(i) = app.root_list_state.selected()
+                            && i < app.roots.len()
+                        {
+                            app.root_list_state.select(Some(i + 1));
+                        }
                     }
+                    Some(Action::Confirm) => app.confirm_root_select(),
                     _ => {}
                 },
+                AppMode::About => {
+                    if action == Some(Action::Cancel) {
+                        app.mode = app.home_mode();
+                    }
+                }
             }
         }
     }
 
-    Ok((app.final_selection, app.wants_editor))
+    Ok(())
 }
@@ -0,0 +1,313 @@
+use anyhow::{Context, Result, anyhow, bail};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::cli::{ThemeAction, ThemeImportArgs};
+use crate::config::get_config_dir;
+use crate::theme_import::{import_scheme, theme_file_name};
+use crate::theme_preview::{SAMPLE_SIZE, buffer_to_ansi, sample_buffer, theme_list};
+use crate::themes::{PALETTE_ROLES, Theme};
+
+/// Value of `background` for a theme that keeps the terminal background.
+pub const NO_BACKGROUND: &str = "none";
+
+/// A theme file of the themes directory, e.g.:
+///
+/// ```toml
+/// name = "Company Dark"
+/// inherits = "Nord"
+///
+/// [palette]
+/// accent1 = "#5e81ac"
+///
+/// [colors]
+/// list_highlight_bg = "#3b4252"
+/// ```
+#[derive(Deserialize, Serialize, Default, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeFile {
+    /// Shown in the theme selector, defaults to the file name
+    pub name: Option<String>,
+    /// Built-in theme the colors not set here are taken from (default: `Default`)
+    pub inherits: Option<String>,
+    /// Background color, or `"none"` for the terminal background
+    pub background: Option<String>,
+    /// Colors of the palette roles, each applied to several fields
+    pub palette: Option<PaletteFile>,
+    /// Colors of individual fields (`title_try`, `list_highlight_bg`, ...)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub colors: BTreeMap<String, String>,
+}
+
+/// The palette roles of `Theme::from_palette`, all optional.
+#[derive(Deserialize, Serialize, Default, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PaletteFile {
+    pub accent1: Option<String>,
+    pub accent2: Option<String>,
+    pub warm: Option<String>,
+    pub cool: Option<String>,
+    pub green: Option<String>,
+    pub yellow: Option<String>,
+    pub purple: Option<String>,
+    pub overlay: Option<String>,
+    pub subtext: Option<String>,
+    pub surface: Option<String>,
+    pub text: Option<String>,
+    pub base: Option<String>,
+}
+
+impl PaletteFile {
+    /// The roles that are set, in the order of `PALETTE_ROLES`.
+    pub fn roles(&self) -> Vec<(&'static str, &str)> {
+        let values = [
+            &self.accent1,
+            &self.accent2,
+            &self.warm,
+            &self.cool,
+            &self.green,
+            &self.yellow,
+            &self.purple,
+            &self.overlay,
+            &self.subtext,
+            &self.surface,
+            &self.text,
+            &self.base,
+        ];
+        PALETTE_ROLES
+            .iter()
+            .zip(values)
+            .filter_map(|((role, _), value)| value.as_deref().map(|v| (*role, v)))
+            .collect()
+    }
+}
+
+pub fn themes_dir() -> PathBuf {
+    get_config_dir().join("themes")
+}
+
+fn parse_color(value: &str, key: &str) -> Result<Color> {
+    Color::from_str(value).map_err(|_| {
+        anyhow!(
+            "invalid color '{}' for {} (expected \"#rrggbb\", a color name or a 0-255 index)",
+            value,
+            key
+        )
+    })
+}
+
+impl ThemeFile {
+    /// Builds the theme: the inherited built-in, then the palette roles, then
+    /// the individual colors.
+    pub fn to_theme(&self, default_name: &str, builtins: &[Theme]) -> Result<Theme> {
+        let base_name = self.inherits.as_deref().unwrap_or("Default");
+        let mut theme = builtins
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(base_name))
+            .cloned()
+            .ok_or_else(|| {
+                let names: Vec<&str> = builtins.iter().map(|t| t.name.as_str()).collect();
+                anyhow!(
+                    "unknown theme '{}' in inherits (built-in themes: {})",
+                    base_name,
+                    names.join(", ")
+                )
+            })?;
+        theme.name = self
+            .name
+            .clone()
+            .unwrap_or_else(|| default_name.to_string());
+
+        if let Some(background) = &self.background {
+            theme.background = if background.eq_ignore_ascii_case(NO_BACKGROUND) {
+                None
+            } else {
+                Some(parse_color(background, "background")?)
+            };
+        }
+        if let Some(palette) = &self.palette {
+            for (role, value) in palette.roles() {
+                theme.set_role(role, parse_color(value, &format!("palette.{}", role))?);
+            }
+        }
+        for (field, value) in &self.colors {
+            let color = parse_color(value, &format!("colors.{}", field))?;
+            let Some(slot) = theme.color_mut(field) else {
+                bail!(
+                    "unknown field 'colors.{}' (fields: {})",
+                    field,
+                    Theme::FIELDS.join(", ")
+                );
+            };
+            *slot = color;
+        }
+        theme.light = theme.has_light_colors();
+        Ok(theme)
+    }
+}
+
+/// Reads the theme file at `path`, named after the file unless it sets `name`.
+pub fn load_theme_file(path: &Path, builtins: &[Theme]) -> Result<Theme> {
+    let stem = path
+        .file_stem()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let contents = fs::read_to_string(path)?;
+    let file: ThemeFile = toml::from_str(&contents)
+        .with_context(|| format!("invalid theme file '{}'", path.display()))?;
+    file.to_theme(&stem, builtins)
+        .with_context(|| format!("invalid theme file '{}'", path.display()))
+}
+
+/// The built-in themes followed by the `*.toml` themes of `dir`, sorted by
+/// file name. A user theme named like a built-in one replaces it. Files that
+/// cannot be loaded are returned as errors next to the themes.
+pub fn load_themes(dir: &Path) -> (Vec<Theme>, Vec<String>) {
+    let builtins = Theme::all();
+    let mut themes = builtins.clone();
+    let mut errors = Vec::new();
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|read_dir| {
+            read_dir
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.sort();
+
+    let mut user_names: Vec<String> = Vec::new();
+    for path in paths {
+        match load_theme_file(&path, &builtins) {
+            Ok(theme) if user_names.contains(&theme.name) => errors.push(format!(
+                "theme file '{}': another file already defines '{}'",
+                path.display(),
+                theme.name
+            )),
+            Ok(theme) => {
+                user_names.push(theme.name.clone());
+                match themes.iter_mut().find(|t| t.name == theme.name) {
+                    Some(builtin) => *builtin = theme,
+                    None => themes.push(theme),
+                }
+            }
+            Err(e) => errors.push(format!("{:#}", e)),
+        }
+    }
+    (themes, errors)
+}
+
+/// Converts the color scheme of `args` into a theme file in `dir` and returns
+/// its path.
+pub fn import_theme(dir: &Path, args: &ThemeImportArgs) -> Result<PathBuf> {
+    let mut file = import_scheme(&args.file)?;
+    let name = args
+        .name
+        .clone()
+        .or_else(|| file.name.clone())
+        .or_else(|| {
+            args.file
+                .file_stem()
+                .map(|n| n.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "imported".to_string());
+    file.name = Some(name.clone());
+    file.to_theme(&name, &Theme::all())?;
+
+    let path = dir.join(theme_file_name(&name));
+    if path.exists() && !args.force {
+        bail!(
+            "'{}' already exists, use --force to overwrite it",
+            path.display()
+        );
+    }
+    fs::create_dir_all(dir)?;
+    let contents = format!(
+        "# Imported from {} by `try-rs theme import`\n{}",
+        args.file.display(),
+        toml::to_string(&file)?
+    );
+    fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// A color as written in theme files: `#rrggbb`, a name or an index.
+fn color_value(color: Color) -> String {
+    match color {
+        Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        other => other.to_string().to_lowercase(),
+    }
+}
+
+/// The theme file setting every color of `theme`, independent of a built-in.
+pub fn export_theme(theme: &Theme) -> Result<String> {
+    let file = ThemeFile {
+        name: Some(theme.name.clone()),
+        background: Some(
+            theme
+                .background
+                .map(color_value)
+                .unwrap_or_else(|| NO_BACKGROUND.to_string()),
+        ),
+        colors: Theme::FIELDS
+            .iter()
+            .filter_map(|field| Some((field.to_string(), color_value(theme.color(field)?))))
+            .collect(),
+        ..ThemeFile::default()
+    };
+    Ok(format!(
+        "# Save as {}/{} to use it\n{}",
+        themes_dir().display(),
+        theme_file_name(&theme.name),
+        toml::to_string(&file)?
+    ))
+}
+
+/// The theme named `name`, ignoring case.
+pub fn find_theme<'a>(themes: &'a [Theme], name: &str) -> Result<&'a Theme> {
+    themes
+        .iter()
+        .find(|t| t.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            let names: Vec<&str> = themes.iter().map(|t| t.name.as_str()).collect();
+            anyhow!("unknown theme '{}' (themes: {})", name, names.join(", "))
+        })
+}
+
+/// Handles `try-rs theme`. `themes` are the themes as loaded for the terminal,
+/// exports read the files again to keep their full colors.
+pub fn run_theme(dir: &Path, themes: &[Theme], action: &ThemeAction) -> Result<()> {
+    match action {
+        ThemeAction::Import(args) => {
+            let path = import_theme(dir, args)?;
+            let name = load_theme_file(&path, &Theme::all())?.name;
+            eprintln!("Imported '{}' to {}", name, path.display());
+            eprintln!(
+                "Pick it with Ctrl+T or set theme = \"{}\" in config.toml.",
+                name
+            );
+        }
+        ThemeAction::List => print!("{}", theme_list(themes)),
+        ThemeAction::Show { name } => {
+            let theme = find_theme(themes, name)?;
+            let (width, height) = crossterm::terminal::size().unwrap_or(SAMPLE_SIZE);
+            let buffer = sample_buffer(
+                theme,
+                width.clamp(60, 140),
+                height.saturating_sub(2).clamp(12, SAMPLE_SIZE.1),
+            );
+            print!("{}", buffer_to_ansi(&buffer));
+        }
+        ThemeAction::Export { name } => {
+            let (all, _) = load_themes(dir);
+            print!("{}", export_theme(find_theme(&all, name)?)?);
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use std::ffi::OsString;
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::SystemTime;
@@ -79,6 +80,143 @@ pub fn remove_git_worktree(path_to_remove: &Path) -> std::io::Result<std::proces
         .output()
 }
 
+/// Removes a try folder from disk. Git worktrees are removed through
+/// `git worktree remove` so the main repository forgets about them.
+/// On failure the error message is returned ready to be shown to the user.
+pub fn remove_try_folder(path: &Path) -> Result<(), String> {
+    // Only use git worktree remove if it's actually a worktree (not main working tree)
+    if is_git_worktree(path) {
+        match remove_git_worktree(path) {
+            Ok(output) if output.status.success() => Ok(()),
+            Ok(output) => Err(format!(
+                "Error deleting: {}",
+                String::from_utf8_lossy(&output.stderr)
+                    .lines()
+                    .take(1)
+                    .collect::<String>()
+            )),
+            Err(e) => Err(format!("Error removing worktree: {}", e)),
+        }
+    } else {
+        // Regular directory or main git repo - just delete it
+        fs::remove_dir_all(path).map_err(|e| format!("Error deleting: {}", e))
+    }
+}
+
+/// Moves a try folder to `dest`. Git worktrees are moved through
+/// `git worktree move` so the main repository keeps track of them.
+/// On failure the error message is returned ready to be shown to the user.
+pub fn move_try_folder(path: &Path, dest: &Path) -> Result<(), String> {
+    if dest.exists() {
+        return Err(format!(
+            "Error moving: '{}' already exists",
+            dest.to_string_lossy()
+        ));
+    }
+    if is_git_worktree(path) {
+        match Command::new("git")
+            .arg("worktree")
+            .arg("move")
+            .arg(path)
+            .arg(dest)
+            .current_dir(path)
+            .output()
+        {
+            Ok(output) if output.status.success() => Ok(()),
+            Ok(output) => Err(format!(
+                "Error moving: {}",
+                String::from_utf8_lossy(&output.stderr)
+                    .lines()
+                    .take(1)
+                    .collect::<String>()
+            )),
+            Err(e) => Err(format!("Error moving worktree: {}", e)),
+        }
+    } else {
+        move_dir(path, dest, &|from, to| fs::rename(from, to))
+            .map_err(|e| format!("Error moving: {}", e))
+    }
+}
+
+/// Moves `path` to `dest` with `rename`. Across file systems, where a rename
+/// fails, the folder is copied and the original removed afterwards.
+pub fn move_dir(
+    path: &Path,
+    dest: &Path,
+    rename: &dyn Fn(&Path, &Path) -> io::Result<()>,
+) -> io::Result<()> {
+    match rename(path, dest) {
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {
+            if let Err(e) = copy_dir(path, dest, &[]) {
+                let _ = fs::remove_dir_all(dest);
+                return Err(e);
+            }
+            fs::remove_dir_all(path)
+        }
+        result => result,
+    }
+}
+
+/// Copies `src` into `dest`, skipping the directories listed in `skip`.
+/// Symlinks are recreated, not followed. `.git` files are left out: they point
+/// at the git data of another checkout, which the copy would share.
+pub fn copy_dir(src: &Path, dest: &Path, skip: &[PathBuf]) -> io::Result<()> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(src)?.flatten() {
+        let file_name = entry.file_name();
+        let target = dest.join(&file_name);
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if skip.contains(&entry.path()) {
+                continue;
+            }
+            copy_dir(&entry.path(), &target, skip)?;
+        } else if file_name == ".git" {
+            continue;
+        } else if file_type.is_symlink() {
+            copy_symlink(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dest: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(src)?, dest)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(src: &Path, dest: &Path) -> io::Result<()> {
+    fs::copy(src, dest).map(|_| ())
+}
+
+/// Matches a file name against a pattern supporting `*` (any run of
+/// characters) and `?` (exactly one character) wildcards.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            p = star_p + 1;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 pub fn expand_path(path_str: &str) -> PathBuf {
     if (path_str.starts_with("~/") || (cfg!(windows) && path_str.starts_with("~\\")))
         && let Some(home) = dirs::home_dir()
@@ -193,9 +331,13 @@ pub fn matching_folders(name: &str, path: &PathBuf) -> Vec<String> {
 // i've put this here since until now there is not really a library part
 pub enum SelectionResult {
     /// A explicit folder that is guaranteed to exist already
-    Folder(String),
+    Folder(PathBuf),
     /// No existing match, a new folder should be created
     New(String),
+    /// A new folder scaffolded from a template: (name, template name)
+    NewFromTemplate(String, String),
+    /// Several existing folders, opened together in the editor
+    Folders(Vec<PathBuf>),
     /// Nothing was selected in the UI, quit
     None,
 }
@@ -0,0 +1,150 @@
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+use tempdir::TempDir;
+use try_rs::archive::*;
+
+#[test]
+fn file_name_round_trip() {
+    assert_eq!(
+        ArchiveFormat::from_file_name("2025-01-02 demo.tar.zst"),
+        Some(("2025-01-02 demo".to_string(), ArchiveFormat::Zst))
+    );
+    assert_eq!(
+        ArchiveFormat::from_file_name("demo.tar.gz"),
+        Some(("demo".to_string(), ArchiveFormat::Gz))
+    );
+    assert_eq!(ArchiveFormat::from_file_name("demo.tar.zst.partial"), None);
+    assert_eq!(ArchiveFormat::from_file_name(".tar.gz"), None);
+    assert_eq!(ArchiveFormat::from_file_name("notes.txt"), None);
+    assert_eq!(
+        ArchiveFormat::from_file_name("demo~3.tar.zst"),
+        Some(("demo".to_string(), ArchiveFormat::Zst))
+    );
+    assert_eq!(
+        ArchiveFormat::from_file_name("demo~v2.tar.zst"),
+        Some(("demo~v2".to_string(), ArchiveFormat::Zst))
+    );
+}
+
+fn archive_and_restore(format: ArchiveFormat) {
+    let tmp = TempDir::new("archive-roundtrip").unwrap();
+    let tries = tmp.path().join("tries");
+    let archive_dir = tmp.path().join("archive");
+    let name = "2024-03-05 old-experiment";
+    let folder = tries.join(name);
+    fs::create_dir_all(folder.join("src")).unwrap();
+    fs::write(folder.join("src").join("main.rs"), "fn main() {}").unwrap();
+    let old = SystemTime::now() - Duration::from_secs(90 * 86400);
+    fs::File::options()
+        .write(true)
+        .open(folder.join("src").join("main.rs"))
+        .unwrap()
+        .set_modified(old)
+        .unwrap();
+
+    let archived = archive_folder(&tries, &archive_dir, name, format).unwrap();
+    assert!(!folder.exists());
+    assert_eq!(archived.name, name);
+    assert!(
+        archived
+            .path
+            .to_string_lossy()
+            .ends_with(format.extension())
+    );
+
+    let archives = list_archives(&archive_dir);
+    assert_eq!(archives.len(), 1);
+    assert_eq!(archives[0].format, format);
+
+    let restored = restore_archive(&archives[0], &tries, false).unwrap();
+    assert_eq!(restored, folder);
+    assert_eq!(
+        fs::read_to_string(folder.join("src").join("main.rs")).unwrap(),
+        "fn main() {}"
+    );
+    let mtime = fs::metadata(folder.join("src").join("main.rs"))
+        .unwrap()
+        .modified()
+        .unwrap();
+    assert!(mtime < SystemTime::now() - Duration::from_secs(89 * 86400));
+    assert!(list_archives(&archive_dir).is_empty());
+}
+
+#[test]
+fn archive_and_restore_zst() {
+    archive_and_restore(ArchiveFormat::Zst);
+}
+
+#[test]
+fn archive_and_restore_gz() {
+    archive_and_restore(ArchiveFormat::Gz);
+}
+
+#[test]
+fn restore_keeps_archive_and_refuses_overwrite() {
+    let tmp = TempDir::new("archive-keep").unwrap();
+    let tries = tmp.path().join("tries");
+    let archive_dir = tmp.path().join("archive");
+    fs::create_dir_all(tries.join("demo")).unwrap();
+
+    archive_folder(&tries, &archive_dir, "demo", ArchiveFormat::Zst).unwrap();
+    let archive = list_archives(&archive_dir).remove(0);
+    restore_archive(&archive, &tries, true).unwrap();
+    assert!(archive.path.exists());
+
+    assert!(restore_archive(&archive, &tries, false).is_err());
+    assert!(archive.path.exists());
+}
+
+#[test]
+fn archive_names_do_not_collide() {
+    let tmp = TempDir::new("archive-collide").unwrap();
+    let tries = tmp.path().join("tries");
+    let archive_dir = tmp.path().join("archive");
+
+    fs::create_dir_all(tries.join("demo")).unwrap();
+    archive_folder(&tries, &archive_dir, "demo", ArchiveFormat::Gz).unwrap();
+    fs::create_dir_all(tries.join("demo")).unwrap();
+    let second = archive_folder(&tries, &archive_dir, "demo", ArchiveFormat::Gz).unwrap();
+
+    // Both restore as `demo`
+    assert_eq!(second.name, "demo");
+    assert!(second.path.ends_with("demo~2.tar.gz"));
+    assert_eq!(list_archives(&archive_dir).len(), 2);
+    assert!(restore_archive(&second, &tries, false).is_ok());
+    assert!(tries.join("demo").is_dir());
+}
+
+#[test]
+fn archive_refuses_worktrees() {
+    let tmp = TempDir::new("archive-worktree").unwrap();
+    let tries = tmp.path().join("tries");
+    fs::create_dir_all(tries.join("wt")).unwrap();
+    fs::write(tries.join("wt").join(".git"), "gitdir: /somewhere/else\n").unwrap();
+
+    let err = archive_folder(
+        &tries,
+        &tmp.path().join("archive"),
+        "wt",
+        ArchiveFormat::Zst,
+    );
+    assert!(err.is_err());
+    assert!(tries.join("wt").exists());
+}
+
+#[test]
+fn search_archives_is_fuzzy() {
+    let tmp = TempDir::new("archive-search").unwrap();
+    let tries = tmp.path().join("tries");
+    let archive_dir = tmp.path().join("archive");
+    for name in ["2024-01-01 rust-parser", "2024-02-01 go-server"] {
+        fs::create_dir_all(tries.join(name)).unwrap();
+        archive_folder(&tries, &archive_dir, name, ArchiveFormat::Zst).unwrap();
+    }
+
+    let found = search_archives(list_archives(&archive_dir), "rsprs");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].name, "2024-01-01 rust-parser");
+    assert_eq!(search_archives(list_archives(&archive_dir), "").len(), 2);
+}
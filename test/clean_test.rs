@@ -0,0 +1,80 @@
+use std::fs;
+use std::process::Command;
+
+use tempdir::TempDir;
+use try_rs::clean::*;
+use try_rs::projects::ProjectDetector;
+
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_AUTHOR_NAME", "test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn finds_artifacts_next_to_the_manifest_only() {
+    let tmp = TempDir::new("clean-find").unwrap();
+    let dir = tmp.path();
+    fs::create_dir_all(dir.join(".venv").join("lib").join("__pycache__")).unwrap();
+    fs::create_dir_all(dir.join("pkg").join("__pycache__")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]\n").unwrap();
+    fs::write(dir.join("pkg").join("mod.py"), "x = 1\n").unwrap();
+    fs::write(dir.join(".venv").join("lib").join("site.py"), vec![0; 64]).unwrap();
+
+    let artifacts = experiment_artifacts(dir, &ProjectDetector::all());
+    assert_eq!(artifact_names(dir, &artifacts), ".venv");
+    assert_eq!(artifacts[0].bytes, 64);
+
+    assert_eq!(remove_artifacts(&artifacts).unwrap(), 64);
+    assert!(!dir.join(".venv").exists());
+    assert!(dir.join("pkg").join("__pycache__").exists());
+    assert!(dir.join("pkg").join("mod.py").exists());
+}
+
+#[test]
+fn nested_build_packages_are_kept() {
+    let tmp = TempDir::new("clean-gradle").unwrap();
+    let dir = tmp.path();
+    let package = dir.join("src/main/java/com/acme/build");
+    fs::create_dir_all(&package).unwrap();
+    fs::write(package.join("Foo.java"), "class Foo {}\n").unwrap();
+    fs::write(dir.join("build.gradle"), "").unwrap();
+    fs::create_dir_all(dir.join("build").join("classes")).unwrap();
+
+    let artifacts = experiment_artifacts(dir, &ProjectDetector::all());
+    assert_eq!(artifact_names(dir, &artifacts), "build");
+    remove_artifacts(&artifacts).unwrap();
+    assert!(!dir.join("build").exists());
+    assert!(package.join("Foo.java").exists());
+}
+
+#[test]
+fn tracked_directories_are_kept() {
+    let tmp = TempDir::new("clean-git").unwrap();
+    let dir = tmp.path();
+    git(dir, &["init", "-q"]);
+    fs::write(dir.join("Cargo.toml"), "[package]\n").unwrap();
+    fs::create_dir_all(dir.join("target")).unwrap();
+    fs::create_dir_all(dir.join("build")).unwrap();
+    fs::write(dir.join("build").join("keep.rs"), "").unwrap();
+    fs::write(dir.join("target").join("out"), "bin").unwrap();
+    git(dir, &["add", "Cargo.toml", "build"]);
+
+    let patterns = vec!["target".to_string(), "build".to_string()];
+    let artifacts = find_artifacts(dir, &patterns);
+    assert_eq!(artifact_names(dir, &artifacts), "target");
+}
+
+#[test]
+fn folders_without_project_type_have_no_artifacts() {
+    let tmp = TempDir::new("clean-none").unwrap();
+    fs::create_dir_all(tmp.path().join("target")).unwrap();
+    assert!(experiment_artifacts(tmp.path(), &ProjectDetector::all()).is_empty());
+}
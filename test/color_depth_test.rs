@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::fs;
+
+use ratatui::style::{Color, Modifier};
+use tempdir::TempDir;
+use try_rs::color_depth::*;
+use try_rs::config::Config;
+use try_rs::projects::ColorRole;
+use try_rs::themes::Theme;
+
+fn detect(vars: &[(&str, &str)]) -> ColorDepth {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    detect_color_depth(&|name| vars.get(name).cloned())
+}
+
+/// A compiled terminfo entry with only the `colors` number set.
+fn terminfo_entry(colors: i16) -> Vec<u8> {
+    let names = b"test-term|fake terminal\0";
+    let mut data = Vec::new();
+    for value in [0o432, names.len() as i16, 1, 14, 0, 0] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(names);
+    // One boolean, then padding to an even offset
+    data.push(0);
+    if data.len() % 2 == 1 {
+        data.push(0);
+    }
+    for i in 0..14 {
+        let value: i16 = if i == 13 { colors } else { -1 };
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data
+}
+
+#[test]
+fn no_color_wins_over_colorterm() {
+    assert_eq!(
+        detect(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")]),
+        ColorDepth::NoColor
+    );
+    // An empty NO_COLOR does not count
+    assert_eq!(
+        detect(&[("NO_COLOR", ""), ("COLORTERM", "truecolor")]),
+        ColorDepth::TrueColor
+    );
+}
+
+#[test]
+fn colorterm_and_term_names() {
+    assert_eq!(
+        detect(&[("COLORTERM", "24bit"), ("TERM", "xterm")]),
+        ColorDepth::TrueColor
+    );
+    assert_eq!(detect(&[]), ColorDepth::TrueColor);
+    assert_eq!(detect(&[("TERM", "dumb")]), ColorDepth::NoColor);
+    assert_eq!(
+        detect(&[("TERM", "unknown-256color-term")]),
+        ColorDepth::Ansi256
+    );
+    assert_eq!(detect(&[("TERM", "unknown-old-term")]), ColorDepth::Ansi16);
+}
+
+#[test]
+fn reads_colors_from_terminfo() {
+    let tmp = TempDir::new("terminfo").unwrap();
+    let dir = tmp.path().to_string_lossy().to_string();
+    fs::create_dir_all(tmp.path().join("z")).unwrap();
+    for (name, colors) in [("zz-mono", 0), ("zz-eight", 8), ("zz-many", 256)] {
+        fs::write(tmp.path().join("z").join(name), terminfo_entry(colors)).unwrap();
+    }
+    let with_term = |term| detect(&[("TERMINFO", dir.as_str()), ("TERM", term)]);
+    assert_eq!(with_term("zz-mono"), ColorDepth::NoColor);
+    assert_eq!(with_term("zz-eight"), ColorDepth::Ansi16);
+    assert_eq!(with_term("zz-many"), ColorDepth::Ansi256);
+}
+
+#[test]
+fn parses_terminfo_entries() {
+    assert_eq!(parse_terminfo_colors(&terminfo_entry(256)), Some(256));
+    assert_eq!(parse_terminfo_colors(&terminfo_entry(-1)), Some(0));
+    assert_eq!(parse_terminfo_colors(b"not terminfo"), None);
+}
+
+#[test]
+fn quantizes_to_256_colors() {
+    let depth = ColorDepth::Ansi256;
+    assert_eq!(depth.quantize(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+    assert_eq!(depth.quantize(Color::Rgb(0, 0, 0)), Color::Indexed(16));
+    // Grays go to the gray ramp
+    assert_eq!(
+        depth.quantize(Color::Rgb(128, 128, 128)),
+        Color::Indexed(244)
+    );
+    assert_eq!(depth.quantize(Color::Yellow), Color::Yellow);
+    assert_eq!(depth.quantize(Color::Reset), Color::Reset);
+}
+
+#[test]
+fn quantizes_to_16_colors() {
+    let depth = ColorDepth::Ansi16;
+    assert_eq!(depth.quantize(Color::Rgb(250, 10, 10)), Color::LightRed);
+    assert_eq!(depth.quantize(Color::Rgb(137, 180, 250)), Color::LightBlue);
+    assert_eq!(depth.quantize(Color::Rgb(30, 30, 46)), Color::Black);
+    assert_eq!(depth.quantize(Color::Indexed(46)), Color::LightGreen);
+    assert_eq!(depth.quantize(Color::Cyan), Color::Cyan);
+}
+
+#[test]
+fn truecolor_keeps_the_theme() {
+    let theme = Theme::nord();
+    let same = theme.clone().with_color_depth(ColorDepth::TrueColor);
+    assert_eq!(same.title_try, theme.title_try);
+    assert_eq!(same.background, theme.background);
+}
+
+#[test]
+fn themes_are_reduced_at_load() {
+    let theme = Theme::catppuccin_mocha().with_color_depth(ColorDepth::Ansi256);
+    assert_eq!(theme.color_depth, ColorDepth::Ansi256);
+    for field in Theme::FIELDS {
+        let mut theme = theme.clone();
+        let color = *theme.color_mut(field).unwrap();
+        assert!(!matches!(color, Color::Rgb(..)), "{} is {:?}", field, color);
+    }
+    assert!(matches!(theme.background, Some(Color::Indexed(_))));
+    let fixed = theme.role_color(ColorRole::Fixed(Color::Rgb(123, 66, 188)));
+    assert!(matches!(fixed, Color::Indexed(_)));
+}
+
+#[test]
+fn no_color_uses_modifiers_only() {
+    let theme = Theme::dracula().with_color_depth(ColorDepth::NoColor);
+    assert_eq!(theme.background, None);
+    assert_eq!(theme.title_try, Color::Reset);
+    let style = theme.highlight_style();
+    assert!(style.add_modifier.contains(Modifier::REVERSED));
+    assert!(style.add_modifier.contains(Modifier::BOLD));
+    assert!(
+        !Theme::dracula()
+            .highlight_style()
+            .add_modifier
+            .contains(Modifier::REVERSED)
+    );
+}
+
+#[test]
+fn config_values() {
+    for (value, depth) in [
+        ("truecolor", ColorDepth::TrueColor),
+        ("256", ColorDepth::Ansi256),
+        ("16", ColorDepth::Ansi16),
+        ("none", ColorDepth::NoColor),
+    ] {
+        let config: Config = toml::from_str(&format!("color_depth = \"{}\"", value)).unwrap();
+        assert_eq!(config.color_depth, Some(depth));
+    }
+    assert!(toml::from_str::<Config>("color_depth = \"8\"").is_err());
+}
@@ -1,7 +1,10 @@
 use std::path::PathBuf;
 
 use tempdir::TempDir;
+use try_rs::archive::ArchiveFormat;
+use try_rs::color_depth::ColorDepth;
 use try_rs::config::*;
+use try_rs::projects::ColorRole;
 use try_rs::themes::Theme;
 
 #[test]
@@ -77,9 +80,23 @@ fn config_serialization_roundtrip() {
     let config = Config {
         tries_path: Some("~/work/tries".to_string()),
         theme: Some("Tokyo Night".to_string()),
+        theme_light: Some("Solarized Light".to_string()),
+        theme_dark: None,
         editor: Some("nvim".to_string()),
         apply_date_prefix: Some(true),
         transparent_background: Some(true),
+        color_depth: Some(ColorDepth::Ansi256),
+        max_age_days: Some(30),
+        keep_pinned: Some(false),
+        trash_auto_empty_days: Some(7),
+        archive_path: Some("~/archive".to_string()),
+        archive_format: Some(ArchiveFormat::Gz),
+        sort_order: Some(SortOrder::Created),
+        project_types: None,
+        roots: None,
+        default_root: None,
+        vim_mode: Some(true),
+        keybindings: None,
     };
 
     let toml_str = toml::to_string(&config).unwrap();
@@ -90,6 +107,15 @@ fn config_serialization_roundtrip() {
     assert_eq!(loaded.editor, config.editor);
     assert_eq!(loaded.apply_date_prefix, config.apply_date_prefix);
     assert_eq!(loaded.transparent_background, config.transparent_background);
+    assert_eq!(loaded.max_age_days, config.max_age_days);
+    assert_eq!(loaded.keep_pinned, config.keep_pinned);
+    assert_eq!(loaded.trash_auto_empty_days, config.trash_auto_empty_days);
+    assert_eq!(loaded.archive_path, config.archive_path);
+    assert_eq!(loaded.archive_format, config.archive_format);
+    assert_eq!(loaded.sort_order, config.sort_order);
+    assert_eq!(loaded.vim_mode, config.vim_mode);
+    assert_eq!(loaded.color_depth, config.color_depth);
+    assert_eq!(loaded.theme_light, config.theme_light);
 }
 
 #[test]
@@ -156,3 +182,137 @@ fn save_config_preserves_theme_name() {
         assert_eq!(loaded.theme.as_deref(), Some(theme.name.as_str()));
     }
 }
+
+#[test]
+fn config_deserialize_project_types() {
+    let config: Config = toml::from_str(
+        r##"
+[[project_types]]
+name = "Bazel"
+markers = ["WORKSPACE", "MODULE.bazel"]
+icon = ""
+color = "green"
+
+[[project_types]]
+name = "Terraform"
+markers = ["*.tf"]
+icon = "󱁢"
+color = "#7b42bc"
+"##,
+    )
+    .unwrap();
+
+    let types = config.project_types.unwrap();
+    assert_eq!(types.len(), 2);
+    assert_eq!(types[0].name, "Bazel");
+    assert_eq!(types[0].markers, vec!["WORKSPACE", "MODULE.bazel"]);
+    assert_eq!(types[1].color.as_deref(), Some("#7b42bc"));
+}
+
+#[test]
+fn config_deserialize_roots() {
+    let config: Config = toml::from_str(
+        r#"
+default_root = "work"
+
+[roots.work]
+path = "~/work/tries"
+
+[roots.oss]
+path = "/src/oss"
+"#,
+    )
+    .unwrap();
+
+    let roots = config.roots.unwrap();
+    assert_eq!(roots.len(), 2);
+    assert_eq!(roots["work"].path, "~/work/tries");
+    assert_eq!(roots["oss"].path, "/src/oss");
+    assert_eq!(config.default_root.as_deref(), Some("work"));
+}
+
+#[test]
+fn config_deserialize_keybindings() {
+    let config: Config = toml::from_str(
+        r#"
+[keybindings.normal]
+ctrl-b = "theme"
+ctrl-t = "none"
+
+[keybindings.trash]
+d = "delete"
+"#,
+    )
+    .unwrap();
+
+    let keybindings = config.keybindings.unwrap();
+    assert_eq!(keybindings["normal"]["ctrl-b"], "theme");
+    assert_eq!(keybindings["normal"]["ctrl-t"], "none");
+    assert_eq!(keybindings["trash"]["d"], "delete");
+}
+
+#[test]
+fn project_detectors_appends_custom_types() {
+    let custom = vec![ProjectTypeConfig {
+        name: "Terraform".to_string(),
+        markers: vec!["*.tf".to_string()],
+        icon: "󱁢".to_string(),
+        color: Some("#7b42bc".to_string()),
+        artifacts: vec![".terraform".to_string()],
+    }];
+    let detectors = project_detectors(&custom);
+    let terraform = detectors.last().unwrap();
+    assert_eq!(terraform.name, "Terraform");
+    assert_eq!(terraform.icon, "󱁢 ");
+    assert_eq!(
+        terraform.color,
+        ColorRole::Fixed(ratatui::style::Color::Rgb(0x7b, 0x42, 0xbc))
+    );
+    assert!(terraform.matches(&["main.tf".to_string()]));
+    assert_eq!(terraform.artifacts, vec![".terraform"]);
+}
+
+#[test]
+fn project_detectors_custom_type_overrides_builtin() {
+    let custom = vec![ProjectTypeConfig {
+        name: "Rust".to_string(),
+        markers: vec!["rust-toolchain.toml".to_string()],
+        icon: "R".to_string(),
+        color: Some("warm".to_string()),
+        artifacts: vec![],
+    }];
+    let detectors = project_detectors(&custom);
+    let rust: Vec<_> = detectors.iter().filter(|d| d.name == "Rust").collect();
+    assert_eq!(rust.len(), 1);
+    assert_eq!(rust[0].markers, vec!["rust-toolchain.toml"]);
+    assert_eq!(rust[0].color, ColorRole::Warm);
+    assert_eq!(rust[0].artifacts, vec!["target"]);
+}
+
+#[test]
+fn save_config_keeps_project_types() {
+    let tmp = TempDir::new("keep-project-types").unwrap();
+    let config_path = tmp.path().join("config.toml");
+    std::fs::write(
+        &config_path,
+        "[[project_types]]\nname = \"Nix\"\nmarkers = [\"flake.nix\"]\nicon = \"\"\n",
+    )
+    .unwrap();
+
+    save_config(
+        &config_path,
+        &Theme::default(),
+        &PathBuf::from("/tmp/t"),
+        &None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let contents = std::fs::read_to_string(&config_path).unwrap();
+    let loaded: Config = toml::from_str(&contents).unwrap();
+    assert_eq!(loaded.theme.as_deref(), Some("Default"));
+    let types = loaded.project_types.unwrap();
+    assert_eq!(types[0].name, "Nix");
+    assert_eq!(types[0].markers, vec!["flake.nix"]);
+}
@@ -0,0 +1,135 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use tempdir::TempDir;
+use try_rs::fork::*;
+use try_rs::metadata::TryMetadata;
+use try_rs::projects::ProjectDetector;
+use try_rs::utils::is_git_worktree;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn init_repo(dir: &Path) {
+    fs::create_dir_all(dir).unwrap();
+    git(dir, &["init", "-q"]);
+    git(dir, &["config", "user.email", "test@test.internal"]);
+    git(dir, &["config", "user.name", "Test"]);
+    fs::write(dir.join("file.txt"), "v1").unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-q", "-m", "init"]);
+}
+
+#[test]
+fn fork_name_uses_today() {
+    let today = "2026-10-17";
+    assert_eq!(
+        fork_name("2025-01-02 parser", None, today),
+        "2026-10-17 parser-fork"
+    );
+    assert_eq!(
+        fork_name("parser", Some("lexer"), today),
+        "2026-10-17 lexer"
+    );
+    assert_eq!(
+        fork_name("parser", Some("2025-01-02 lexer"), today),
+        "2026-10-17 lexer"
+    );
+}
+
+#[test]
+fn copy_skips_build_artifacts() {
+    let tmp = TempDir::new("fork-copy").unwrap();
+    let tries = tmp.path();
+    let source = tries.join("app");
+    fs::create_dir_all(source.join("src")).unwrap();
+    fs::create_dir_all(source.join("target").join("debug")).unwrap();
+    fs::create_dir_all(source.join("node_modules")).unwrap();
+    fs::write(source.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(source.join("src").join("main.rs"), "fn main() {}").unwrap();
+    fs::write(source.join("target").join("debug").join("app"), "bin").unwrap();
+    // Not next to the manifest: a source package, not the build output
+    fs::create_dir_all(source.join("src").join("target")).unwrap();
+    fs::write(source.join("src").join("target").join("mod.rs"), "").unwrap();
+
+    let to = fork_experiment(
+        tries,
+        "app",
+        "app-fork",
+        ForkMethod::Copy,
+        &ProjectDetector::all(),
+    )
+    .unwrap();
+    assert!(to.join("src").join("main.rs").exists());
+    assert!(!to.join("target").exists());
+    assert!(to.join("src").join("target").join("mod.rs").exists());
+    // Only the artifacts of the detected project types are skipped
+    assert!(to.join("node_modules").exists());
+    assert!(source.join("target").exists());
+    assert_eq!(
+        TryMetadata::load(&to).source,
+        Some(source.to_string_lossy().to_string())
+    );
+
+    assert!(
+        fork_experiment(tries, "app", "app-fork", ForkMethod::Copy, &[]).is_err(),
+        "existing target"
+    );
+    assert!(fork_experiment(tries, "app", "other", ForkMethod::Worktree, &[]).is_err());
+}
+
+#[test]
+fn fork_git_repository_as_worktree_and_clone() {
+    let tmp = TempDir::new("fork-git").unwrap();
+    let tries = tmp.path();
+    init_repo(&tries.join("repo"));
+
+    let worktree =
+        fork_experiment(tries, "repo", "2026-10-17 idea", ForkMethod::Worktree, &[]).unwrap();
+    assert!(git(&tries.join("repo"), &["worktree", "list"]).contains("[idea]"));
+    assert!(git(&worktree, &["status", "--porcelain"]).is_empty());
+
+    let clone = fork_experiment(tries, "repo", "copy", ForkMethod::Clone, &[]).unwrap();
+    assert_eq!(fs::read_to_string(clone.join("file.txt")).unwrap(), "v1");
+    assert!(git(&clone, &["status", "--porcelain"]).is_empty());
+}
+
+#[test]
+fn worktrees_are_not_copied() {
+    let tmp = TempDir::new("fork-worktree").unwrap();
+    let tries = tmp.path();
+    init_repo(&tries.join("repo"));
+    let worktree = fork_experiment(tries, "repo", "idea", ForkMethod::Worktree, &[]).unwrap();
+    assert!(is_git_worktree(&worktree));
+    assert!(!is_git_worktree(&tries.join("repo")));
+
+    let error = fork_experiment(tries, "idea", "copy", ForkMethod::Copy, &[]).unwrap_err();
+    assert!(error.to_string().contains("worktree"));
+    assert!(!tries.join("copy").exists());
+
+    // A `.git` file deeper down is not copied either
+    let nested = tries.join("plain").join("module");
+    fs::create_dir_all(&nested).unwrap();
+    fs::copy(worktree.join(".git"), nested.join(".git")).unwrap();
+    fs::write(nested.join("lib.rs"), "").unwrap();
+    let copy = fork_experiment(tries, "plain", "plain-copy", ForkMethod::Copy, &[]).unwrap();
+    assert!(copy.join("module").join("lib.rs").exists());
+    assert!(!copy.join("module").join(".git").exists());
+
+    let clone = fork_experiment(tries, "idea", "clone", ForkMethod::Clone, &[]).unwrap();
+    assert!(!is_git_worktree(&clone));
+    assert_eq!(fs::read_to_string(clone.join("file.txt")).unwrap(), "v1");
+}
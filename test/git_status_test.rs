@@ -0,0 +1,103 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use tempdir::TempDir;
+use try_rs::git_status::*;
+
+fn git(dir: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn parse_porcelain_v2_output() {
+    let output = "\
+# branch.oid 1234567890abcdef
+# branch.head main
+# branch.upstream origin/main
+# branch.ab +2 -1
+1 .M N... 100644 100644 100644 abc abc src/main.rs
+2 R. N... 100644 100644 100644 abc abc R100 new.rs\told.rs
+u UU N... 100644 100644 100644 100644 abc abc abc conflict.rs
+? notes.txt
+";
+    let status = parse_porcelain_v2(output);
+    assert_eq!(status.branch.as_deref(), Some("main"));
+    assert_eq!(status.upstream.as_deref(), Some("origin/main"));
+    assert_eq!((status.ahead, status.behind), (2, 1));
+    assert_eq!(status.changed, 3);
+    assert_eq!(status.untracked, 1);
+    assert!(status.is_dirty());
+
+    let detached = parse_porcelain_v2("# branch.head (detached)\n");
+    assert_eq!(detached.branch, None);
+    assert!(!detached.is_dirty());
+}
+
+#[test]
+fn git_status_of_repository() {
+    let tmp = TempDir::new("git-status").unwrap();
+    let origin = tmp.path().join("origin");
+    fs::create_dir_all(&origin).unwrap();
+    git(&origin, &["init", "-q", "-b", "main"]);
+    git(&origin, &["config", "user.email", "test@test.internal"]);
+    git(&origin, &["config", "user.name", "Test"]);
+    fs::write(origin.join("file.txt"), "v1").unwrap();
+    git(&origin, &["add", "."]);
+    git(&origin, &["commit", "-q", "-m", "first commit"]);
+
+    assert!(git_status(&tmp.path().join("missing")).is_none());
+    let status = git_status(&origin).unwrap();
+    assert_eq!(status.branch.as_deref(), Some("main"));
+    // Without a remote every commit would be lost
+    assert_eq!(status.unpushed, Some(1));
+    assert_eq!(status.unpushed_commits(), 1);
+    assert_eq!(status.last_commit.unwrap().subject, "first commit");
+
+    let clone = tmp.path().join("clone");
+    git(
+        tmp.path(),
+        &["clone", "-q", &origin.to_string_lossy(), "clone"],
+    );
+    git(&clone, &["config", "user.email", "test@test.internal"]);
+    git(&clone, &["config", "user.name", "Test"]);
+    fs::write(clone.join("file.txt"), "v2").unwrap();
+    git(&clone, &["commit", "-q", "-am", "second commit"]);
+    fs::write(clone.join("new.txt"), "x").unwrap();
+
+    let status = git_status(&clone).unwrap();
+    assert_eq!(status.upstream.as_deref(), Some("origin/main"));
+    assert_eq!(status.ahead, 1);
+    assert_eq!(status.unpushed_commits(), 1);
+    assert_eq!((status.changed, status.untracked), (0, 1));
+}
+
+#[test]
+fn unpushed_counts_every_branch_without_remote() {
+    let tmp = TempDir::new("git-status-local").unwrap();
+    let repo = tmp.path();
+    git(repo, &["init", "-q", "-b", "main"]);
+    git(repo, &["config", "user.email", "test@test.internal"]);
+    git(repo, &["config", "user.name", "Test"]);
+    fs::write(repo.join("file.txt"), "v1").unwrap();
+    git(repo, &["add", "."]);
+    git(repo, &["commit", "-q", "-m", "first commit"]);
+    git(repo, &["checkout", "-q", "-b", "side"]);
+    fs::write(repo.join("file.txt"), "v2").unwrap();
+    git(repo, &["commit", "-q", "-am", "side commit"]);
+    git(repo, &["checkout", "-q", "main"]);
+
+    let status = git_status(repo).unwrap();
+    assert_eq!(status.upstream, None);
+    assert_eq!(status.unpushed_commits(), 2);
+}
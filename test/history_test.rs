@@ -0,0 +1,49 @@
+use std::fs;
+use std::path::Path;
+
+use tempdir::TempDir;
+use try_rs::history::*;
+
+const NOW: i64 = 1_700_000_000;
+
+#[test]
+fn frecency_grows_with_visits_and_decays_with_age() {
+    let mut history = History::default();
+    let folder = Path::new("/tries/demo");
+    assert_eq!(history.frecency(folder, NOW), 0.0);
+
+    history.record(folder, NOW);
+    history.record(folder, NOW);
+    assert_eq!(
+        history.visits[&folder.to_string_lossy().to_string()].count,
+        2
+    );
+    assert_eq!(history.frecency(folder, NOW), 8.0);
+    assert_eq!(history.frecency(folder, NOW + 2 * 3600), 4.0);
+    assert_eq!(history.frecency(folder, NOW + 2 * 86400), 1.0);
+    assert_eq!(history.frecency(folder, NOW + 30 * 86400), 0.5);
+}
+
+#[test]
+fn save_and_load_forgets_missing_folders() {
+    let tmp = TempDir::new("history").unwrap();
+    let kept = tmp.path().join("kept");
+    fs::create_dir_all(&kept).unwrap();
+    let path = tmp.path().join("data").join("history.toml");
+
+    let mut history = History::default();
+    history.record(&kept, NOW);
+    history.record(&tmp.path().join("gone"), NOW);
+    history.save(&path).unwrap();
+
+    let loaded = History::load(&path);
+    assert_eq!(loaded.visits.len(), 1);
+    assert_eq!(loaded.frecency(&kept, NOW), 4.0);
+}
+
+#[test]
+fn frecency_bonus_is_monotonic_and_bounded() {
+    assert_eq!(frecency_bonus(0.0), 0);
+    assert!(frecency_bonus(4.0) > frecency_bonus(1.0));
+    assert!(frecency_bonus(1000.0) < 100);
+}
@@ -343,6 +343,7 @@ impl Harness {
             .args(args)
             .env("SHELL", "")
             .env("TRY_CONFIG_DIR", self.dir.path())
+            .env("TRY_DATA_DIR", self.dir.path().join("data"))
             .output()
             .map(|output| Output {
                 status: output.status,
@@ -362,6 +363,7 @@ impl Harness {
             .arg("--")
             .args(args)
             .env("TRY_CONFIG_DIR", self.dir.path())
+            .env("TRY_DATA_DIR", self.dir.path().join("data"))
             .env(env_key, env_val)
             .output()
             .map(|output| Output {
@@ -372,6 +374,33 @@ impl Harness {
             .expect("failed to spawn process")
     }
 
+    /// Runs `commands` in bash after loading the shell integration, with the
+    /// built binary on the PATH.
+    fn run_in_bash(&self, commands: &str) -> Output {
+        let setup = self.run_try(&["--setup-stdout", "bash"]);
+        assert!(setup.status.success(), "{}", setup.stderr);
+        let bin_dir = current_dir().unwrap().join("target").join("debug");
+        let path = format!(
+            "{}:{}",
+            bin_dir.display(),
+            std::env::var("PATH").unwrap_or_default()
+        );
+        Command::new("bash")
+            .arg("-c")
+            .arg(format!("{}\n{}", setup.stdout, commands))
+            .env("PATH", path)
+            .env("SHELL", "")
+            .env("TRY_CONFIG_DIR", self.dir.path())
+            .env("TRY_DATA_DIR", self.dir.path().join("data"))
+            .output()
+            .map(|output| Output {
+                status: output.status,
+                stderr: String::from_utf8(output.stderr).expect("couldn't read stderr to string"),
+                stdout: String::from_utf8(output.stdout).expect("couldn't read stdout to string"),
+            })
+            .expect("failed to spawn bash")
+    }
+
     fn create_try_folder(&self, name: &str) {
         fs::DirBuilder::new()
             .recursive(true)
@@ -587,3 +616,383 @@ fn try_path_env_overrides_config() {
         "TRY_PATH env should override config tries_path"
     );
 }
+
+#[test]
+fn list_prints_entries_as_json() {
+    let h = Harness::new(false);
+    h.create_try_folder("first");
+    h.create_try_folder("second");
+    fs::write(h.tries_path().join("second").join("Cargo.toml"), "").unwrap();
+
+    let p = h.run_try(&["list", "--format", "json", "--sort", "name"]);
+
+    assert!(p.status.success());
+    assert!(
+        !p.stdout.contains("cd '"),
+        "list must not print a cd command"
+    );
+    let first = p
+        .stdout
+        .find("\"name\": \"first\"")
+        .expect("first is listed");
+    let second = p
+        .stdout
+        .find("\"name\": \"second\"")
+        .expect("second is listed");
+    assert!(first < second, "entries are sorted by name");
+    assert!(p.stdout.contains("\"Rust\""));
+}
+
+#[test]
+fn list_and_jump_cover_every_root() {
+    let h = Harness::new(false);
+    let other = h.dir.path().join("other");
+    fs::create_dir_all(other.join("elsewhere")).unwrap();
+    h.create_try_folder("here");
+    let config_path = h.dir.path().join("config.toml");
+    let mut config = fs::read_to_string(&config_path).unwrap();
+    config.push_str(&format!(
+        "\n[roots.other]\npath = \"{}\"\n",
+        other.display()
+    ));
+    fs::write(&config_path, config).unwrap();
+
+    let p = h.run_try(&["list", "--format", "json"]);
+    assert!(p.status.success());
+    assert!(p.stdout.contains("\"name\": \"here\""));
+    assert!(p.stdout.contains("\"root\": \"other\""));
+
+    let p = h.run_try(&["--root", "other", "list", "--format", "json"]);
+    assert!(p.stdout.contains("\"name\": \"elsewhere\""));
+    assert!(!p.stdout.contains("\"name\": \"here\""));
+
+    let p = h.run_try(&["elsewhere"]);
+    assert!(p.status.success());
+    assert!(
+        p.stdout
+            .contains(&other.join("elsewhere").display().to_string())
+    );
+
+    let p = h.run_try(&["--root", "nope", "list"]);
+    assert!(!p.status.success());
+    assert!(p.stderr.contains("Unknown root 'nope'"));
+}
+
+#[cfg(unix)]
+fn make_old(path: &std::path::Path, days: u64) {
+    let time = std::time::SystemTime::now() - std::time::Duration::from_secs(days * 86400);
+    fs::File::open(path)
+        .and_then(|f| f.set_modified(time))
+        .expect("couldn't set modification time");
+}
+
+#[cfg(unix)]
+#[test]
+fn prune_dry_run_keeps_folders() {
+    let h = Harness::new(false);
+    h.create_try_folder("ancient");
+    h.create_try_folder("recent");
+    make_old(&h.tries_path().join("ancient"), 100);
+
+    let p = h.run_try(&["prune", "--older-than", "30", "--dry-run"]);
+
+    assert!(p.status.success());
+    assert!(p.stdout.contains("ancient"));
+    assert!(!p.stdout.contains("recent"));
+    assert!(h.tries_path().join("ancient").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn prune_removes_stale_folders_and_worktrees() {
+    let h = Harness::new(false);
+    let git_dir = create_git_origin(&h).expect("could not setup git origin");
+    h.create_try_folder("ancient");
+    h.create_try_folder("recent");
+    let worktree = h.tries_path().join("old-worktree");
+    command(
+        &git_dir,
+        "git",
+        &[
+            "worktree",
+            "add",
+            "-b",
+            "old-branch",
+            &worktree.to_string_lossy(),
+        ],
+    )
+    .unwrap();
+    make_old(&h.tries_path().join("ancient"), 100);
+    make_old(&worktree, 100);
+
+    let p = h.run_try(&["prune", "--older-than", "30", "--yes"]);
+
+    assert!(p.status.success(), "{}", p.stderr);
+    assert!(!h.tries_path().join("ancient").exists());
+    assert!(!worktree.exists());
+    assert!(h.tries_path().join("recent").exists());
+    let worktrees = command(&git_dir, "git", &["worktree", "list"]).unwrap();
+    assert!(
+        !worktrees.stdout.contains("old-worktree"),
+        "worktree must be unregistered from the main repository"
+    );
+}
+
+#[test]
+fn clean_removes_artifacts_and_keeps_sources() {
+    let h = Harness::new(false);
+    let exp = h.tries_path().join("crate");
+    h.create_try_folder("crate");
+    h.create_try_folder("notes");
+    fs::write(exp.join("Cargo.toml"), "[package]\n").unwrap();
+    fs::create_dir_all(exp.join("target").join("debug")).unwrap();
+    fs::write(exp.join("target").join("debug").join("crate"), "bin").unwrap();
+
+    let p = h.run_try(&["clean", "--dry-run"]);
+    assert!(p.status.success(), "{}", p.stderr);
+    assert!(p.stdout.contains("crate (target)"));
+    assert!(!p.stdout.contains("notes"));
+    assert!(exp.join("target").exists());
+
+    let p = h.run_try(&["clean", "crate", "--yes"]);
+    assert!(p.status.success(), "{}", p.stderr);
+    assert!(!exp.join("target").exists());
+    assert!(exp.join("Cargo.toml").exists());
+}
+
+#[test]
+fn prune_without_age_fails() {
+    let h = Harness::new(false);
+    let p = h.run_try(&["prune"]);
+    assert!(!p.status.success());
+}
+
+#[test]
+fn archive_and_restore_round_trip() {
+    let h = Harness::new(false);
+    h.create_try_folder("2024-05-06 parser");
+    std::fs::write(
+        h.tries_path().join("2024-05-06 parser").join("notes.md"),
+        "hi",
+    )
+    .unwrap();
+
+    let p = h.run_try(&["archive", "parser"]);
+    assert!(p.status.success(), "{}", p.stderr);
+    assert!(!h.tries_path().join("2024-05-06 parser").exists());
+    assert!(
+        h.tries_path()
+            .join(".archive")
+            .join("2024-05-06 parser.tar.zst")
+            .exists()
+    );
+
+    let p = h.run_try(&["restore", "--list", "pars"]);
+    assert!(p.status.success());
+    assert!(p.stdout.contains("2024-05-06 parser"));
+
+    let p = h.run_try(&["restore", "pars"]);
+    assert!(p.status.success(), "{}", p.stderr);
+    assert!(
+        h.tries_path()
+            .join("2024-05-06 parser")
+            .join("notes.md")
+            .exists()
+    );
+}
+
+#[test]
+fn new_with_template_scaffolds_folder() {
+    let h = Harness::new(false);
+    let templates = h.dir.path().join("templates");
+    fs::create_dir_all(&templates).unwrap();
+    fs::write(
+        templates.join("notes.toml"),
+        "[[files]]\npath = \"README.md\"\ncontent = \"# {{name}} {{topic}}\"\n",
+    )
+    .unwrap();
+
+    let p = h.run_try(&["new", "demo", "--template", "notes", "--var", "topic=x"]);
+    assert!(p.status.success(), "{}", p.stderr);
+    assert!(p.stdout.contains("cd '"));
+    assert_eq!(
+        fs::read_to_string(h.tries_path().join("demo").join("README.md")).unwrap(),
+        "# demo x"
+    );
+
+    let p = h.run_try(&["new", "other", "--template", "missing"]);
+    assert!(!p.status.success());
+    assert!(!h.tries_path().join("other").exists());
+}
+
+#[test]
+fn shell_wrapper_changes_into_new_template_experiment() {
+    let h = Harness::new(false);
+    let templates = h.dir.path().join("templates");
+    fs::create_dir_all(&templates).unwrap();
+    fs::write(
+        templates.join("notes.toml"),
+        "[[files]]\npath = \"README.md\"\ncontent = \"# {{name}}\"\n",
+    )
+    .unwrap();
+
+    let p = h.run_in_bash("cd / && try-rs new parser --template notes --var topic=x && pwd");
+    assert!(p.status.success(), "{}", p.stderr);
+    let expected = h.tries_path().join("parser");
+    assert_eq!(p.stdout.trim(), expected.display().to_string());
+    assert!(expected.join("README.md").exists());
+
+    // Help is printed, not evaluated
+    let p = h.run_in_bash("try-rs new --help");
+    assert!(p.status.success(), "{}", p.stderr);
+    assert!(p.stdout.contains("--template"));
+}
+
+#[test]
+fn shell_wrapper_changes_into_experiment_of_picked_root() {
+    let h = Harness::new(false);
+    let other = h.dir.path().join("other");
+    fs::create_dir_all(other.join("elsewhere")).unwrap();
+    let config_path = h.dir.path().join("config.toml");
+    let mut config = fs::read_to_string(&config_path).unwrap();
+    config.push_str(&format!(
+        "\n[roots.other]\npath = \"{}\"\n",
+        other.display()
+    ));
+    fs::write(&config_path, config).unwrap();
+
+    let p = h.run_in_bash("cd / && try-rs --root other elsewhere && pwd");
+    assert!(p.status.success(), "{}", p.stderr);
+    assert_eq!(
+        p.stdout.trim(),
+        other.join("elsewhere").display().to_string()
+    );
+
+    // Listing is printed, not evaluated
+    let p = h.run_in_bash("try-rs --root other list");
+    assert!(p.status.success(), "{}", p.stderr);
+    assert!(p.stdout.contains("elsewhere"));
+}
+
+#[test]
+fn selecting_folder_records_visit() {
+    let h = Harness::new(false);
+    h.create_try_folder("visited");
+
+    let p = h.run_try(&["visited"]);
+    assert!(p.status.success());
+
+    let history = fs::read_to_string(h.dir.path().join("data").join("history.toml")).unwrap();
+    assert!(history.contains("visited"));
+    assert!(history.contains("count = 1"));
+}
+
+#[test]
+fn prune_keeps_pinned_unless_forced() {
+    let h = Harness::new(false);
+    h.create_try_folder("playground");
+    make_old(&h.tries_path().join("playground"), 100);
+    let data = h.dir.path().join("data");
+    fs::create_dir_all(&data).unwrap();
+    fs::write(
+        data.join("pins.toml"),
+        format!(
+            "pinned = [{:?}]\n",
+            h.tries_path().join("playground").to_string_lossy()
+        ),
+    )
+    .unwrap();
+
+    let p = h.run_try(&["prune", "--older-than", "30", "--yes"]);
+    assert!(p.status.success());
+    assert!(p.stderr.contains("Keeping 1 pinned"));
+    assert!(h.tries_path().join("playground").exists());
+
+    let p = h.run_try(&["prune", "--older-than", "30", "--yes", "--force"]);
+    assert!(p.status.success());
+    assert!(!h.tries_path().join("playground").exists());
+}
+
+#[test]
+fn mv_keeps_date_prefix_and_pin() {
+    let h = Harness::new(false);
+    h.create_try_folder("2025-01-02 parser");
+    let old = h.tries_path().join("2025-01-02 parser");
+    let data = h.dir.path().join("data");
+    fs::create_dir_all(&data).unwrap();
+    fs::write(
+        data.join("pins.toml"),
+        format!("pinned = [{:?}]\n", old.to_string_lossy()),
+    )
+    .unwrap();
+
+    let p = h.run_try(&["mv", "parser", "lexer"]);
+    assert!(p.status.success(), "stderr: {}", p.stderr);
+    assert!(p.stdout.is_empty());
+    let new = h.tries_path().join("2025-01-02 lexer");
+    assert!(new.is_dir());
+    assert!(!old.exists());
+    let pins = fs::read_to_string(data.join("pins.toml")).unwrap();
+    assert!(pins.contains(new.to_string_lossy().as_ref()));
+
+    let p = h.run_try(&["mv", "missing", "other"]);
+    assert!(!p.status.success());
+}
+
+#[test]
+fn shell_wrapper_changes_into_fork() {
+    let h = Harness::new(false);
+    h.create_try_folder("parser");
+
+    let p = h.run_in_bash("cd / && try-rs fork parser lexer -m copy && pwd");
+    assert!(p.status.success(), "{}", p.stderr);
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    let fork = h.tries_path().join(format!("{} lexer", today));
+    assert_eq!(p.stdout.trim(), fork.display().to_string());
+}
+
+#[test]
+fn fork_copies_into_dated_folder() {
+    let h = Harness::new(false);
+    h.create_try_folder("2025-01-02 parser");
+    fs::write(h.tries_path().join("2025-01-02 parser/notes.txt"), "idea").unwrap();
+
+    let p = h.run_try(&["fork", "parser", "lexer", "--method", "copy"]);
+    assert!(p.status.success(), "stderr: {}", p.stderr);
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    let fork = h.tries_path().join(format!("{} lexer", today));
+    assert!(
+        p.stdout
+            .contains(&format!("cd '{}'", fork.to_string_lossy()))
+    );
+    assert_eq!(fs::read_to_string(fork.join("notes.txt")).unwrap(), "idea");
+    assert!(h.tries_path().join("2025-01-02 parser/notes.txt").exists());
+}
+
+#[test]
+fn exported_theme_can_be_customized() {
+    let h = Harness::new(false);
+    let p = h.run_try(&["theme", "export", "nord"]);
+    assert!(p.status.success());
+    assert!(p.stdout.contains("name = \"Nord\""));
+    assert!(p.stdout.contains("list_highlight_bg = \"#"));
+
+    let themes_dir = h.dir.path().join("themes");
+    fs::create_dir_all(&themes_dir).unwrap();
+    let custom = p.stdout.replace("name = \"Nord\"", "name = \"My Nord\"");
+    fs::write(themes_dir.join("my-nord.toml"), custom).unwrap();
+
+    let p = h.run_try_with_env(&["theme", "list"], "NO_COLOR", "1");
+    assert!(p.status.success());
+    assert!(p.stdout.contains("My Nord"));
+    assert!(p.stdout.contains("Light:"));
+    assert!(!p.stderr.contains("Warning"), "{}", p.stderr);
+
+    let p = h.run_try(&["theme", "show", "my nord"]);
+    assert!(p.status.success());
+    assert!(p.stdout.contains("Legends"));
+
+    let p = h.run_try(&["theme", "show", "missing"]);
+    assert!(!p.status.success());
+    assert!(p.stderr.contains("unknown theme 'missing'"));
+}
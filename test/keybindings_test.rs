@@ -0,0 +1,205 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use try_rs::keybindings::*;
+use try_rs::tui::AppMode;
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+}
+
+fn ctrl(c: char) -> KeyEvent {
+    key(KeyCode::Char(c), KeyModifiers::CONTROL)
+}
+
+fn config(mode: &str, bindings: &[(&str, &str)]) -> KeybindingsConfig {
+    let table = bindings
+        .iter()
+        .map(|(k, a)| (k.to_string(), a.to_string()))
+        .collect();
+    [(mode.to_string(), table)].into_iter().collect()
+}
+
+#[test]
+fn parse_accepts_several_spellings() {
+    let ctrl_t = KeyChord::new(KeyCode::Char('t'), KeyModifiers::CONTROL);
+    for text in ["ctrl-t", "Ctrl+T", "C-t", "ctrl-T"] {
+        assert_eq!(KeyChord::parse(text), Ok(ctrl_t), "{}", text);
+    }
+    assert_eq!(KeyChord::parse("shift-tab").unwrap().code, KeyCode::BackTab);
+    assert_eq!(KeyChord::parse("F2").unwrap().code, KeyCode::F(2));
+    assert_eq!(
+        KeyChord::parse("alt-x"),
+        Ok(KeyChord::new(KeyCode::Char('x'), KeyModifiers::ALT))
+    );
+    assert_eq!(KeyChord::parse("shift-y"), KeyChord::parse("Y"));
+    assert_eq!(KeyChord::parse("ctrl--").unwrap().code, KeyCode::Char('-'));
+    assert!(KeyChord::parse("ctrl-nope").is_err());
+}
+
+#[test]
+fn display_matches_the_help_bar() {
+    assert_eq!(KeyChord::parse("ctrl-d").unwrap().to_string(), "Ctrl-D");
+    assert_eq!(KeyChord::parse("pageup").unwrap().to_string(), "PgUp");
+    assert_eq!(KeyChord::parse("shift-up").unwrap().to_string(), "Shift-↑");
+    assert_eq!(KeyChord::parse("space").unwrap().to_string(), "Space");
+}
+
+#[test]
+fn default_keys() {
+    let keymap = Keymap::default();
+    assert_eq!(
+        keymap.action(AppMode::Normal, &ctrl('d')),
+        Some(Action::Delete)
+    );
+    assert_eq!(
+        keymap.action(
+            AppMode::Normal,
+            &key(KeyCode::Char('j'), KeyModifiers::NONE)
+        ),
+        None
+    );
+    // Shift is reported with upper case letters
+    assert_eq!(
+        keymap.action(
+            AppMode::BatchConfirm,
+            &key(KeyCode::Char('Y'), KeyModifiers::SHIFT)
+        ),
+        Some(Action::Confirm)
+    );
+    assert_eq!(
+        keymap.action(AppMode::Normal, &key(KeyCode::BackTab, KeyModifiers::SHIFT)),
+        Some(Action::Unmark)
+    );
+    assert_eq!(
+        keymap.action(AppMode::Trash, &key(KeyCode::Char('x'), KeyModifiers::NONE)),
+        Some(Action::Delete)
+    );
+}
+
+#[test]
+fn configured_keys_replace_defaults() {
+    let keymap = Keymap::from_config(&config(
+        "normal",
+        &[("ctrl-b", "theme"), ("ctrl-t", "none"), ("ctrl-a", "none")],
+    ))
+    .unwrap();
+    assert_eq!(
+        keymap.action(AppMode::Normal, &ctrl('b')),
+        Some(Action::Theme)
+    );
+    assert_eq!(keymap.action(AppMode::Normal, &ctrl('t')), None);
+    assert_eq!(keymap.action(AppMode::Normal, &ctrl('a')), None);
+    // Other modes keep their keys
+    assert_eq!(
+        keymap.action(AppMode::ThemeSelect, &key(KeyCode::Esc, KeyModifiers::NONE)),
+        Some(Action::Cancel)
+    );
+}
+
+#[test]
+fn help_reflects_the_keymap() {
+    let help = Keymap::default().help(AppMode::Normal);
+    assert_eq!(help[0], ("↑↓".to_string(), "Nav"));
+    assert!(help.contains(&("Ctrl-T".to_string(), "Theme")));
+    assert_eq!(help.last().unwrap(), &("Esc/Ctrl-C".to_string(), "Quit"));
+
+    let keymap =
+        Keymap::from_config(&config("normal", &[("alt-t", "theme"), ("ctrl-a", "none")])).unwrap();
+    let help = keymap.help(AppMode::Normal);
+    assert!(help.contains(&("Alt-t".to_string(), "Theme")));
+    assert!(!help.iter().any(|(_, label)| *label == "About"));
+}
+
+#[test]
+fn same_key_twice_is_a_conflict() {
+    let err = Keymap::from_config(&config("normal", &[("ctrl-b", "theme"), ("C-b", "about")]))
+        .unwrap_err();
+    assert!(err.contains("are the same key"), "{}", err);
+}
+
+#[test]
+fn plain_characters_are_kept_for_typing() {
+    let err = Keymap::from_config(&config("normal", &[("q", "quit")])).unwrap_err();
+    assert!(err.contains("'q' is needed to type text"), "{}", err);
+    // Popups without a text field may use them
+    assert!(Keymap::from_config(&config("theme_select", &[("q", "cancel")])).is_ok());
+}
+
+#[test]
+fn unknown_names_are_reported_together() {
+    let mut bindings = config("normal", &[("ctrl-b", "launch"), ("ctrl-q", "force")]);
+    bindings.extend(config("nowhere", &[("x", "up")]));
+    let err = Keymap::from_config(&bindings).unwrap_err();
+    assert!(err.contains("unknown action 'launch'"), "{}", err);
+    assert!(err.contains("unknown action 'force'"), "{}", err);
+    assert!(err.contains("unknown mode 'nowhere'"), "{}", err);
+    assert_eq!(err.lines().count(), 3);
+}
+
+#[test]
+fn every_mode_keeps_a_way_out() {
+    let err = Keymap::from_config(&config(
+        "about",
+        &[
+            ("esc", "none"),
+            ("enter", "none"),
+            ("q", "none"),
+            ("space", "none"),
+            ("ctrl-c", "none"),
+        ],
+    ))
+    .unwrap_err();
+    assert!(err.contains("no key is left"), "{}", err);
+}
+
+#[test]
+fn navigate_falls_back_to_normal_shortcuts() {
+    let keymap = Keymap::default();
+    let plain = |c| key(KeyCode::Char(c), KeyModifiers::NONE);
+    assert_eq!(
+        keymap.action(AppMode::Navigate, &plain('j')),
+        Some(Action::Down)
+    );
+    assert_eq!(
+        keymap.action(AppMode::Navigate, &plain('/')),
+        Some(Action::Search)
+    );
+    assert_eq!(
+        keymap.action(
+            AppMode::Navigate,
+            &key(KeyCode::Char('G'), KeyModifiers::SHIFT)
+        ),
+        Some(Action::Last)
+    );
+    assert_eq!(
+        keymap.action(AppMode::Navigate, &ctrl('d')),
+        Some(Action::Delete)
+    );
+    // Letters only type text in the search box
+    assert_eq!(keymap.action(AppMode::Normal, &plain('j')), None);
+
+    let help = keymap.help(AppMode::Navigate);
+    assert_eq!(help[0], ("k/j".to_string(), "Nav"));
+    assert!(help.contains(&("/".to_string(), "Search")));
+    assert!(help.contains(&("Ctrl-D".to_string(), "Del")));
+    assert!(
+        !keymap
+            .help(AppMode::Normal)
+            .iter()
+            .any(|(_, l)| *l == "Search")
+    );
+}
+
+#[test]
+fn vim_counts() {
+    let mut pending = VimPending::default();
+    assert!(!pending.push_digit('0'), "0 does not start a count");
+    assert!(pending.push_digit('1'));
+    assert!(pending.push_digit('2'));
+    assert!(!pending.push_digit('x'));
+    pending.operator = Some('d');
+    assert!(!pending.push_digit('3'), "nothing follows the operator");
+    assert_eq!(pending.to_string(), "12d");
+    assert_eq!(pending.take_count(), 12);
+    assert!(pending.is_empty());
+    assert_eq!(pending.take_count(), 1);
+}
@@ -0,0 +1,175 @@
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use try_rs::cli::{ListArgs, ListFormat, SortKey};
+use try_rs::entries::{SearchQuery, TryEntry};
+use try_rs::list::*;
+use try_rs::metadata::TryMetadata;
+
+fn entry(name: &str, age_days: u64, project_types: &[&str]) -> TryEntry {
+    let time = SystemTime::now() - Duration::from_secs(age_days * 86400);
+    TryEntry {
+        name: name.to_string(),
+        display_name: name.to_string(),
+        path: Path::new("/tries").join(name),
+        root: None,
+        modified: time,
+        created: time,
+        score: 0,
+        is_git: false,
+        is_worktree: false,
+        is_worktree_locked: false,
+        is_gitmodules: false,
+        project_types: project_types.iter().map(|t| t.to_string()).collect(),
+        metadata: Default::default(),
+    }
+}
+
+fn names(entries: &[TryEntry]) -> Vec<&str> {
+    entries.iter().map(|e| e.name.as_str()).collect()
+}
+
+fn sample() -> Vec<TryEntry> {
+    vec![
+        entry("beta", 1, &["Rust"]),
+        entry("alpha", 10, &["Go"]),
+        entry("gamma", 40, &["Rust", "Node"]),
+    ]
+}
+
+#[test]
+fn default_sort_is_most_recent_first() {
+    let result = filter_and_sort(sample(), &ListArgs::default());
+    assert_eq!(names(&result), vec!["beta", "alpha", "gamma"]);
+}
+
+#[test]
+fn sort_by_name_reversed() {
+    let args = ListArgs {
+        sort: SortKey::Name,
+        reverse: true,
+        ..Default::default()
+    };
+    let result = filter_and_sort(sample(), &args);
+    assert_eq!(names(&result), vec!["gamma", "beta", "alpha"]);
+}
+
+#[test]
+fn filter_by_project_type_is_case_insensitive() {
+    let args = ListArgs {
+        project_types: vec!["rust".to_string()],
+        ..Default::default()
+    };
+    let result = filter_and_sort(sample(), &args);
+    assert_eq!(names(&result), vec!["beta", "gamma"]);
+}
+
+#[test]
+fn filter_by_age() {
+    let older = ListArgs {
+        older_than: Some(5),
+        ..Default::default()
+    };
+    assert_eq!(
+        names(&filter_and_sort(sample(), &older)),
+        vec!["alpha", "gamma"]
+    );
+
+    let newer = ListArgs {
+        newer_than: Some(5),
+        ..Default::default()
+    };
+    assert_eq!(names(&filter_and_sort(sample(), &newer)), vec!["beta"]);
+}
+
+#[test]
+fn filter_by_query() {
+    let args = ListArgs {
+        query: Some("gma".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(names(&filter_and_sort(sample(), &args)), vec!["gamma"]);
+}
+
+#[test]
+fn json_output_contains_all_fields() {
+    let output = format_entries(&sample(), ListFormat::Json);
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let items = parsed.as_array().unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0]["name"], "beta");
+    assert_eq!(items[0]["path"], "/tries/beta");
+    assert_eq!(items[2]["project_types"][1], "Node");
+    assert!(items[0]["created"].is_string());
+    assert!(items[0]["modified"].is_string());
+}
+
+#[test]
+fn tsv_output_has_one_line_per_entry() {
+    let output = format_entries(&sample(), ListFormat::Tsv);
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 3);
+    let columns: Vec<&str> = lines[2].split('\t').collect();
+    assert_eq!(columns.len(), 5);
+    assert_eq!(columns[0], "gamma");
+    assert_eq!(columns[4], "Rust,Node");
+}
+
+#[test]
+fn tsv_output_escapes_tabs_and_newlines() {
+    let output = format_entries(&[entry("a\tb\nc\\d", 1, &["Rust"])], ListFormat::Tsv);
+    assert_eq!(output.lines().count(), 1);
+    let columns: Vec<&str> = output.trim_end().split('\t').collect();
+    assert_eq!(columns.len(), 5);
+    assert_eq!(columns[0], "a\\tb\\nc\\\\d");
+}
+
+#[test]
+fn table_output_has_header() {
+    let output = format_entries(&sample(), ListFormat::Table);
+    assert!(output.lines().next().unwrap().starts_with("CREATED"));
+    assert_eq!(output.lines().count(), 4);
+}
+
+#[test]
+fn search_query_splits_tags_and_text() {
+    assert_eq!(
+        SearchQuery::parse("tag:rust parser tag:wip"),
+        SearchQuery {
+            tags: vec!["rust".to_string(), "wip".to_string()],
+            text: "parser".to_string(),
+        }
+    );
+}
+
+#[test]
+fn filter_by_tag_and_description() {
+    let mut entries = sample();
+    entries[1].metadata = TryMetadata {
+        description: Some("websocket server prototype".to_string()),
+        tags: vec!["networking".to_string()],
+        source: None,
+    };
+
+    let by_tag = ListArgs {
+        query: Some("tag:net".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        names(&filter_and_sort(sample(), &by_tag)),
+        Vec::<&str>::new()
+    );
+    assert_eq!(
+        names(&filter_and_sort(entries.clone(), &by_tag)),
+        vec!["alpha"]
+    );
+
+    let by_description = ListArgs {
+        query: Some("websocket".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        names(&filter_and_sort(entries, &by_description)),
+        vec!["alpha"]
+    );
+}
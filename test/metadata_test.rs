@@ -0,0 +1,54 @@
+use std::fs;
+
+use tempdir::TempDir;
+use try_rs::metadata::*;
+
+#[test]
+fn parse_tags_splits_and_dedups() {
+    assert_eq!(
+        parse_tags("rust, wasm #demo rust"),
+        vec!["rust", "wasm", "demo"]
+    );
+    assert!(parse_tags(" , ").is_empty());
+}
+
+#[test]
+fn has_tag_matches_prefix_case_insensitively() {
+    let metadata = TryMetadata {
+        tags: vec!["Networking".to_string()],
+        ..Default::default()
+    };
+    assert!(metadata.has_tag("net"));
+    assert!(!metadata.has_tag("web"));
+}
+
+#[test]
+fn save_load_and_clear() {
+    let tmp = TempDir::new("metadata").unwrap();
+    let metadata = TryMetadata {
+        description: Some("parser experiments".to_string()),
+        tags: vec!["rust".to_string()],
+        source: Some("https://github.com/user/repo.git".to_string()),
+    };
+    metadata.save(tmp.path()).unwrap();
+    assert_eq!(TryMetadata::load(tmp.path()), metadata);
+
+    TryMetadata::default().save(tmp.path()).unwrap();
+    assert!(!tmp.path().join(METADATA_FILE_NAME).exists());
+    assert!(TryMetadata::load(tmp.path()).is_empty());
+}
+
+#[test]
+fn save_excludes_file_from_git() {
+    let tmp = TempDir::new("metadata").unwrap();
+    fs::create_dir_all(tmp.path().join(".git").join("info")).unwrap();
+    let metadata = TryMetadata {
+        description: Some("clone".to_string()),
+        ..Default::default()
+    };
+    metadata.save(tmp.path()).unwrap();
+    metadata.save(tmp.path()).unwrap();
+
+    let exclude = fs::read_to_string(tmp.path().join(".git/info/exclude")).unwrap();
+    assert_eq!(exclude.matches(METADATA_FILE_NAME).count(), 1);
+}
@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use tempdir::TempDir;
+use try_rs::pins::*;
+
+#[test]
+fn toggle_pins_and_unpins() {
+    let mut pins = Pins::default();
+    let folder = Path::new("/tries/playground");
+    assert!(!pins.is_pinned(folder));
+    assert!(pins.toggle(folder));
+    assert!(pins.is_pinned(folder));
+    assert!(!pins.toggle(folder));
+    assert!(!pins.is_pinned(folder));
+}
+
+#[test]
+fn rename_keeps_pin() {
+    let mut pins = Pins::default();
+    pins.toggle(Path::new("/tries/old"));
+    pins.rename(Path::new("/tries/old"), Path::new("/other/new"));
+    assert!(!pins.is_pinned(Path::new("/tries/old")));
+    assert!(pins.is_pinned(Path::new("/other/new")));
+
+    pins.rename(Path::new("/tries/unpinned"), Path::new("/tries/x"));
+    assert!(!pins.is_pinned(Path::new("/tries/x")));
+}
+
+#[test]
+fn save_and_load() {
+    let tmp = TempDir::new("pins").unwrap();
+    let path = tmp.path().join("data").join("pins.toml");
+    let mut pins = Pins::default();
+    pins.toggle(Path::new("/tries/a"));
+    pins.save(&path).unwrap();
+
+    let loaded = Pins::load(&path);
+    assert!(loaded.is_pinned(Path::new("/tries/a")));
+    assert!(
+        Pins::load(&tmp.path().join("missing.toml"))
+            .pinned
+            .is_empty()
+    );
+}
@@ -0,0 +1,98 @@
+use std::fs;
+
+use ratatui::style::Color;
+use tempdir::TempDir;
+use try_rs::preview::*;
+use try_rs::themes::Theme;
+
+fn line_text(line: &ratatui::text::Line) -> String {
+    line.spans.iter().map(|s| s.content.as_ref()).collect()
+}
+
+#[test]
+fn tree_lists_directories_first_and_honours_gitignore() {
+    let tmp = TempDir::new("preview-tree").unwrap();
+    let dir = tmp.path();
+    fs::create_dir_all(dir.join("src").join("deep").join("deeper")).unwrap();
+    fs::create_dir_all(dir.join("target")).unwrap();
+    fs::create_dir_all(dir.join(".git")).unwrap();
+    fs::write(dir.join(".gitignore"), "target/\n").unwrap();
+    fs::write(dir.join("Cargo.toml"), "[package]\n").unwrap();
+    fs::write(dir.join("src").join("main.rs"), "fn main() {}\n").unwrap();
+
+    let entries = tree_entries(dir, 2);
+    let names: Vec<(usize, &str)> = entries.iter().map(|e| (e.depth, e.name.as_str())).collect();
+    assert_eq!(
+        names,
+        vec![
+            (1, "src"),
+            (2, "deep"),
+            (2, "main.rs"),
+            (1, ".gitignore"),
+            (1, "Cargo.toml"),
+        ]
+    );
+
+    let lines: Vec<String> = tree_lines(&entries, &Theme::default())
+        .iter()
+        .map(line_text)
+        .collect();
+    assert!(lines[0].starts_with("├─ "));
+    assert!(lines[1].starts_with("│  ├─ "));
+    assert!(lines[2].starts_with("│  └─ "));
+    assert!(lines[4].starts_with("└─ "));
+}
+
+#[test]
+fn preview_file_prefers_readme() {
+    let tmp = TempDir::new("preview-file").unwrap();
+    let dir = tmp.path();
+    assert_eq!(preview_file(dir), None);
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    assert_eq!(preview_file(dir), Some(dir.join("package.json")));
+    fs::write(dir.join("README.md"), "# Hi").unwrap();
+    assert_eq!(preview_file(dir), Some(dir.join("README.md")));
+}
+
+#[test]
+fn highlight_manifest() {
+    let tmp = TempDir::new("preview-highlight").unwrap();
+    let path = tmp.path().join("Cargo.toml");
+    fs::write(&path, "[package]\nname = \"demo\"\n\tversion = \"0.1.0\"\n").unwrap();
+
+    let lines = highlight_file(&path, false);
+    assert_eq!(lines.len(), 3);
+    assert_eq!(line_text(&lines[1]), "name = \"demo\"");
+    assert_eq!(line_text(&lines[2]), "    version = \"0.1.0\"");
+    // The key and the string get different colors
+    let colors: Vec<Option<Color>> = lines[1].spans.iter().map(|s| s.style.fg).collect();
+    assert!(colors.windows(2).any(|w| w[0] != w[1]));
+
+    // Light themes get darker syntax colors
+    let light = highlight_file(&path, true);
+    assert_eq!(line_text(&light[1]), "name = \"demo\"");
+    assert_ne!(light[1].spans[0].style.fg, lines[1].spans[0].style.fg);
+}
+
+#[test]
+fn highlight_tolerates_invalid_utf8() {
+    let tmp = TempDir::new("preview-utf8").unwrap();
+    let long = tmp.path().join("long.txt");
+    // The read limit cuts the last character in half
+    let mut text = "a".to_string();
+    text.push_str(&"é".repeat(200 * 1024));
+    fs::write(&long, text).unwrap();
+    let lines = highlight_file(&long, false);
+    assert!(line_text(&lines[0]).starts_with("aéé"));
+
+    let latin1 = tmp.path().join("notes.txt");
+    fs::write(&latin1, b"caf\xe9\n").unwrap();
+    assert_eq!(line_text(&highlight_file(&latin1, false)[0]), "caf\u{fffd}");
+
+    let binary = tmp.path().join("app.bin");
+    fs::write(&binary, b"ELF\0\x01").unwrap();
+    assert_eq!(
+        line_text(&highlight_file(&binary, false)[0]),
+        "(binary file)"
+    );
+}
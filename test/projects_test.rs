@@ -0,0 +1,51 @@
+use std::fs;
+
+use tempdir::TempDir;
+use try_rs::projects::*;
+
+#[test]
+fn all_detectors_have_unique_names() {
+    let detectors = ProjectDetector::all();
+    let mut names: Vec<&str> = detectors.iter().map(|d| d.name.as_str()).collect();
+    let total = names.len();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), total, "detector names must be unique");
+}
+
+#[test]
+fn all_detectors_have_markers_and_icons() {
+    for detector in ProjectDetector::all() {
+        assert!(
+            !detector.markers.is_empty(),
+            "{} has no markers",
+            detector.name
+        );
+        assert!(!detector.icon.is_empty(), "{} has no icon", detector.name);
+    }
+}
+
+#[test]
+fn detects_builtin_project_types() {
+    let tmp = TempDir::new("detect-types").unwrap();
+    fs::write(tmp.path().join("Cargo.toml"), "").unwrap();
+    fs::write(tmp.path().join("package.json"), "{}").unwrap();
+
+    let types = detect_project_types(tmp.path(), &ProjectDetector::all());
+    assert_eq!(types, vec!["Rust".to_string(), "Node".to_string()]);
+}
+
+#[test]
+fn detects_glob_markers() {
+    let tmp = TempDir::new("detect-glob").unwrap();
+    fs::write(tmp.path().join("App.csproj"), "").unwrap();
+
+    let types = detect_project_types(tmp.path(), &ProjectDetector::all());
+    assert_eq!(types, vec![".NET".to_string()]);
+}
+
+#[test]
+fn empty_folder_has_no_types() {
+    let tmp = TempDir::new("detect-empty").unwrap();
+    assert!(detect_project_types(tmp.path(), &ProjectDetector::all()).is_empty());
+}
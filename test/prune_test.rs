@@ -0,0 +1,68 @@
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use try_rs::entries::TryEntry;
+use try_rs::prune::*;
+
+const DAY: u64 = 86400;
+
+fn entry(name: &str, modified_days_ago: u64) -> TryEntry {
+    let modified = SystemTime::now() - Duration::from_secs(modified_days_ago * DAY);
+    TryEntry {
+        name: name.to_string(),
+        display_name: name.to_string(),
+        path: Path::new("/tries").join(name),
+        root: None,
+        modified,
+        created: modified,
+        score: 0,
+        is_git: false,
+        is_worktree: false,
+        is_worktree_locked: false,
+        is_gitmodules: false,
+        project_types: vec![],
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn stale_entries_are_sorted_oldest_first() {
+    let entries = vec![entry("new", 1), entry("old", 100), entry("older", 200)];
+    let stale = stale_entries(entries, Duration::from_secs(30 * DAY), SystemTime::now());
+    let names: Vec<&str> = stale.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["older", "old"]);
+}
+
+#[test]
+fn recent_date_prefix_keeps_entry_alive() {
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let e = entry(&format!("{today} fresh"), 100);
+    assert!(last_activity(&e) > e.modified);
+    assert!(stale_entries(vec![e], Duration::from_secs(30 * DAY), SystemTime::now()).is_empty());
+}
+
+#[test]
+fn recent_modification_beats_old_date_prefix() {
+    let e = entry("2020-01-01 still-used", 2);
+    assert_eq!(last_activity(&e), e.modified);
+}
+
+#[test]
+fn confirmation_yes_removes_all() {
+    assert_eq!(parse_confirmation("y\n", 3), Some(vec![0, 1, 2]));
+    assert_eq!(parse_confirmation("YES", 2), Some(vec![0, 1]));
+}
+
+#[test]
+fn confirmation_numbers_are_kept() {
+    assert_eq!(parse_confirmation("1 3", 4), Some(vec![1, 3]));
+    assert_eq!(parse_confirmation("2,4", 4), Some(vec![0, 2]));
+}
+
+#[test]
+fn confirmation_aborts_on_anything_else() {
+    assert_eq!(parse_confirmation("", 3), None);
+    assert_eq!(parse_confirmation("n", 3), None);
+    assert_eq!(parse_confirmation("5", 3), None);
+    assert_eq!(parse_confirmation("0", 3), None);
+}
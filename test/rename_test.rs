@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use tempdir::TempDir;
+use try_rs::rename::*;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn renamed_folder_name_keeps_or_updates_the_date() {
+    let today = "2026-10-17";
+    assert_eq!(
+        renamed_folder_name("2025-01-02 parser", "lexer", false, today),
+        "2025-01-02 lexer"
+    );
+    assert_eq!(
+        renamed_folder_name("2025-01-02 parser", "lexer", true, today),
+        "2026-10-17 lexer"
+    );
+    assert_eq!(
+        renamed_folder_name("parser", "lexer", false, today),
+        "lexer"
+    );
+    assert_eq!(
+        renamed_folder_name("parser", "lexer", true, today),
+        "2026-10-17 lexer"
+    );
+    assert_eq!(
+        renamed_folder_name("2025-01-02 parser", "2024-05-06 lexer", false, today),
+        "2024-05-06 lexer"
+    );
+}
+
+#[test]
+fn rename_folder() {
+    let tmp = TempDir::new("rename-folder").unwrap();
+    let tries = tmp.path();
+    fs::create_dir(tries.join("old")).unwrap();
+    fs::create_dir(tries.join("taken")).unwrap();
+
+    assert!(rename_experiment(tries, "old", "taken").is_err());
+    assert!(rename_experiment(tries, "old", "../escape").is_err());
+    assert!(rename_experiment(tries, "old", ".hidden").is_err());
+
+    let to = rename_experiment(tries, "old", "new").unwrap();
+    assert_eq!(to, tries.join("new"));
+    assert!(to.is_dir());
+    assert!(!tries.join("old").exists());
+}
+
+#[test]
+fn rename_worktree_keeps_the_link() {
+    let tmp = TempDir::new("rename-worktree").unwrap();
+    let repo = tmp.path().join("repo");
+    let tries = tmp.path().join("tries");
+    fs::create_dir_all(&repo).unwrap();
+    fs::create_dir_all(&tries).unwrap();
+    git(&repo, &["init", "-q"]);
+    git(&repo, &["config", "user.email", "test@test.internal"]);
+    git(&repo, &["config", "user.name", "Test"]);
+    fs::write(repo.join("file.txt"), "v1").unwrap();
+    git(&repo, &["add", "."]);
+    git(&repo, &["commit", "-q", "-m", "init"]);
+    let worktree = tries.join("feature");
+    git(
+        &repo,
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "feature",
+            &worktree.to_string_lossy(),
+        ],
+    );
+
+    let to = rename_experiment(&tries, "feature", "feature-renamed").unwrap();
+    assert!(git(&repo, &["worktree", "list"]).contains("feature-renamed"));
+    assert!(git(&to, &["status", "--porcelain"]).is_empty());
+}
@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use tempdir::TempDir;
+use try_rs::roots::*;
+
+fn configured(roots: &[(&str, &str)]) -> BTreeMap<String, RootConfig> {
+    roots
+        .iter()
+        .map(|(name, path)| {
+            (
+                name.to_string(),
+                RootConfig {
+                    path: path.to_string(),
+                },
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn tries_path_is_the_default_root() {
+    let roots = tries_roots(
+        Some(PathBuf::from("/tries")),
+        PathBuf::from("/home/tries"),
+        &BTreeMap::new(),
+    );
+    assert_eq!(
+        roots,
+        vec![TriesRoot {
+            name: DEFAULT_ROOT.to_string(),
+            path: PathBuf::from("/tries"),
+        }]
+    );
+}
+
+#[test]
+fn default_path_is_used_without_roots() {
+    let roots = tries_roots(None, PathBuf::from("/home/tries"), &BTreeMap::new());
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].path, PathBuf::from("/home/tries"));
+}
+
+#[test]
+fn configured_roots_follow_the_default_by_name() {
+    let roots = tries_roots(
+        Some(PathBuf::from("/tries")),
+        PathBuf::from("/home/tries"),
+        &configured(&[("work", "/work/tries"), ("oss", "/oss/tries")]),
+    );
+    let names: Vec<&str> = roots.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, vec!["default", "oss", "work"]);
+    assert_eq!(roots[2].path, PathBuf::from("/work/tries"));
+}
+
+#[test]
+fn configured_roots_replace_the_default_path() {
+    let roots = tries_roots(
+        None,
+        PathBuf::from("/home/tries"),
+        &configured(&[("work", "/work/tries")]),
+    );
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].name, "work");
+}
+
+#[test]
+fn duplicate_roots_are_skipped() {
+    let roots = tries_roots(
+        Some(PathBuf::from("/tries")),
+        PathBuf::from("/home/tries"),
+        &configured(&[("again", "/tries"), ("default", "/other")]),
+    );
+    assert_eq!(roots.len(), 1);
+}
+
+#[test]
+fn find_root_lists_available_names() {
+    let roots = tries_roots(
+        Some(PathBuf::from("/tries")),
+        PathBuf::from("/home/tries"),
+        &configured(&[("work", "/work/tries")]),
+    );
+    assert_eq!(find_root(&roots, "work"), Ok(1));
+    assert_eq!(
+        find_root(&roots, "play").unwrap_err(),
+        "Unknown root 'play' (available: default, work)"
+    );
+}
+
+#[test]
+fn matching_folders_in_every_root() {
+    let tmp = TempDir::new("roots-test").unwrap();
+    let home = tmp.path().join("home");
+    let work = tmp.path().join("work");
+    fs::create_dir_all(home.join("2024-01-01 demo")).unwrap();
+    fs::create_dir_all(work.join("demo")).unwrap();
+    fs::create_dir_all(work.join("other")).unwrap();
+
+    let roots = vec![
+        TriesRoot {
+            name: "home".to_string(),
+            path: home.clone(),
+        },
+        TriesRoot {
+            name: "work".to_string(),
+            path: work.clone(),
+        },
+    ];
+    let found = matching_folders_in_roots("demo", &roots);
+    assert_eq!(found, vec![home.join("2024-01-01 demo"), work.join("demo")]);
+}
@@ -0,0 +1,96 @@
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+use tempdir::TempDir;
+use try_rs::sizes::*;
+
+#[test]
+fn artifacts_are_counted_separately() {
+    let tmp = TempDir::new("sizes-artifacts").unwrap();
+    let dir = tmp.path();
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::create_dir_all(dir.join("target").join("debug")).unwrap();
+    fs::write(dir.join("src").join("main.rs"), vec![b'a'; 100]).unwrap();
+    fs::write(dir.join("target").join("debug").join("app"), vec![0; 1000]).unwrap();
+    fs::write(dir.join("target.txt"), vec![b'b'; 10]).unwrap();
+
+    let size = folder_size(dir, &["target".to_string()]);
+    assert_eq!(
+        size,
+        FolderSize {
+            total: 1110,
+            artifacts: 1000
+        }
+    );
+
+    let size = folder_size(dir, &[]);
+    assert_eq!(
+        size,
+        FolderSize {
+            total: 1110,
+            artifacts: 0
+        }
+    );
+}
+
+#[test]
+fn cache_is_saved_with_mtime() {
+    let tmp = TempDir::new("sizes-cache").unwrap();
+    let folder = tmp.path().join("2025-01-01 exp");
+    fs::create_dir_all(&folder).unwrap();
+    let cache_path = tmp.path().join("data").join("sizes.toml");
+    let size = FolderSize {
+        total: 2048,
+        artifacts: 1024,
+    };
+
+    let mut cache = SizeCache::default();
+    cache.insert(&folder, 100, size);
+    cache.insert(&tmp.path().join("gone"), 100, size);
+    cache.save(&cache_path).unwrap();
+
+    let cache = SizeCache::load(&cache_path);
+    assert_eq!(cache.get(&folder), Some(CachedSize { mtime: 100, size }));
+    assert_eq!(
+        cache.folders.len(),
+        1,
+        "missing folders are dropped on save"
+    );
+}
+
+#[test]
+fn contents_mtime_follows_artifact_directories() {
+    let tmp = TempDir::new("sizes-mtime").unwrap();
+    let dir = tmp.path();
+    let debug = dir.join("target").join("debug");
+    fs::create_dir_all(&debug).unwrap();
+    fs::create_dir_all(dir.join("src")).unwrap();
+    let old = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+    for path in [dir, &dir.join("target"), &debug, &dir.join("src")] {
+        fs::File::open(path).unwrap().set_modified(old).unwrap();
+    }
+    let artifacts = ["target".to_string()];
+    assert_eq!(contents_mtime(dir, &artifacts), 1_000_000);
+
+    // A build writes below target/ without touching the folder itself
+    let newer = old + Duration::from_secs(60);
+    fs::File::open(&debug).unwrap().set_modified(newer).unwrap();
+    assert_eq!(contents_mtime(dir, &artifacts), 1_000_060);
+    assert_eq!(contents_mtime(dir, &[]), 1_000_000);
+
+    // Sources are not artifacts
+    fs::File::open(dir.join("src"))
+        .unwrap()
+        .set_modified(newer)
+        .unwrap();
+    fs::File::open(&debug).unwrap().set_modified(old).unwrap();
+    assert_eq!(contents_mtime(dir, &artifacts), 1_000_000);
+}
+
+#[test]
+fn sizes_are_formatted_compactly() {
+    assert_eq!(format_short(512 * 1024), "512K");
+    assert_eq!(format_short(340 * 1024 * 1024), "340M");
+    assert_eq!(format_short(1229 * 1024 * 1024), "1.2G");
+    assert_eq!(format_long(340 * 1024 * 1024), "340 MB");
+}
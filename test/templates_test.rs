@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+use tempdir::TempDir;
+use try_rs::templates::*;
+
+#[test]
+fn render_replaces_known_variables_only() {
+    let vars = BTreeMap::from([("name".to_string(), "demo".to_string())]);
+    assert_eq!(
+        render("# {{name}} by {{author}}", &vars),
+        "# demo by {{author}}"
+    );
+}
+
+#[test]
+fn loads_toml_and_directory_templates() {
+    let tmp = TempDir::new("templates-load").unwrap();
+    let dir = tmp.path();
+    fs::write(
+        dir.join("rust-bin.toml"),
+        "description = \"Rust binary\"\ncommands = [\"cargo init\"]\n",
+    )
+    .unwrap();
+    fs::create_dir_all(dir.join("web")).unwrap();
+    fs::write(dir.join("web").join("index.html"), "<h1>{{name}}</h1>").unwrap();
+    fs::write(dir.join("broken.toml"), "commands = 3").unwrap();
+    fs::write(dir.join("README.md"), "not a template").unwrap();
+
+    let templates = load_templates(dir);
+    let names: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["rust-bin", "web"]);
+    assert_eq!(
+        templates[0].manifest.description.as_deref(),
+        Some("Rust binary")
+    );
+    assert!(templates[1].dir.is_some());
+
+    assert!(find_template(dir, "broken").is_err());
+    let err = find_template(dir, "missing").unwrap_err().to_string();
+    assert!(err.contains("rust-bin, web"));
+}
+
+#[test]
+fn apply_writes_files_with_variables() {
+    let tmp = TempDir::new("templates-apply").unwrap();
+    let dir = tmp.path().join("templates");
+    fs::create_dir_all(dir.join("lib").join("src")).unwrap();
+    fs::write(
+        dir.join("lib").join(MANIFEST_FILE_NAME),
+        r#"
+description = "Library"
+
+[variables]
+author = "nobody"
+crate = "{{name}}-lib"
+
+[[files]]
+path = "{{crate}}.md"
+content = "{{crate}} by {{author}}"
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("lib").join("src").join("lib.rs"),
+        "// {{name}} ({{date}})",
+    )
+    .unwrap();
+
+    let template = find_template(&dir, "lib")
+        .unwrap()
+        .with_vars(&["author=Ada".to_string()])
+        .unwrap();
+    let target = tmp.path().join("2025-01-01 parser");
+    fs::create_dir_all(&target).unwrap();
+    template.apply(&target).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(target.join("parser-lib.md")).unwrap(),
+        "parser-lib by Ada"
+    );
+    let lib = fs::read_to_string(target.join("src").join("lib.rs")).unwrap();
+    assert!(lib.starts_with("// parser ("));
+    assert!(!lib.contains("{{"));
+    assert!(!target.join(MANIFEST_FILE_NAME).exists());
+}
+
+#[test]
+fn with_vars_rejects_malformed_pairs() {
+    let tmp = TempDir::new("templates-vars").unwrap();
+    fs::write(tmp.path().join("t.toml"), "").unwrap();
+    let template = find_template(tmp.path(), "t").unwrap();
+    assert!(template.with_vars(&["novalue".to_string()]).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn apply_runs_commands_in_new_folder() {
+    let tmp = TempDir::new("templates-commands").unwrap();
+    fs::write(
+        tmp.path().join("init.toml"),
+        "commands = [\"echo {{name}} > created.txt\", \"false\"]\n",
+    )
+    .unwrap();
+    let target = tmp.path().join("demo");
+    fs::create_dir_all(&target).unwrap();
+
+    find_template(tmp.path(), "init")
+        .unwrap()
+        .apply(&target)
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(target.join("created.txt"))
+            .unwrap()
+            .trim(),
+        "demo"
+    );
+}
@@ -0,0 +1,53 @@
+use try_rs::terminal_background::*;
+
+fn from_env(vars: &[(&str, &str)]) -> Option<Background> {
+    background_from_env(&|name| {
+        vars.iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| v.to_string())
+    })
+}
+
+#[test]
+fn explicit_hint_wins() {
+    assert_eq!(
+        from_env(&[("TRY_BACKGROUND", "Light"), ("COLORFGBG", "15;0")]),
+        Some(Background::Light)
+    );
+    assert_eq!(
+        from_env(&[("TRY_BACKGROUND", "dark")]),
+        Some(Background::Dark)
+    );
+}
+
+#[test]
+fn colorfgbg_background_index() {
+    assert_eq!(from_env(&[("COLORFGBG", "15;0")]), Some(Background::Dark));
+    assert_eq!(from_env(&[("COLORFGBG", "0;15")]), Some(Background::Light));
+    assert_eq!(
+        from_env(&[("COLORFGBG", "0;default;7")]),
+        Some(Background::Light)
+    );
+    assert_eq!(from_env(&[("COLORFGBG", "7;8")]), Some(Background::Dark));
+    assert_eq!(from_env(&[("COLORFGBG", "default")]), None);
+    assert_eq!(from_env(&[("TRY_BACKGROUND", "auto")]), None);
+}
+
+#[test]
+fn parses_osc11_answers() {
+    assert_eq!(
+        parse_osc11_response(b"\x1b]11;rgb:ffff/ffff/ffff\x1b\\\x1b[?62;22c"),
+        Some((255, 255, 255))
+    );
+    assert_eq!(
+        parse_osc11_response(b"\x1b]11;rgb:1e1e/1e1e/2e2e\x07"),
+        Some((30, 30, 46))
+    );
+    assert_eq!(
+        parse_osc11_response(b"\x1b]11;rgb:fd/f6/e3\x1b\\"),
+        Some((253, 246, 227))
+    );
+    // Terminals without OSC 11 only answer the device attributes query
+    assert_eq!(parse_osc11_response(b"\x1b[?1;2c"), None);
+    assert_eq!(parse_osc11_response(b"\x1b]11;rgb:ff/ff\x07"), None);
+}
@@ -0,0 +1,214 @@
+use std::fs;
+use std::path::Path;
+
+use ratatui::style::Color;
+use tempdir::TempDir;
+use try_rs::cli::ThemeImportArgs;
+use try_rs::theme_import::*;
+use try_rs::themes::Theme;
+use try_rs::user_themes::{import_theme, load_theme_file};
+
+const BASE16: &str = r#"
+scheme: "Company Dark"
+author: "Design Team"
+base00: "1d1f21" # background
+base01: "282a2e"
+base02: "373b41"
+base03: "969896"
+base04: "b4b7b4"
+base05: "c5c8c6"
+base06: "e0e0e0"
+base07: "ffffff"
+base08: "cc6666"
+base09: "de935f"
+base0A: "f0c674"
+base0B: "b5bd68"
+base0C: "8abeb7"
+base0D: "81a2be"
+base0E: "b294bb"
+base0F: "a3685a"
+"#;
+
+const BASE24: &str = r##"
+system: "base24"
+name: "Night Owl"
+variant: "dark"
+palette:
+  base00: "#011627"
+  base01: "#0b2942"
+  base02: "#1d3b53"
+  base03: "#5f7e97"
+  base04: "#d6deeb"
+  base05: "#d6deeb"
+  base06: "#ffffff"
+  base07: "#ffffff"
+  base08: "#ef5350"
+  base09: "#f78c6c"
+  base0A: "#ffcb8b"
+  base0B: "#22da6e"
+  base0C: "#21c7a8"
+  base0D: "#82aaff"
+  base0E: "#c792ea"
+  base0F: "#ff5874"
+  base10: "#010e1a"
+  base11: "#010a12"
+  base12: "#ef5350"
+  base13: "#ffeb95"
+  base14: "#22da6e"
+  base15: "#7fdbca"
+  base16: "#82aaff"
+  base17: "#c792ea"
+"##;
+
+const ALACRITTY: &str = r##"
+[colors.primary]
+background = "#282c34"
+foreground = "#abb2bf"
+
+[colors.selection]
+background = "CellForeground"
+
+[colors.normal]
+black = "#1e2127"
+red = "#e06c75"
+green = "#98c379"
+yellow = "#d19a66"
+blue = "#61afef"
+magenta = "#c678dd"
+cyan = "#56b6c2"
+white = "#828791"
+
+[colors.bright]
+black = "#5c6370"
+red = "0xe06c75"
+"##;
+
+const KITTY: &str = "
+## name: Kitty Scheme
+background #222222
+foreground #dddddd
+selection_background #444444
+color0 #000000
+color1 #aa0000
+color2 #00aa00
+color3 #aaaa00
+color4 #0000aa
+color5 #aa00aa
+color6 #00aaaa
+color7 #aaaaaa
+color8 #555555
+color9 #ff5555
+";
+
+const WEZTERM: &str = r##"
+[colors]
+foreground = "#c0caf5"
+background = "#1a1b26"
+selection_bg = "#33467c"
+ansi = ["#15161e", "#f7768e", "#9ece6a", "#e0af68", "#7aa2f7", "#bb9af7", "#7dcfff", "#a9b1d6"]
+brights = ["#414868", "#ff899d", "#9fe044", "#faba4a", "#8db0ff", "#c7a9ff", "#a4daff", "#c0caf5"]
+
+[metadata]
+name = "Tokyo Night (WezTerm)"
+"##;
+
+#[test]
+fn detects_formats() {
+    let detect = |file: &str, contents: &str| detect_format(Path::new(file), contents).unwrap();
+    assert_eq!(detect("a.yaml", BASE16), SchemeFormat::Base16);
+    assert_eq!(detect("a.toml", ALACRITTY), SchemeFormat::Alacritty);
+    assert_eq!(detect("a.conf", KITTY), SchemeFormat::Kitty);
+    assert_eq!(detect("a.toml", WEZTERM), SchemeFormat::WezTerm);
+    assert_eq!(detect("scheme", BASE16), SchemeFormat::Base16);
+    assert!(detect_format(Path::new("a.toml"), "x = 1").is_err());
+}
+
+#[test]
+fn normalizes_hex_colors() {
+    assert_eq!(normalize_hex("\"ABC123\"").unwrap(), "#abc123");
+    assert_eq!(normalize_hex("0xabc123").unwrap(), "#abc123");
+    assert_eq!(normalize_hex("#abc123").unwrap(), "#abc123");
+    assert!(normalize_hex("#abc").is_err());
+}
+
+#[test]
+fn base16_follows_the_styling_guidelines() {
+    let file = parse_base16(BASE16).unwrap();
+    assert_eq!(file.name.as_deref(), Some("Company Dark"));
+    assert_eq!(file.background.as_deref(), Some("#1d1f21"));
+    let palette = file.palette.unwrap();
+    assert_eq!(palette.accent1.as_deref(), Some("#81a2be"));
+    assert_eq!(palette.accent2.as_deref(), Some("#cc6666"));
+    assert_eq!(palette.warm.as_deref(), Some("#de935f"));
+    assert_eq!(palette.overlay.as_deref(), Some("#969896"));
+    assert_eq!(palette.surface.as_deref(), Some("#373b41"));
+    assert_eq!(palette.base.as_deref(), Some("#1d1f21"));
+}
+
+#[test]
+fn base24_palette_block() {
+    let file = parse_base16(BASE24).unwrap();
+    assert_eq!(file.name.as_deref(), Some("Night Owl"));
+    assert_eq!(file.palette.unwrap().purple.as_deref(), Some("#c792ea"));
+}
+
+#[test]
+fn base16_reports_missing_slots() {
+    let err = parse_base16("scheme: x\nbase00: \"000000\"\n").unwrap_err();
+    assert!(err.to_string().contains("missing base0D"), "{}", err);
+}
+
+#[test]
+fn terminal_schemes_map_ansi_colors() {
+    let alacritty = parse_alacritty(ALACRITTY).unwrap().palette.unwrap();
+    assert_eq!(alacritty.accent1.as_deref(), Some("#61afef"));
+    assert_eq!(alacritty.warm.as_deref(), Some("#e06c75"));
+    // No usable selection color, bright black instead
+    assert_eq!(alacritty.surface.as_deref(), Some("#5c6370"));
+    assert_eq!(alacritty.text.as_deref(), Some("#abb2bf"));
+
+    let kitty = parse_kitty(KITTY).unwrap();
+    assert_eq!(kitty.name.as_deref(), Some("Kitty Scheme"));
+    let palette = kitty.palette.unwrap();
+    assert_eq!(palette.surface.as_deref(), Some("#444444"));
+    assert_eq!(palette.purple.as_deref(), Some("#aa00aa"));
+
+    let wezterm = parse_wezterm(WEZTERM).unwrap();
+    assert_eq!(wezterm.name.as_deref(), Some("Tokyo Night (WezTerm)"));
+    assert_eq!(wezterm.palette.unwrap().overlay.as_deref(), Some("#414868"));
+}
+
+#[test]
+fn theme_file_names() {
+    assert_eq!(
+        theme_file_name("Tokyo Night (WezTerm)"),
+        "tokyo-night-wezterm.toml"
+    );
+    assert_eq!(theme_file_name("Company Dark"), "company-dark.toml");
+}
+
+#[test]
+fn import_writes_a_loadable_theme() {
+    let tmp = TempDir::new("theme-import").unwrap();
+    let scheme = tmp.path().join("company.yaml");
+    fs::write(&scheme, BASE16).unwrap();
+    let themes_dir = tmp.path().join("themes");
+    let mut args = ThemeImportArgs {
+        file: scheme,
+        ..Default::default()
+    };
+
+    let path = import_theme(&themes_dir, &args).unwrap();
+    assert_eq!(path, themes_dir.join("company-dark.toml"));
+    let theme = load_theme_file(&path, &Theme::all()).unwrap();
+    assert_eq!(theme.name, "Company Dark");
+    assert_eq!(theme.title_try, Color::Rgb(0x81, 0xa2, 0xbe));
+    assert_eq!(theme.background, Some(Color::Rgb(0x1d, 0x1f, 0x21)));
+
+    let err = import_theme(&themes_dir, &args).unwrap_err();
+    assert!(err.to_string().contains("--force"), "{}", err);
+    args.force = true;
+    args.name = Some("Renamed".to_string());
+    let path = import_theme(&themes_dir, &args).unwrap();
+    assert_eq!(path, themes_dir.join("renamed.toml"));
+}
@@ -0,0 +1,89 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use try_rs::color_depth::ColorDepth;
+use try_rs::theme_preview::*;
+use try_rs::themes::Theme;
+use try_rs::user_themes::{ThemeFile, export_theme, find_theme};
+
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| *c == 'm');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[test]
+fn buffer_is_printed_with_escapes() {
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 2));
+    buffer.set_string(0, 0, "ab", Style::default().fg(Color::Rgb(1, 2, 3)));
+    buffer.set_string(0, 1, "c", Style::default().bg(Color::Indexed(42)));
+    let ansi = buffer_to_ansi(&buffer);
+    assert_eq!(
+        ansi,
+        "\x1b[0;38;2;1;2;3mab\x1b[0m \x1b[0m\n\x1b[0;48;5;42mc\x1b[0m  \x1b[0m\n"
+    );
+    assert_eq!(strip_ansi(&ansi), "ab \nc  \n");
+}
+
+#[test]
+fn list_groups_dark_and_light_themes() {
+    let list = strip_ansi(&theme_list(&Theme::all()));
+    let dark = list.find("Dark:").unwrap();
+    let light = list.find("Light:").unwrap();
+    let nord = list.find("Nord").unwrap();
+    let latte = list.find("Catppuccin Latte").unwrap();
+    assert!(dark < nord && nord < light && light < latte);
+    assert!(list.contains("██"));
+}
+
+#[test]
+fn swatches_follow_the_color_depth() {
+    let theme = Theme::nord().with_color_depth(ColorDepth::Ansi256);
+    assert!(theme_swatches(&theme).contains("38;5;"));
+    assert!(theme_swatches(&Theme::nord()).contains("38;2;"));
+}
+
+#[test]
+fn sample_shows_the_main_layout() {
+    let theme = Theme::dracula();
+    let buffer = sample_buffer(&theme, 100, 20);
+    let text = strip_ansi(&buffer_to_ansi(&buffer));
+    for title in ["Search/New", "Disk", "Folders", "Preview", "Legends"] {
+        assert!(text.contains(title), "missing {}", title);
+    }
+    assert_eq!(text.lines().count(), 20);
+    assert_eq!(buffer[(0, 0)].bg, theme.background.unwrap());
+}
+
+#[test]
+fn export_keeps_every_color() {
+    for theme in [Theme::tron(), Theme::default_theme()] {
+        let exported = export_theme(&theme).unwrap();
+        let file: ThemeFile = toml::from_str(&exported).unwrap();
+        assert_eq!(file.colors.len(), Theme::FIELDS.len());
+        let reloaded = file.to_theme("unused", &Theme::all()).unwrap();
+        assert_eq!(reloaded.name, theme.name);
+        assert_eq!(reloaded.background, theme.background);
+        for field in Theme::FIELDS {
+            assert_eq!(reloaded.color(field), theme.color(field), "{}", field);
+        }
+    }
+}
+
+#[test]
+fn themes_are_found_ignoring_case() {
+    let themes = Theme::all();
+    assert_eq!(
+        find_theme(&themes, "tokyo night").unwrap().name,
+        "Tokyo Night"
+    );
+    let err = find_theme(&themes, "nope").unwrap_err().to_string();
+    assert!(err.contains("unknown theme 'nope'"), "{}", err);
+}
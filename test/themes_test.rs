@@ -1,5 +1,6 @@
 use ratatui::style::Color;
-use try_rs::themes::Theme;
+use try_rs::color_depth::ColorDepth;
+use try_rs::themes::{PALETTE_ROLES, Theme};
 
 #[test]
 fn all_themes_have_unique_names() {
@@ -79,6 +80,9 @@ fn known_themes_exist() {
         "Black & White",
         "Matrix",
         "Tron",
+        "Catppuccin Latte",
+        "Solarized Light",
+        "GitHub Light",
     ];
     for name in expected {
         assert!(names.contains(&name.to_string()), "missing theme '{}'", name);
@@ -87,7 +91,7 @@ fn known_themes_exist() {
 
 #[test]
 fn theme_count() {
-    assert_eq!(Theme::all().len(), 16);
+    assert_eq!(Theme::all().len(), 19);
 }
 
 #[test]
@@ -124,4 +128,54 @@ fn each_theme_constructor_does_not_panic() {
     let _ = Theme::black_and_white();
     let _ = Theme::matrix();
     let _ = Theme::tron();
+    let _ = Theme::catppuccin_latte();
+    let _ = Theme::solarized_light();
+    let _ = Theme::github_light();
+}
+
+#[test]
+fn light_themes_are_light() {
+    let light: Vec<String> = Theme::all()
+        .into_iter()
+        .filter(|t| t.is_light())
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(
+        light,
+        ["Catppuccin Latte", "Solarized Light", "GitHub Light"]
+    );
+    assert!(!Theme::default().is_light());
+    // Kept when the colors are reduced, e.g. under NO_COLOR
+    assert!(
+        Theme::github_light()
+            .with_color_depth(ColorDepth::NoColor)
+            .is_light()
+    );
+}
+
+#[test]
+fn palette_roles_match_from_palette() {
+    // Catppuccin Mocha is built from its palette without overrides
+    let mut theme = Theme::catppuccin_mocha();
+    for (role, fields) in PALETTE_ROLES {
+        let first = *theme.color_mut(fields[0]).unwrap();
+        for field in *fields {
+            let color = theme.color_mut(field).copied();
+            assert_eq!(color, Some(first), "{} of {}", field, role);
+        }
+    }
+}
+
+#[test]
+fn every_field_is_accessible_by_name() {
+    let mut theme = Theme::default();
+    for field in Theme::FIELDS {
+        *theme.color_mut(field).unwrap() = Color::Rgb(1, 2, 3);
+    }
+    assert_eq!(theme.search_border, Color::Rgb(1, 2, 3));
+    assert_eq!(theme.icon_file, Color::Rgb(1, 2, 3));
+    assert!(theme.color_mut("name").is_none());
+    assert!(theme.set_role("accent1", Color::Red));
+    assert_eq!(theme.title_try, Color::Red);
+    assert!(!theme.set_role("accent3", Color::Red));
 }
@@ -0,0 +1,133 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use tempdir::TempDir;
+use try_rs::trash::*;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn trash_and_restore_folder() {
+    let tmp = TempDir::new("trash-folder").unwrap();
+    let tries = tmp.path();
+    fs::create_dir(tries.join("experiment")).unwrap();
+    fs::write(tries.join("experiment").join("notes.txt"), "keep me").unwrap();
+
+    let item = move_to_trash(tries, "experiment").unwrap();
+    assert!(!tries.join("experiment").exists());
+    assert!(trash_dir(tries).join(&item.id).join("notes.txt").exists());
+
+    let items = list_trash(tries);
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].original_name, "experiment");
+    assert_eq!(items[0].id, item.id);
+    assert!(items[0].worktree.is_none());
+    assert!(items[0].deleted_at().is_some());
+
+    restore(tries, &items[0]).unwrap();
+    assert_eq!(
+        fs::read_to_string(tries.join("experiment").join("notes.txt")).unwrap(),
+        "keep me"
+    );
+    assert!(list_trash(tries).is_empty());
+}
+
+#[test]
+fn restore_refuses_to_overwrite() {
+    let tmp = TempDir::new("trash-overwrite").unwrap();
+    let tries = tmp.path();
+    fs::create_dir(tries.join("dup")).unwrap();
+    let item = move_to_trash(tries, "dup").unwrap();
+    fs::create_dir(tries.join("dup")).unwrap();
+
+    assert!(restore(tries, &item).is_err());
+    assert_eq!(list_trash(tries).len(), 1);
+}
+
+#[test]
+fn same_name_can_be_trashed_twice() {
+    let tmp = TempDir::new("trash-twice").unwrap();
+    let tries = tmp.path();
+    fs::create_dir(tries.join("again")).unwrap();
+    let first = move_to_trash(tries, "again").unwrap();
+    fs::create_dir(tries.join("again")).unwrap();
+    let second = move_to_trash(tries, "again").unwrap();
+
+    assert_ne!(first.id, second.id);
+    assert_eq!(list_trash(tries).len(), 2);
+    assert!(find_trash_item(tries, "again").is_some());
+    assert_eq!(find_trash_item(tries, &first.id).unwrap().id, first.id);
+}
+
+#[test]
+fn empty_trash_respects_age() {
+    let tmp = TempDir::new("trash-empty").unwrap();
+    let tries = tmp.path();
+    fs::create_dir(tries.join("fresh")).unwrap();
+    move_to_trash(tries, "fresh").unwrap();
+
+    let removed = empty_trash(tries, Some(Duration::from_secs(86400))).unwrap();
+    assert_eq!(removed, 0);
+    assert_eq!(list_trash(tries).len(), 1);
+
+    let removed = empty_trash(tries, None).unwrap();
+    assert_eq!(removed, 1);
+    assert!(list_trash(tries).is_empty());
+    assert_eq!(fs::read_dir(trash_dir(tries)).unwrap().count(), 0);
+}
+
+#[test]
+fn worktree_is_reregistered_on_restore() {
+    let tmp = TempDir::new("trash-worktree").unwrap();
+    let repo = tmp.path().join("repo");
+    let tries = tmp.path().join("tries");
+    fs::create_dir_all(&repo).unwrap();
+    fs::create_dir_all(&tries).unwrap();
+    git(&repo, &["init", "-q"]);
+    git(&repo, &["config", "user.email", "test@test.internal"]);
+    git(&repo, &["config", "user.name", "Test"]);
+    fs::write(repo.join("file.txt"), "v1").unwrap();
+    git(&repo, &["add", "."]);
+    git(&repo, &["commit", "-q", "-m", "init"]);
+    let worktree = tries.join("feature");
+    git(
+        &repo,
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "feature",
+            &worktree.to_string_lossy(),
+        ],
+    );
+    fs::write(worktree.join("file.txt"), "uncommitted change").unwrap();
+
+    let item = move_to_trash(&tries, "feature").unwrap();
+    let info = item.worktree.clone().expect("worktree info is recorded");
+    assert_eq!(info.branch.as_deref(), Some("feature"));
+    assert!(!git(&repo, &["worktree", "list"]).contains("feature"));
+
+    restore(&tries, &item).unwrap();
+    assert!(git(&repo, &["worktree", "list"]).contains("[feature]"));
+    assert_eq!(
+        fs::read_to_string(worktree.join("file.txt")).unwrap(),
+        "uncommitted change"
+    );
+    assert!(git(&worktree, &["status", "--porcelain"]).contains("file.txt"));
+}
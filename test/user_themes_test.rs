@@ -0,0 +1,124 @@
+use ratatui::style::Color;
+use std::fs;
+
+use tempdir::TempDir;
+use try_rs::themes::Theme;
+use try_rs::user_themes::*;
+
+fn write_theme(dir: &TempDir, file: &str, contents: &str) {
+    fs::write(dir.path().join(file), contents).unwrap();
+}
+
+fn theme_named<'a>(themes: &'a [Theme], name: &str) -> &'a Theme {
+    themes.iter().find(|t| t.name == name).unwrap()
+}
+
+#[test]
+fn palette_roles_set_their_fields() {
+    let file: ThemeFile = toml::from_str(
+        r##"
+background = "#101010"
+
+[palette]
+accent1 = "#ff0000"
+overlay = "blue"
+"##,
+    )
+    .unwrap();
+    let theme = file.to_theme("company", &Theme::all()).unwrap();
+
+    assert_eq!(theme.name, "company");
+    assert_eq!(theme.background, Some(Color::Rgb(16, 16, 16)));
+    assert_eq!(theme.title_try, Color::Rgb(255, 0, 0));
+    assert_eq!(theme.preview_title, Color::Rgb(255, 0, 0));
+    assert_eq!(theme.folder_border, Color::Blue);
+    assert_eq!(theme.helpers_colors, Color::Blue);
+    // Roles not in the file come from the Default theme
+    assert_eq!(theme.title_rs, Theme::default().title_rs);
+}
+
+#[test]
+fn colors_override_fields_after_the_palette() {
+    let file: ThemeFile = toml::from_str(
+        r##"
+name = "Nord Tweaked"
+inherits = "nord"
+background = "none"
+
+[palette]
+accent1 = "#ff0000"
+
+[colors]
+preview_title = "#00ff00"
+"##,
+    )
+    .unwrap();
+    let theme = file.to_theme("ignored", &Theme::all()).unwrap();
+    let nord = theme_named(&Theme::all(), "Nord").clone();
+
+    assert_eq!(theme.name, "Nord Tweaked");
+    assert_eq!(theme.background, None);
+    assert_eq!(theme.title_try, Color::Rgb(255, 0, 0));
+    assert_eq!(theme.preview_title, Color::Rgb(0, 255, 0));
+    assert_eq!(theme.search_title, nord.search_title);
+}
+
+#[test]
+fn invalid_values_are_explained() {
+    let builtins = Theme::all();
+    let err = |contents: &str| {
+        let file: ThemeFile = toml::from_str(contents).unwrap();
+        format!("{:#}", file.to_theme("bad", &builtins).unwrap_err())
+    };
+
+    assert!(
+        err("[palette]\naccent1 = \"#12\"").contains("invalid color '#12' for palette.accent1")
+    );
+    assert!(err("[colors]\ntitle = \"red\"").contains("unknown field 'colors.title'"));
+    assert!(err("inherits = \"Solarized\"").contains("unknown theme 'Solarized' in inherits"));
+    assert!(toml::from_str::<ThemeFile>("[palette]\naccentt = \"red\"").is_err());
+}
+
+#[test]
+fn load_themes_adds_user_themes_and_reports_bad_files() {
+    let dir = TempDir::new("user-themes").unwrap();
+    write_theme(&dir, "company.toml", "[palette]\naccent1 = \"#112233\"\n");
+    write_theme(&dir, "broken.toml", "[palette]\naccent1 = \"nope\"\n");
+    write_theme(&dir, "notes.txt", "not a theme");
+
+    let (themes, errors) = load_themes(dir.path());
+
+    assert_eq!(themes.len(), Theme::all().len() + 1);
+    assert_eq!(themes.last().unwrap().name, "company");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("broken.toml"), "{}", errors[0]);
+    assert!(errors[0].contains("invalid color 'nope'"), "{}", errors[0]);
+}
+
+#[test]
+fn user_theme_replaces_builtin_of_the_same_name() {
+    let dir = TempDir::new("user-themes").unwrap();
+    write_theme(
+        &dir,
+        "dracula.toml",
+        "name = \"Dracula\"\ninherits = \"Dracula\"\n[colors]\ntitle_try = \"#000001\"\n",
+    );
+    write_theme(&dir, "zz.toml", "name = \"Dracula\"\n");
+
+    let (themes, errors) = load_themes(dir.path());
+
+    assert_eq!(themes.len(), Theme::all().len());
+    assert_eq!(
+        theme_named(&themes, "Dracula").title_try,
+        Color::Rgb(0, 0, 1)
+    );
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].contains("already defines 'Dracula'"));
+}
+
+#[test]
+fn missing_dir_has_only_builtins() {
+    let (themes, errors) = load_themes(std::path::Path::new("/nonexistent/try-rs/themes"));
+    assert_eq!(themes.len(), Theme::all().len());
+    assert!(errors.is_empty());
+}
@@ -269,3 +269,56 @@ fn get_free_disk_space_mb_returns_none_for_invalid() {
     let space = get_free_disk_space_mb(Path::new("/nonexistent/path/xyz"));
     assert!(space.is_none());
 }
+
+#[test]
+fn glob_match_literal_and_wildcards() {
+    assert!(glob_match("Cargo.toml", "Cargo.toml"));
+    assert!(!glob_match("Cargo.toml", "Cargo.lock"));
+    assert!(glob_match("*.csproj", "App.csproj"));
+    assert!(!glob_match("*.csproj", "App.csproj.user"));
+    assert!(glob_match("settings.gradle*", "settings.gradle.kts"));
+    assert!(glob_match("?.txt", "a.txt"));
+    assert!(!glob_match("?.txt", "ab.txt"));
+    assert!(glob_match("*", ""));
+    assert!(glob_match("a*b*c", "axxbyyc"));
+}
+
+#[test]
+fn move_try_folder_moves_and_refuses_overwrite() {
+    let tmp = TempDir::new("move-folder").unwrap();
+    let src = tmp.path().join("experiment");
+    let other_root = tmp.path().join("other");
+    std::fs::create_dir_all(&src).unwrap();
+    std::fs::create_dir_all(other_root.join("taken")).unwrap();
+    std::fs::write(src.join("main.rs"), "fn main() {}").unwrap();
+
+    move_try_folder(&src, &other_root.join("experiment")).unwrap();
+    assert!(!src.exists());
+    assert!(other_root.join("experiment").join("main.rs").exists());
+
+    let err = move_try_folder(&other_root.join("experiment"), &other_root.join("taken"));
+    assert!(err.unwrap_err().contains("already exists"));
+}
+
+#[test]
+fn move_dir_copies_across_file_systems() {
+    let tmp = TempDir::new("move-exdev").unwrap();
+    let src = tmp.path().join("experiment");
+    let dest = tmp.path().join("other").join("experiment");
+    std::fs::create_dir_all(src.join("src")).unwrap();
+    std::fs::create_dir_all(tmp.path().join("other")).unwrap();
+    std::fs::write(src.join("src").join("main.rs"), "fn main() {}").unwrap();
+
+    let cross_device = |_: &Path, _: &Path| Err(std::io::ErrorKind::CrossesDevices.into());
+    move_dir(&src, &dest, &cross_device).unwrap();
+    assert!(!src.exists());
+    assert_eq!(
+        std::fs::read_to_string(dest.join("src").join("main.rs")).unwrap(),
+        "fn main() {}"
+    );
+
+    // Other errors are passed on and leave the folder where it was
+    let denied = |_: &Path, _: &Path| Err(std::io::ErrorKind::PermissionDenied.into());
+    assert!(move_dir(&dest, &src, &denied).is_err());
+    assert!(dest.join("src").join("main.rs").exists());
+}